@@ -0,0 +1,140 @@
+use std::cmp::Ordering;
+use rand::Rng;
+use crate::population::{Encoding, Population};
+
+/// Objetivo que só sabe comparar dois indivíduos (ex. um juiz humano ou uma
+/// partida de jogo). `Greater` significa que `a` é melhor que `b`.
+pub trait ComparativeObjective<E: Encoding> {
+    fn compare<R: Rng + ?Sized>(&self, a: &E, b: &E, rng: &mut R) -> Ordering;
+}
+
+/// Torneio binário decidido pela comparação; em caso de empate vence o
+/// primeiro sorteado. Retorna o índice do vencedor.
+pub fn comparison_tournament<E, O, R>(objective: &O, pop: &Population<E>, rng: &mut R) -> usize
+where
+    E: Encoding,
+    O: ComparativeObjective<E> + ?Sized,
+    R: Rng + ?Sized,
+{
+    let individuals = pop.get_individuals();
+    assert!(!individuals.is_empty(), "tournament on an empty population");
+    let (a, b) = (rng.gen_range(0..individuals.len()), rng.gen_range(0..individuals.len()));
+    match objective.compare(&individuals[a], &individuals[b], rng) {
+        Ordering::Less => b,
+        _ => a,
+    }
+}
+
+/// Posições sintetizadas a partir de pontos (vitória 1, empate 1/2):
+/// `ranks[i]` é a posição do indivíduo `i`, 0 para o melhor. Empates em
+/// pontos são resolvidos pelo menor índice.
+fn ranks_from_points(points: &[f64]) -> Vec<usize> {
+    let mut order: Vec<usize> = (0..points.len()).collect();
+    order.sort_by(|&a, &b| points[b].total_cmp(&points[a]).then(a.cmp(&b)));
+    let mut ranks = vec![0; points.len()];
+    order.into_iter().enumerate().for_each(|(rank, index)| ranks[index] = rank);
+    ranks
+}
+
+fn play<E, O, R>(objective: &O, individuals: &[E], (a, b): (usize, usize), points: &mut [f64], rng: &mut R)
+where
+    E: Encoding,
+    O: ComparativeObjective<E> + ?Sized,
+    R: Rng + ?Sized,
+{
+    match objective.compare(&individuals[a], &individuals[b], rng) {
+        Ordering::Greater => points[a] += 1.0,
+        Ordering::Less => points[b] += 1.0,
+        Ordering::Equal => { points[a] += 0.5; points[b] += 0.5 },
+    }
+}
+
+/// Todos contra todos: `n(n-1)/2` comparações.
+pub fn round_robin_ranks<E, O, R>(objective: &O, pop: &Population<E>, rng: &mut R) -> Vec<usize>
+where
+    E: Encoding,
+    O: ComparativeObjective<E> + ?Sized,
+    R: Rng + ?Sized,
+{
+    let individuals = pop.get_individuals();
+    let mut points = vec![0.0f64; individuals.len()];
+    for a in 0..individuals.len() {
+        for b in a + 1..individuals.len() {
+            play(objective, individuals, (a, b), &mut points, rng);
+        }
+    }
+    ranks_from_points(&points)
+}
+
+/// Sistema suíço: a cada rodada os indivíduos são ordenados pelos pontos e
+/// emparelhados com o vizinho na ordem (com número ímpar, o último folga).
+/// Com `ceil(log2 n)` rodadas o melhor de uma comparação transitiva é o
+/// único invicto.
+pub fn swiss_ranks<E, O, R>(objective: &O, pop: &Population<E>, rounds: usize, rng: &mut R) -> Vec<usize>
+where
+    E: Encoding,
+    O: ComparativeObjective<E> + ?Sized,
+    R: Rng + ?Sized,
+{
+    let individuals = pop.get_individuals();
+    let mut points = vec![0.0f64; individuals.len()];
+    for _ in 0..rounds {
+        let mut standings: Vec<usize> = (0..individuals.len()).collect();
+        standings.sort_by(|&a, &b| points[b].total_cmp(&points[a]).then(a.cmp(&b)));
+        for pair in standings.chunks_exact(2) {
+            play(objective, individuals, (pair[0], pair[1]), &mut points, rng);
+        }
+    }
+    ranks_from_points(&points)
+}
+
+#[cfg(test)]
+mod comparative_tests {
+    use rand::{SeedableRng, rngs::StdRng};
+    use crate::population::BinaryEncoding;
+    use super::*;
+
+    struct OneMaxComparator;
+    impl ComparativeObjective<BinaryEncoding> for OneMaxComparator {
+        fn compare<R: Rng + ?Sized>(&self, a: &BinaryEncoding, b: &BinaryEncoding, _rng: &mut R) -> Ordering {
+            a.count_ones().cmp(&b.count_ones())
+        }
+    }
+
+    fn population(ones: &[usize]) -> Population<BinaryEncoding> {
+        Population(ones.iter().map(|&n| BinaryEncoding::from_bools(&(0..8).map(|i| i < n).collect::<Vec<_>>())).collect())
+    }
+
+    #[test]
+    fn test_round_robin_ranks_follow_transitive_order() {
+        let mut rng = StdRng::seed_from_u64(0);
+        let ranks = round_robin_ranks(&OneMaxComparator, &population(&[3, 7, 1, 5]), &mut rng);
+        assert_eq!(ranks, vec![2, 0, 3, 1]);
+    }
+
+    #[test]
+    fn test_swiss_ranks_find_the_best() {
+        let mut rng = StdRng::seed_from_u64(0);
+        let ones = [2, 6, 0, 4, 7, 1, 5, 3];
+        let ranks = swiss_ranks(&OneMaxComparator, &population(&ones), 3, &mut rng);
+
+        let mut sorted = ranks.clone();
+        sorted.sort();
+        assert_eq!(sorted, (0..8).collect::<Vec<_>>());
+        assert_eq!(ranks[4], 0);
+        assert_eq!(ranks[2], 7);
+
+        // a posição é consistente com a ordem nos confrontos diretos
+        let best = ranks.iter().position(|&rank| rank == 0).unwrap();
+        assert!((0..8).all(|i| ones[best] >= ones[i]));
+    }
+
+    #[test]
+    fn test_comparison_tournament_prefers_better() {
+        let mut rng = StdRng::seed_from_u64(5);
+        let pop = population(&[0, 8]);
+        let wins = (0..1000).filter(|_| comparison_tournament(&OneMaxComparator, &pop, &mut rng) == 1).count();
+        // o indivíduo 1 vence sempre que é sorteado: P = 3/4
+        assert!((700..=800).contains(&wins));
+    }
+}
@@ -0,0 +1,858 @@
+use std::cmp::Ordering;
+use std::collections::HashSet;
+use std::fmt;
+use rand::{Rng, RngCore};
+use rand::seq::SliceRandom;
+use crate::alg::objectives::{Formula, Literal};
+use crate::population::{BinaryEncoding, CategoricalEncoding, Genome, IntPermEncoding};
+
+/// Crossover que respeita a estrutura das cláusulas: as variáveis são
+/// agrupadas pelo grafo de interação (variáveis que aparecem numa mesma
+/// cláusula são ligadas) e o filho herda cada grupo inteiro de um dos pais.
+/// Grupos são unidos só enquanto o tamanho não passa de `max_cluster_size`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ClauseAwareCrossover {
+    clusters: Vec<Vec<usize>>,
+    num_vars: usize,
+}
+
+impl ClauseAwareCrossover {
+    pub fn new(formula: &Formula, max_cluster_size: usize) -> Self {
+        let num_vars = formula.get_num_vars() as usize;
+        let mut parent: Vec<usize> = (0..num_vars).collect();
+        let mut size = vec![1; num_vars];
+
+        fn find(parent: &mut [usize], mut var: usize) -> usize {
+            while parent[var] != var {
+                parent[var] = parent[parent[var]];
+                var = parent[var];
+            }
+            var
+        }
+
+        for clause in formula.get_clauses() {
+            let vars: Vec<usize> = clause.literals()
+                .iter()
+                .map(|literal| match literal { Literal::Var(var) | Literal::NegatedVar(var) => *var as usize - 1 })
+                .collect();
+            for pair in vars.windows(2) {
+                let (a, b) = (find(&mut parent, pair[0]), find(&mut parent, pair[1]));
+                if a != b && size[a] + size[b] <= max_cluster_size {
+                    let (root, child) = if size[a] >= size[b] { (a, b) } else { (b, a) };
+                    parent[child] = root;
+                    size[root] += size[child];
+                }
+            }
+        }
+
+        // grupos na ordem da primeira variável de cada um
+        let mut cluster_of_root = vec![usize::MAX; num_vars];
+        let mut clusters: Vec<Vec<usize>> = Vec::new();
+        for var in 0..num_vars {
+            let root = find(&mut parent, var);
+            if cluster_of_root[root] == usize::MAX {
+                cluster_of_root[root] = clusters.len();
+                clusters.push(Vec::new());
+            }
+            clusters[cluster_of_root[root]].push(var);
+        }
+
+        ClauseAwareCrossover { clusters, num_vars }
+    }
+
+    /// Índices (a partir de 0) das variáveis de cada grupo.
+    pub fn clusters(&self) -> &[Vec<usize>] {
+        &self.clusters
+    }
+
+    /// Cada grupo vem de `a` ou de `b` com probabilidade 1/2.
+    pub fn crossover<R: Rng + ?Sized>(&self, a: &BinaryEncoding, b: &BinaryEncoding, rng: &mut R) -> BinaryEncoding {
+        assert!(a.len() == self.num_vars && b.len() == self.num_vars, "parents must have one bit per formula variable");
+
+        let mut child = a.clone();
+        for cluster in &self.clusters {
+            if rng.gen_bool(0.5) {
+                cluster.iter().for_each(|&var| child.set(var, b.get(var)));
+            }
+        }
+        child
+    }
+}
+
+/// Recombinação de vários pais em um filho. `parents` é quantos pais a
+/// seleção deve fornecer por filho; `cross` recebe exatamente esse número.
+/// O gerador é `dyn RngCore` para que o operador possa ser guardado como
+/// `Box<dyn MultiCrossover<E>>`.
+pub trait MultiCrossover<E> {
+    fn parents(&self) -> usize;
+    fn cross(&self, parents: &[&E], rng: &mut dyn RngCore) -> E;
+}
+
+fn check_parent_count(parents: usize) -> Result<(), CrossoverParameterError> {
+    if parents == 0
+    { return Err(CrossoverParameterError::ZeroParents) }
+    Ok(())
+}
+
+/// Quantos pais têm cada bit ligado.
+fn ones_per_bit(parents: &[&BinaryEncoding], expected: usize) -> Vec<usize> {
+    assert_eq!(parents.len(), expected, "crossover expects a different number of parents");
+    let len = parents[0].len();
+    assert!(parents.iter().all(|parent| parent.len() == len), "parents must have the same length");
+    (0..len).map(|bit| parents.iter().filter(|parent| parent.get(bit)).count()).collect()
+}
+
+/// Cada bit do filho é o da maioria dos pais; empates (número par de pais)
+/// são decididos ao acaso.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MajorityVoteCrossover {
+    parents: usize,
+}
+
+impl MajorityVoteCrossover {
+    pub fn new(parents: usize) -> Result<Self, CrossoverParameterError> {
+        check_parent_count(parents)?;
+        Ok(MajorityVoteCrossover { parents })
+    }
+}
+
+impl MultiCrossover<BinaryEncoding> for MajorityVoteCrossover {
+    fn parents(&self) -> usize {
+        self.parents
+    }
+
+    fn cross(&self, parents: &[&BinaryEncoding], rng: &mut dyn RngCore) -> BinaryEncoding {
+        let k = self.parents;
+        let bits: Vec<bool> = ones_per_bit(parents, k)
+            .into_iter()
+            .map(|ones| match (2 * ones).cmp(&k) {
+                Ordering::Greater => true,
+                Ordering::Less => false,
+                Ordering::Equal => rng.gen_bool(0.5),
+            })
+            .collect();
+        BinaryEncoding::from_bools(&bits)
+    }
+}
+
+/// Bits em que todos os pais concordam são mantidos; os disputados são
+/// sorteados.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ConsensusCrossover {
+    parents: usize,
+}
+
+impl ConsensusCrossover {
+    pub fn new(parents: usize) -> Result<Self, CrossoverParameterError> {
+        check_parent_count(parents)?;
+        Ok(ConsensusCrossover { parents })
+    }
+}
+
+impl MultiCrossover<BinaryEncoding> for ConsensusCrossover {
+    fn parents(&self) -> usize {
+        self.parents
+    }
+
+    fn cross(&self, parents: &[&BinaryEncoding], rng: &mut dyn RngCore) -> BinaryEncoding {
+        let k = self.parents;
+        let bits: Vec<bool> = ones_per_bit(parents, k)
+            .into_iter()
+            .map(|ones| match ones {
+                0 => false,
+                ones if ones == k => true,
+                _ => rng.gen_bool(0.5),
+            })
+            .collect();
+        BinaryEncoding::from_bools(&bits)
+    }
+}
+
+/// Crossover uniforme para genes categóricos: cada posição do primeiro filho
+/// vem do primeiro pai com probabilidade `ratio`, e o segundo filho recebe a
+/// outra escolha. Como cada gene é copiado de um pai na mesma posição, os
+/// filhos respeitam o alfabeto de cada gene.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct UniformCrossover {
+    ratio: f64,
+}
+
+impl UniformCrossover {
+    pub fn new(ratio: f64) -> Result<Self, CrossoverParameterError> {
+        if !(0.0..=1.0).contains(&ratio)
+        { return Err(CrossoverParameterError::RatioOutOfRange { ratio }) }
+        Ok(UniformCrossover { ratio })
+    }
+
+    pub fn ratio(&self) -> f64 {
+        self.ratio
+    }
+
+    pub fn crossover<R: Rng + ?Sized>(&self, a: &CategoricalEncoding, b: &CategoricalEncoding, rng: &mut R) -> (CategoricalEncoding, CategoricalEncoding) {
+        assert_eq!(a.len(), b.len(), "parents must have the same length");
+        let (mut first, mut second) = (a.clone(), b.clone());
+        for gene in 0..a.len() {
+            if !rng.gen_bool(self.ratio)
+            { std::mem::swap(&mut first.0[gene], &mut second.0[gene]) }
+        }
+        (first, second)
+    }
+}
+
+/// Arestas não direcionadas de uma rota cíclica, como `(menor, maior)`.
+fn tour_edges(tour: &[usize]) -> HashSet<(usize, usize)> {
+    (0..tour.len())
+        .map(|i| {
+            let (x, y) = (tour[i], tour[(i + 1) % tour.len()]);
+            (x.min(y), x.max(y))
+        })
+        .collect()
+}
+
+/// Distância de arestas entre duas rotas cíclicas: quantas arestas de `a`
+/// não estão em `b`.
+pub fn edge_distance(a: &IntPermEncoding, b: &IntPermEncoding) -> usize {
+    let b_edges = tour_edges(&b.0);
+    tour_edges(&a.0).iter().filter(|edge| !b_edges.contains(edge)).count()
+}
+
+/// Como os fragmentos são religados.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Reconnection {
+    Random,
+    /// Liga ao extremo de fragmento mais próximo segundo a matriz de distâncias.
+    NearestNeighbor(Vec<Vec<f64>>),
+}
+
+/// DPX: o filho mantém exatamente as arestas comuns aos pais e religa os
+/// fragmentos resultantes por arestas que não estão em nenhum dos pais, de
+/// modo que sua distância de arestas a cada pai é a distância entre os pais.
+///
+/// A religação é uma busca em profundidade com retrocesso sobre a ordem e a
+/// orientação dos fragmentos, exaustiva até [`Self::SEARCH_BUDGET`] passos
+/// (o que cobre com folga pais que diferem em poucas arestas). A distância só
+/// não é preservada quando nenhuma religação evita as arestas dos pais (ex.
+/// dois fragmentos, ou poucos fragmentos cujos extremos já são vizinhos em
+/// algum pai) ou quando o orçamento se esgota; nesses casos o filho é
+/// religado de forma gulosa, ainda contém todas as arestas comuns, e a
+/// distância pode ser menor.
+///
+/// O filho é rotacionado para começar pelo mesmo gene que `a`; como a rota é
+/// cíclica, as arestas não mudam, e uma cidade de partida fixada nos pais
+/// (ver [`crate::population::IntPermPopGenerator::new_with_layout`]) se
+/// mantém na posição 0.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DistancePreservingCrossover {
+    reconnection: Reconnection,
+}
+
+impl DistancePreservingCrossover {
+    /// Número máximo de extensões parciais visitadas pela busca.
+    pub const SEARCH_BUDGET: usize = 10_000;
+
+    /// Falha se a matriz de `Reconnection::NearestNeighbor` é vazia ou não é
+    /// quadrada.
+    pub fn new(reconnection: Reconnection) -> Result<Self, CrossoverParameterError> {
+        if let Reconnection::NearestNeighbor(distances) = &reconnection {
+            let n = distances.len();
+            if n == 0 || distances.iter().any(|row| row.len() != n)
+            { return Err(CrossoverParameterError::InvalidDistanceMatrix) }
+        }
+        Ok(DistancePreservingCrossover { reconnection })
+    }
+
+    pub fn reconnection(&self) -> &Reconnection {
+        &self.reconnection
+    }
+
+    pub fn crossover<R: Rng + ?Sized>(&self, a: &IntPermEncoding, b: &IntPermEncoding, rng: &mut R) -> IntPermEncoding {
+        assert_eq!(a.len(), b.len(), "parents must have the same length");
+        let n = a.len();
+        if let Reconnection::NearestNeighbor(distances) = &self.reconnection {
+            assert_eq!(distances.len(), n, "distance matrix must have one row per city of the parents");
+        }
+        let b_edges = tour_edges(&b.0);
+        let is_common = |x: usize, y: usize| b_edges.contains(&(x.min(y), x.max(y)));
+
+        // começa a percorrer `a` logo após uma aresta não comum
+        let Some(start) = (0..n).find(|&i| !is_common(a.0[(i + n - 1) % n], a.0[i])) else { return a.clone() };
+        let mut fragments: Vec<Vec<usize>> = Vec::new();
+        for offset in 0..n {
+            let gene = a.0[(start + offset) % n];
+            match fragments.last_mut() {
+                Some(fragment) if is_common(*fragment.last().unwrap(), gene) => fragment.push(gene),
+                _ => fragments.push(vec![gene]),
+            }
+        }
+
+        let mut parent_edges = tour_edges(&a.0);
+        parent_edges.extend(b_edges.iter().copied());
+        let is_parent_edge = |x: usize, y: usize| parent_edges.contains(&(x.min(y), x.max(y)));
+
+        // a rota é um ciclo: fixar o primeiro fragmento e sua orientação não
+        // elimina nenhuma religação
+        let first = rng.gen_range(0..fragments.len());
+        let mut tour = fragments.swap_remove(first);
+        let mut budget = Self::SEARCH_BUDGET;
+        if !self.search(&mut tour, &mut fragments, &is_parent_edge, rng, &mut budget) {
+            tour = self.reconnect_greedily(tour, fragments, &is_parent_edge, rng);
+        }
+        let mut child = IntPermEncoding(tour);
+        child.rotate_to_front(a.0[0]);
+        child
+    }
+
+    /// Candidatos para continuar a rota a partir de `end`: (fragmento,
+    /// invertido), na ordem em que devem ser tentados.
+    fn candidates<R: Rng + ?Sized>(&self, end: usize, remaining: &[Vec<usize>], rng: &mut R) -> Vec<(usize, bool)> {
+        let mut candidates: Vec<(usize, bool)> = (0..remaining.len()).flat_map(|f| [(f, false), (f, true)]).collect();
+        match &self.reconnection {
+            Reconnection::Random => candidates.shuffle(rng),
+            Reconnection::NearestNeighbor(distances) =>
+                candidates.sort_by(|x, y| distances[end][endpoint(remaining, x)].total_cmp(&distances[end][endpoint(remaining, y)])),
+        }
+        candidates
+    }
+
+    /// Estende `tour` com os fragmentos restantes usando apenas arestas novas,
+    /// incluindo a de fechamento. Em caso de falha, `tour` e `remaining`
+    /// voltam ao estado original.
+    fn search<R: Rng + ?Sized>(
+        &self,
+        tour: &mut Vec<usize>,
+        remaining: &mut Vec<Vec<usize>>,
+        is_parent_edge: &impl Fn(usize, usize) -> bool,
+        rng: &mut R,
+        budget: &mut usize,
+    ) -> bool {
+        let end = *tour.last().unwrap();
+        if remaining.is_empty()
+        { return !is_parent_edge(end, tour[0]) }
+        if *budget == 0
+        { return false }
+        *budget -= 1;
+
+        let length = tour.len();
+        for (f, reversed) in self.candidates(end, remaining, rng) {
+            if is_parent_edge(end, endpoint(remaining, &(f, reversed)))
+            { continue }
+
+            let fragment = remaining.swap_remove(f);
+            if reversed { tour.extend(fragment.iter().rev()) } else { tour.extend(&fragment) }
+            if self.search(tour, remaining, is_parent_edge, rng, budget)
+            { return true }
+
+            tour.truncate(length);
+            remaining.push(fragment);
+            let last = remaining.len() - 1;
+            remaining.swap(f, last);
+        }
+        false
+    }
+
+    /// Religação gulosa que prefere arestas novas, mas aceita as dos pais
+    /// quando não há alternativa.
+    fn reconnect_greedily<R: Rng + ?Sized>(
+        &self,
+        mut tour: Vec<usize>,
+        mut remaining: Vec<Vec<usize>>,
+        is_parent_edge: &impl Fn(usize, usize) -> bool,
+        rng: &mut R,
+    ) -> Vec<usize> {
+        while !remaining.is_empty() {
+            let end = *tour.last().unwrap();
+            let candidates = self.candidates(end, &remaining, rng);
+            let &(f, reversed) = candidates
+                .iter()
+                .find(|c| !is_parent_edge(end, endpoint(&remaining, c)))
+                .unwrap_or(&candidates[0]);
+            let mut fragment = remaining.swap_remove(f);
+            if reversed { fragment.reverse() }
+            tour.extend(fragment);
+        }
+        tour
+    }
+}
+
+/// Extremo pelo qual o fragmento `f` entra na rota.
+fn endpoint(remaining: &[Vec<usize>], &(f, reversed): &(usize, bool)) -> usize {
+    if reversed { *remaining[f].last().unwrap() } else { remaining[f][0] }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CrossoverParameterError {
+    /// `keep_prob` fora de `[0, 1]`.
+    KeepProbabilityOutOfRange { keep_prob: f64 },
+    /// `ratio` do crossover uniforme fora de `[0, 1]`.
+    RatioOutOfRange { ratio: f64 },
+    ZeroPositions,
+    /// Matriz de distâncias vazia ou não quadrada.
+    InvalidDistanceMatrix,
+    ZeroParents,
+}
+
+impl fmt::Display for CrossoverParameterError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CrossoverParameterError::KeepProbabilityOutOfRange { keep_prob } =>
+                write!(f, "keep probability {} must be within [0, 1]", keep_prob),
+            CrossoverParameterError::RatioOutOfRange { ratio } => write!(f, "uniform crossover ratio {} must be within [0, 1]", ratio),
+            CrossoverParameterError::ZeroPositions => write!(f, "at least one position must be inherited"),
+            CrossoverParameterError::InvalidDistanceMatrix => write!(f, "distance matrix must be square and non-empty"),
+            CrossoverParameterError::ZeroParents => write!(f, "at least one parent is required"),
+        }
+    }
+}
+
+impl std::error::Error for CrossoverParameterError {}
+
+/// Copia de `a` as posições marcadas em `keep` e preenche as demais com os
+/// genes restantes na ordem em que aparecem em `b`. O filho é uma permutação
+/// sempre que `a` e `b` são permutações dos mesmos elementos.
+///
+/// Se os pais começam pelo mesmo gene, o filho também começa por ele: a
+/// posição 0 é mantida de `a` ou recebe o primeiro gene de `b`. Assim UOX e
+/// PBX preservam uma cidade de partida fixada nos pais.
+fn keep_and_fill(a: &IntPermEncoding, b: &IntPermEncoding, keep: &[bool]) -> IntPermEncoding {
+    assert_eq!(a.len(), b.len(), "parents must have the same length");
+    let kept: HashSet<usize> = a.0.iter().zip(keep).filter(|&(_, &keep)| keep).map(|(&gene, _)| gene).collect();
+    let mut fill = b.0.iter().filter(|gene| !kept.contains(gene));
+    let child = a.0.iter()
+        .zip(keep)
+        .map(|(&gene, &keep)| if keep { gene } else { *fill.next().expect("parents are permutations of the same elements") })
+        .collect();
+    IntPermEncoding(child)
+}
+
+/// Crossover uniforme baseado em ordem (UOX): cada posição é mantida do pai
+/// `a` com probabilidade `keep_prob`; as demais recebem os genes restantes na
+/// ordem do pai `b`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct UniformOrderCrossover {
+    keep_prob: f64,
+}
+
+impl UniformOrderCrossover {
+    pub fn new(keep_prob: f64) -> Result<Self, CrossoverParameterError> {
+        if !(0.0..=1.0).contains(&keep_prob)
+        { return Err(CrossoverParameterError::KeepProbabilityOutOfRange { keep_prob }) }
+        Ok(UniformOrderCrossover { keep_prob })
+    }
+
+    pub fn keep_prob(&self) -> f64 {
+        self.keep_prob
+    }
+
+    pub fn crossover<R: Rng + ?Sized>(&self, a: &IntPermEncoding, b: &IntPermEncoding, rng: &mut R) -> IntPermEncoding {
+        let keep: Vec<bool> = (0..a.len()).map(|_| rng.gen_bool(self.keep_prob)).collect();
+        keep_and_fill(a, b, &keep)
+    }
+}
+
+/// Crossover baseado em posição (PBX): `positions` posições sorteadas são
+/// herdadas do pai `a` e o restante vem do pai `b`, preservando sua ordem. Se
+/// `positions` excede o comprimento, o filho é uma cópia de `a`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PositionBasedCrossover {
+    positions: usize,
+}
+
+impl PositionBasedCrossover {
+    pub fn new(positions: usize) -> Result<Self, CrossoverParameterError> {
+        if positions == 0
+        { return Err(CrossoverParameterError::ZeroPositions) }
+        Ok(PositionBasedCrossover { positions })
+    }
+
+    pub fn positions(&self) -> usize {
+        self.positions
+    }
+
+    pub fn crossover<R: Rng + ?Sized>(&self, a: &IntPermEncoding, b: &IntPermEncoding, rng: &mut R) -> IntPermEncoding {
+        let mut keep = vec![false; a.len()];
+        for position in rand::seq::index::sample(rng, a.len(), self.positions.min(a.len())) {
+            keep[position] = true;
+        }
+        keep_and_fill(a, b, &keep)
+    }
+}
+
+#[cfg(test)]
+mod crossover_tests {
+    use std::io::Cursor;
+    use super::*;
+
+    fn two_groups() -> Formula {
+        let dimacs_cnf =
+            r#"p cnf 6 4
+            1 -2 0
+            2 3 0
+            -4 5 0
+            5 -6 0
+            %"#;
+        Formula::parse_from_dimacs_cnf(Cursor::new(dimacs_cnf)).unwrap()
+    }
+
+    #[test]
+    fn test_clause_aware_clusters_disjoint_groups() {
+        let crossover = ClauseAwareCrossover::new(&two_groups(), 10);
+        assert_eq!(crossover.clusters(), &[vec![0, 1, 2], vec![3, 4, 5]]);
+    }
+
+    #[test]
+    fn test_clause_aware_clusters_respect_size_cap() {
+        let crossover = ClauseAwareCrossover::new(&two_groups(), 2);
+        assert!(crossover.clusters().iter().all(|cluster| cluster.len() <= 2));
+        assert_eq!(crossover.clusters().iter().map(Vec::len).sum::<usize>(), 6);
+    }
+
+    #[test]
+    fn test_clause_aware_offspring_inherit_whole_clusters() {
+        let crossover = ClauseAwareCrossover::new(&two_groups(), 10);
+        let a = BinaryEncoding::zeros(6);
+        let b = BinaryEncoding::from_bitstring("111111").unwrap();
+        let mut rng = rand::thread_rng();
+
+        for _ in 0..50 {
+            let child = crossover.crossover(&a, &b, &mut rng);
+            for cluster in crossover.clusters() {
+                let from_a = cluster.iter().all(|&var| child.get(var) == a.get(var));
+                let from_b = cluster.iter().all(|&var| child.get(var) == b.get(var));
+                assert!(from_a || from_b);
+            }
+        }
+    }
+
+    fn common_edges(a: &IntPermEncoding, b: &IntPermEncoding) -> HashSet<(usize, usize)> {
+        tour_edges(&a.0).intersection(&tour_edges(&b.0)).copied().collect()
+    }
+
+    #[test]
+    fn test_dpx_preserves_common_edges() {
+        use rand::{SeedableRng, rngs::StdRng};
+
+        let mut rng = StdRng::seed_from_u64(9);
+        let a = IntPermEncoding(vec![0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11]);
+        let b = IntPermEncoding(vec![0, 1, 2, 6, 7, 3, 4, 5, 10, 11, 8, 9]);
+        let dpx = DistancePreservingCrossover::new(Reconnection::Random).unwrap();
+
+        for _ in 0..20 {
+            let child = dpx.crossover(&a, &b, &mut rng);
+            let mut sorted = child.0.clone();
+            sorted.sort();
+            assert_eq!(sorted, (0..12).collect::<Vec<_>>());
+            assert!(common_edges(&a, &b).is_subset(&tour_edges(&child.0)));
+        }
+    }
+
+    #[test]
+    fn test_dpx_distance_property() {
+        use rand::{SeedableRng, rngs::StdRng};
+
+        let mut rng = StdRng::seed_from_u64(4);
+        let dpx = DistancePreservingCrossover::new(Reconnection::Random).unwrap();
+        for _ in 0..50 {
+            let mut a: Vec<usize> = (0..30).collect();
+            a.shuffle(&mut rng);
+            // b = a com dois segmentos invertidos: quatro fragmentos, que
+            // sempre admitem uma religação só com arestas novas
+            let mut b = a.clone();
+            b[3..9].reverse();
+            b[15..24].reverse();
+            let (a, b) = (IntPermEncoding(a), IntPermEncoding(b));
+
+            let child = dpx.crossover(&a, &b, &mut rng);
+            let mut sorted = child.0.clone();
+            sorted.sort();
+            assert_eq!(sorted, (0..30).collect::<Vec<_>>());
+            assert!(common_edges(&a, &b).is_subset(&tour_edges(&child.0)));
+            assert_eq!(edge_distance(&child, &a), edge_distance(&a, &b));
+            assert_eq!(edge_distance(&child, &b), edge_distance(&a, &b));
+        }
+    }
+
+    #[test]
+    fn test_dpx_two_fragments_cannot_preserve_distance() {
+        // um único segmento invertido deixa dois fragmentos, e as duas
+        // religações possíveis reconstroem os próprios pais
+        let a = IntPermEncoding(vec![0, 1, 2, 3, 4, 5]);
+        let b = IntPermEncoding(vec![0, 3, 2, 1, 4, 5]);
+        let dpx = DistancePreservingCrossover::new(Reconnection::Random).unwrap();
+        let mut rng = rand::thread_rng();
+        for _ in 0..10 {
+            let child = tour_edges(&dpx.crossover(&a, &b, &mut rng).0);
+            assert!(child == tour_edges(&a.0) || child == tour_edges(&b.0));
+        }
+    }
+
+    #[test]
+    fn test_dpx_identical_parents() {
+        let a = IntPermEncoding(vec![2, 0, 3, 1]);
+        let child = DistancePreservingCrossover::new(Reconnection::Random).unwrap().crossover(&a, &a, &mut rand::thread_rng());
+        assert_eq!(child, a);
+    }
+
+    #[test]
+    fn test_dpx_nearest_neighbor_reconnection() {
+        use rand::{SeedableRng, rngs::StdRng};
+
+        // pares de cidades numa reta; fragmentos [0, 1], [6, 7], [3, 2] e [5, 4]
+        let positions = [0.0, 1.0, 10.0, 11.0, 20.0, 21.0, 30.0, 31.0];
+        let distances: Vec<Vec<f64>> = positions.iter().map(|x: &f64| positions.iter().map(|y| (x - y).abs()).collect()).collect();
+        let a = IntPermEncoding(vec![0, 1, 6, 7, 3, 2, 5, 4]);
+        let b = IntPermEncoding(vec![0, 1, 4, 5, 7, 6, 3, 2]);
+
+        // partindo de [0, 1] ou de [5, 4], o vizinho mais próximo percorre a
+        // reta; partindo de [6, 7] ou de [3, 2] (este após retroceder quando
+        // 7-3 fecharia com uma aresta dos pais), fecha por 0-6
+        let along_line = tour_edges(&[0, 1, 2, 3, 4, 5, 6, 7]);
+        let folded = tour_edges(&[6, 7, 4, 5, 3, 2, 1, 0]);
+
+        let nearest = DistancePreservingCrossover::new(Reconnection::NearestNeighbor(distances)).unwrap();
+        let random = DistancePreservingCrossover::new(Reconnection::Random).unwrap();
+        let mut rng = StdRng::seed_from_u64(5);
+        let mut seen = HashSet::new();
+        let mut random_differs = false;
+        for _ in 0..40 {
+            let child = tour_edges(&nearest.crossover(&a, &b, &mut rng).0);
+            assert!(child == along_line || child == folded, "{:?}", child);
+            seen.insert(child == along_line);
+
+            let child = tour_edges(&random.crossover(&a, &b, &mut rng).0);
+            random_differs |= child != along_line && child != folded;
+        }
+        assert_eq!(seen.len(), 2);
+        assert!(random_differs);
+    }
+
+    #[test]
+    fn test_dpx_rejects_invalid_distance_matrix() {
+        for distances in [Vec::new(), vec![vec![0.0, 1.0], vec![1.0]]] {
+            assert_eq!(
+                DistancePreservingCrossover::new(Reconnection::NearestNeighbor(distances)),
+                Err(CrossoverParameterError::InvalidDistanceMatrix)
+            );
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "distance matrix must have one row per city")]
+    fn test_dpx_distance_matrix_shorter_than_parents() {
+        let dpx = DistancePreservingCrossover::new(Reconnection::NearestNeighbor(vec![vec![0.0; 3]; 3])).unwrap();
+        let a = IntPermEncoding(vec![0, 1, 2, 3, 4, 5]);
+        let b = IntPermEncoding(vec![0, 2, 4, 1, 3, 5]);
+        dpx.crossover(&a, &b, &mut rand::thread_rng());
+    }
+
+    #[test]
+    fn test_uniform_crossover_categorical() {
+        use crate::population::CategoricalDomain;
+
+        let mut rng = rand::thread_rng();
+        let domain = CategoricalDomain::new(vec![2, 5, 3, 4]).unwrap();
+        let a = CategoricalEncoding(vec![0, 4, 2, 1]);
+        let b = CategoricalEncoding(vec![1, 0, 0, 3]);
+
+        for _ in 0..20 {
+            let (first, second) = UniformCrossover::new(0.5).unwrap().crossover(&a, &b, &mut rng);
+            assert!(domain.contains(&first) && domain.contains(&second));
+            for gene in 0..4 {
+                let pair = (first.0[gene], second.0[gene]);
+                assert!(pair == (a.0[gene], b.0[gene]) || pair == (b.0[gene], a.0[gene]));
+            }
+        }
+        assert_eq!(UniformCrossover::new(1.0).unwrap().crossover(&a, &b, &mut rng), (a.clone(), b.clone()));
+        assert_eq!(UniformCrossover::new(0.0).unwrap().crossover(&a, &b, &mut rng), (b, a));
+
+        assert_eq!(UniformCrossover::new(1.5), Err(CrossoverParameterError::RatioOutOfRange { ratio: 1.5 }));
+        assert_eq!(UniformCrossover::new(-0.5), Err(CrossoverParameterError::RatioOutOfRange { ratio: -0.5 }));
+        assert!(UniformCrossover::new(f64::NAN).is_err());
+    }
+
+    fn random_parents(n: usize, rng: &mut impl Rng) -> (IntPermEncoding, IntPermEncoding) {
+        let mut a: Vec<usize> = (0..n).collect();
+        let mut b = a.clone();
+        a.shuffle(rng);
+        b.shuffle(rng);
+        (IntPermEncoding(a), IntPermEncoding(b))
+    }
+
+    fn is_permutation(child: &IntPermEncoding, n: usize) -> bool {
+        let mut sorted = child.0.clone();
+        sorted.sort();
+        sorted == (0..n).collect::<Vec<_>>()
+    }
+
+    #[test]
+    fn test_keep_and_fill_example() {
+        let a = IntPermEncoding(vec![0, 1, 2, 3, 4, 5, 6, 7]);
+        let b = IntPermEncoding(vec![3, 7, 5, 1, 6, 0, 2, 4]);
+        let keep = [false, true, true, false, false, true, false, false];
+        // restantes na ordem de b: 3, 7, 6, 0, 4
+        assert_eq!(keep_and_fill(&a, &b, &keep), IntPermEncoding(vec![3, 1, 2, 7, 6, 5, 0, 4]));
+        assert_eq!(keep_and_fill(&a, &b, &[true; 8]), a);
+        assert_eq!(keep_and_fill(&a, &b, &[false; 8]), b);
+    }
+
+    #[test]
+    fn test_uniform_order_crossover() {
+        let mut rng = rand::thread_rng();
+        let uox = UniformOrderCrossover::new(0.5).unwrap();
+        for n in [1, 2, 9, 40] {
+            for _ in 0..20 {
+                let (a, b) = random_parents(n, &mut rng);
+                let child = uox.crossover(&a, &b, &mut rng);
+                assert!(is_permutation(&child, n));
+
+                // os genes de `a` fora das posições mantidas aparecem na ordem de `b`
+                let kept: Vec<bool> = child.0.iter().zip(&a.0).map(|(x, y)| x == y).collect();
+                assert_eq!(keep_and_fill(&a, &b, &kept), child);
+            }
+        }
+
+        let (a, b) = random_parents(10, &mut rng);
+        assert_eq!(UniformOrderCrossover::new(1.0).unwrap().crossover(&a, &b, &mut rng), a);
+        assert_eq!(UniformOrderCrossover::new(0.0).unwrap().crossover(&a, &b, &mut rng), b);
+    }
+
+    #[test]
+    fn test_position_based_crossover() {
+        let mut rng = rand::thread_rng();
+        let pbx = PositionBasedCrossover::new(4).unwrap();
+        for _ in 0..50 {
+            let (a, b) = random_parents(12, &mut rng);
+            let child = pbx.crossover(&a, &b, &mut rng);
+            assert!(is_permutation(&child, 12));
+
+            // ao menos as 4 posições sorteadas coincidem com `a`
+            let matching = child.0.iter().zip(&a.0).filter(|(x, y)| x == y).count();
+            assert!(matching >= 4);
+            let from_b: Vec<usize> = b.0.iter().copied().filter(|gene| child.0.iter().zip(&a.0).any(|(x, y)| x != y && x == gene)).collect();
+            let in_child: Vec<usize> = child.0.iter().zip(&a.0).filter(|(x, y)| x != y).map(|(&x, _)| x).collect();
+            assert_eq!(in_child, from_b);
+        }
+
+        let (a, b) = random_parents(5, &mut rng);
+        assert_eq!(PositionBasedCrossover::new(9).unwrap().crossover(&a, &b, &mut rng), a);
+    }
+
+    #[test]
+    fn test_permutation_crossovers_keep_a_shared_first_gene() {
+        let mut rng = rand::thread_rng();
+        let uox = UniformOrderCrossover::new(0.5).unwrap();
+        let pbx = PositionBasedCrossover::new(3).unwrap();
+        let dpx = DistancePreservingCrossover::new(Reconnection::Random).unwrap();
+        for _ in 0..50 {
+            let (mut a, mut b) = random_parents(10, &mut rng);
+            a.rotate_to_front(4);
+            b.rotate_to_front(4);
+            assert_eq!(uox.crossover(&a, &b, &mut rng).0[0], 4);
+            assert_eq!(pbx.crossover(&a, &b, &mut rng).0[0], 4);
+            let child = dpx.crossover(&a, &b, &mut rng);
+            assert!(is_permutation(&child, 10));
+            assert_eq!(child.0[0], 4);
+        }
+    }
+
+    #[test]
+    fn test_permutation_crossover_parameters() {
+        assert_eq!(UniformOrderCrossover::new(1.5), Err(CrossoverParameterError::KeepProbabilityOutOfRange { keep_prob: 1.5 }));
+        assert_eq!(UniformOrderCrossover::new(-0.1), Err(CrossoverParameterError::KeepProbabilityOutOfRange { keep_prob: -0.1 }));
+        assert!(UniformOrderCrossover::new(f64::NAN).is_err());
+        assert_eq!(PositionBasedCrossover::new(0), Err(CrossoverParameterError::ZeroPositions));
+        assert_eq!(PositionBasedCrossover::new(3).unwrap().positions(), 3);
+    }
+
+    fn bits(bitstring: &str) -> BinaryEncoding {
+        BinaryEncoding::from_bitstring(bitstring).unwrap()
+    }
+
+    #[test]
+    fn test_majority_vote() {
+        let mut rng = rand::thread_rng();
+        let parents = [bits("110010"), bits("100110"), bits("011011")];
+        let refs: Vec<&BinaryEncoding> = parents.iter().collect();
+        let majority = MajorityVoteCrossover::new(3).unwrap();
+        assert_eq!(majority.cross(&refs, &mut rng), bits("110010"));
+
+        // com quatro pais, os bits 1 e 2 empatam (2 a 2)
+        let parents = [bits("1110"), bits("1010"), bits("1100"), bits("0000")];
+        let refs: Vec<&BinaryEncoding> = parents.iter().collect();
+        let majority = MajorityVoteCrossover::new(4).unwrap();
+        let (mut seen_bit1, mut seen_bit2) = ([false; 2], [false; 2]);
+        for _ in 0..200 {
+            let child = majority.cross(&refs, &mut rng);
+            assert!(child.get(0));
+            assert!(!child.get(3));
+            seen_bit1[child.get(1) as usize] = true;
+            seen_bit2[child.get(2) as usize] = true;
+        }
+        assert_eq!((seen_bit1, seen_bit2), ([true; 2], [true; 2]));
+    }
+
+    #[test]
+    fn test_consensus_keeps_unanimous_bits() {
+        let mut rng = rand::thread_rng();
+        let parents = [bits("1100101"), bits("1001100"), bits("1011111")];
+        let refs: Vec<&BinaryEncoding> = parents.iter().collect();
+        let consensus = ConsensusCrossover::new(3).unwrap();
+        let disputed = [1, 2, 3, 5, 6];
+        let mut varied = [false; 7];
+        let first = consensus.cross(&refs, &mut rng);
+        for _ in 0..200 {
+            let child = consensus.cross(&refs, &mut rng);
+            assert!(child.get(0));
+            assert!(child.get(4));
+            disputed.iter().for_each(|&bit| varied[bit] |= child.get(bit) != first.get(bit));
+        }
+        assert!(disputed.iter().all(|&bit| varied[bit]));
+
+        // um único pai é copiado
+        let single = ConsensusCrossover::new(1).unwrap();
+        assert_eq!(single.cross(&[&parents[0]], &mut rng), parents[0]);
+    }
+
+    #[test]
+    fn test_multi_crossover_parent_count() {
+        assert_eq!(MajorityVoteCrossover::new(0), Err(CrossoverParameterError::ZeroParents));
+        assert_eq!(ConsensusCrossover::new(0), Err(CrossoverParameterError::ZeroParents));
+
+        // os operadores podem ser escolhidos em tempo de execução
+        let operators: Vec<Box<dyn MultiCrossover<BinaryEncoding>>> =
+            vec![Box::new(MajorityVoteCrossover::new(3).unwrap()), Box::new(ConsensusCrossover::new(2).unwrap())];
+        let parents = [bits("1100"), bits("1010"), bits("1001")];
+        let mut rng = rand::thread_rng();
+        for operator in &operators {
+            let refs: Vec<&BinaryEncoding> = parents.iter().take(operator.parents()).collect();
+            assert!(operator.cross(&refs, &mut rng).get(0));
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "crossover expects a different number of parents")]
+    fn test_multi_crossover_rejects_wrong_parent_count() {
+        let parents = [bits("1100"), bits("1010")];
+        MajorityVoteCrossover::new(3).unwrap().cross(&[&parents[0], &parents[1]], &mut rand::thread_rng());
+    }
+
+    #[test]
+    fn test_majority_vote_on_one_max() {
+        use rand::{SeedableRng, rngs::StdRng};
+
+        // pais com 70% de uns: a maioria de 5 amplifica a tendência
+        let mut rng = StdRng::seed_from_u64(5);
+        let random_parent = |rng: &mut StdRng| BinaryEncoding::from_bools(&(0..200).map(|_| rng.gen_bool(0.7)).collect::<Vec<_>>());
+        let majority = MajorityVoteCrossover::new(5).unwrap();
+        let consensus = ConsensusCrossover::new(5).unwrap();
+        let (mut parent_ones, mut majority_ones, mut consensus_ones) = (0, 0, 0);
+        for _ in 0..20 {
+            let parents: Vec<BinaryEncoding> = (0..majority.parents()).map(|_| random_parent(&mut rng)).collect();
+            let refs: Vec<&BinaryEncoding> = parents.iter().collect();
+            parent_ones += parents.iter().map(BinaryEncoding::count_ones).sum::<usize>() / parents.len();
+            majority_ones += majority.cross(&refs, &mut rng).count_ones();
+            consensus_ones += consensus.cross(&refs, &mut rng).count_ones();
+        }
+        assert!(majority_ones > parent_ones, "{} <= {}", majority_ones, parent_ones);
+        assert!(consensus_ones < parent_ones, "{} >= {}", consensus_ones, parent_ones);
+    }
+}
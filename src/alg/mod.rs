@@ -1,42 +1,218 @@
+pub mod comparative;
+pub mod crossover;
+pub mod mutation;
+pub mod niching;
 pub mod objectives;
-use crate::population::{Encoding, PopGenerator, Population};
+pub mod parallel;
+pub mod polarity;
+pub mod polish;
+pub mod ranking;
+pub mod restart;
+pub mod sat_init;
+pub mod sat_report;
+pub mod score_guard;
+pub mod scaling;
+pub mod substreams;
+pub mod surrogate;
+pub mod termination;
+use std::fmt;
+use crate::population::{Encoding, PopGenerator, PopGeneratorError, Population};
 
 pub trait Objective<E: Encoding> {
     type Output;
     fn eval(&self, pop: &Population<E>) -> Self::Output;
+
+    /// Dimensão que os indivíduos precisam ter, se o objetivo fixa uma (ex. o
+    /// número de variáveis de uma fórmula SAT).
+    fn required_dimension(&self) -> Option<usize> {
+        None
+    }
 } 
 
+/// Objetivo com saída escalar por indivíduo. Ao contrário de `Objective`, é
+/// object-safe, de modo que o runtime pode escolher o objetivo a partir da
+/// configuração e guardá-lo como `Box<dyn ScalarObjective<E>>`.
+pub trait ScalarObjective<E: Encoding> {
+    fn eval_scalar(&self, pop: &Population<E>) -> Option<Vec<f64>>;
+
+    fn required_dimension(&self) -> Option<usize> {
+        None
+    }
+}
+
+impl<E: Encoding> Objective<E> for Box<dyn ScalarObjective<E>> {
+    type Output = Option<Vec<f64>>;
+
+    fn eval(&self, pop: &Population<E>) -> Self::Output {
+        self.as_ref().eval_scalar(pop)
+    }
+
+    fn required_dimension(&self) -> Option<usize> {
+        self.as_ref().required_dimension()
+    }
+}
+
+/// Saída bruta de um objetivo que pode ser lida como um score por indivíduo.
+/// Uma saída `None` (avaliação impossível) não tem scores e continua `None`,
+/// para não ser confundida com uma população vazia.
+pub trait RawScores {
+    fn scores(&self) -> Option<Vec<f64>>;
+}
+
+impl RawScores for Vec<usize> {
+    fn scores(&self) -> Option<Vec<f64>> {
+        Some(self.iter().map(|&score| score as f64).collect())
+    }
+}
+
+impl RawScores for Vec<f64> {
+    fn scores(&self) -> Option<Vec<f64>> {
+        Some(self.clone())
+    }
+}
+
+impl<T: RawScores> RawScores for Option<T> {
+    fn scores(&self) -> Option<Vec<f64>> {
+        self.as_ref().and_then(RawScores::scores)
+    }
+}
+
+/// Converte a saída de um objetivo nos scores vistos pela seleção; `None`
+/// se o objetivo não conseguiu avaliar a população.
 pub trait Fitness<E, O>
 where
     E: Encoding,
     O: Objective<E>
 {
-    type Score;
-    fn eval(input: O::Output) -> Self::Score;
+    type Score: PartialOrd;
+    fn eval(&self, raw: &O::Output) -> Option<Vec<Self::Score>>;
+}
+
+/// Usa os scores do objetivo sem alteração.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct IdentityFitness;
+impl<E, O> Fitness<E, O> for IdentityFitness
+where
+    E: Encoding,
+    O: Objective<E>,
+    O::Output: RawScores,
+{
+    type Score = f64;
+
+    fn eval(&self, raw: &O::Output) -> Option<Vec<f64>> {
+        raw.scores()
+    }
 }
 
-pub struct GeneticAlgorithm<Enc, O, P>
+/// Inverte o sinal dos scores, transformando minimização em maximização.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct NegateFitness;
+impl<E, O> Fitness<E, O> for NegateFitness
+where
+    E: Encoding,
+    O: Objective<E>,
+    O::Output: RawScores,
+{
+    type Score = f64;
+
+    fn eval(&self, raw: &O::Output) -> Option<Vec<f64>> {
+        raw.scores().map(|scores| scores.into_iter().map(|score| -score).collect())
+    }
+}
+
+/// Componente do GA cuja dimensão não bate com a do gerador de população.
+#[derive(Debug, PartialEq)]
+pub struct DimensionMismatch {
+    pub expected: usize,
+    pub found: usize,
+    pub source: &'static str,
+}
+
+impl fmt::Display for DimensionMismatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} requires dim {} but the population generator has dim {}", self.source, self.expected, self.found)
+    }
+}
+
+impl std::error::Error for DimensionMismatch {}
+
+#[derive(Debug, PartialEq)]
+pub enum BuildError {
+    DimensionMismatch(DimensionMismatch),
+    /// Gerador criado sem dimensão e objetivo que não a determina.
+    MissingDimension,
+    /// O gerador recusou a dimensão inferida do objetivo.
+    Generator(PopGeneratorError),
+}
+
+impl fmt::Display for BuildError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BuildError::DimensionMismatch(err) => err.fmt(f),
+            BuildError::MissingDimension =>
+                write!(f, "the population generator has no dim and the objective does not determine one"),
+            BuildError::Generator(err) => write!(f, "the population generator rejected the objective's dim: {}", err),
+        }
+    }
+}
+
+impl std::error::Error for BuildError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            BuildError::DimensionMismatch(err) => Some(err),
+            BuildError::MissingDimension => None,
+            BuildError::Generator(err) => Some(err),
+        }
+    }
+}
+
+impl From<DimensionMismatch> for BuildError {
+    fn from(value: DimensionMismatch) -> Self {
+        BuildError::DimensionMismatch(value)
+    }
+}
+
+#[allow(dead_code)]
+pub struct GeneticAlgorithm<Enc, O, P, F = IdentityFitness>
 where
     Enc: Encoding,
     O: Objective<Enc>,
-    // F: Fitness<Enc, O>,
+    F: Fitness<Enc, O>,
     P: PopGenerator<E = Enc>,
 {
     objective: O,
-    // fitness: F,
+    fitness: F,
     pop_generator: P,
     runs: u64,
     generations: u64,
 }
 
-impl<Enc, O, P> GeneticAlgorithm<Enc, O, P>
+impl<Enc, O, P, F> GeneticAlgorithm<Enc, O, P, F>
 where
     Enc: Encoding,
     O: Objective<Enc>,
-    // F: Fitness<Enc, O>,
+    F: Fitness<Enc, O>,
     P: PopGenerator<E = Enc>,
 {
-    pub fn run() -> () {
+    /// Confere a dimensão do gerador contra a exigida pelo objetivo. Um
+    /// gerador criado sem dimensão recebe a do objetivo.
+    pub fn new(objective: O, fitness: F, mut pop_generator: P, runs: u64, generations: u64) -> Result<Self, BuildError> {
+        match (objective.required_dimension(), pop_generator.has_dimension()) {
+            (Some(expected), false) => pop_generator.infer_dimension(expected).map_err(BuildError::Generator)?,
+            (Some(expected), true) if expected != pop_generator.dimension() =>
+                return Err(DimensionMismatch { expected, found: pop_generator.dimension(), source: "objective" }.into()),
+            (None, false) => return Err(BuildError::MissingDimension),
+            _ => {},
+        }
+
+        Ok(GeneticAlgorithm { objective, fitness, pop_generator, runs, generations })
+    }
+
+    pub fn pop_generator(&self) -> &P {
+        &self.pop_generator
+    }
+
+    pub fn run() {
         
     }
 }
\ No newline at end of file
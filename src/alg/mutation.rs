@@ -0,0 +1,171 @@
+use std::collections::{HashMap, HashSet};
+use rand::Rng;
+use crate::population::{IntegerDomain, IntegerEncoding, Population};
+
+/// Mutação de vizinhança variável: começa com passos pequenos (creep de
+/// `±steps[0]`) e escala para o passo seguinte quando um indivíduo acumula
+/// `patience` aplicações sem melhora; depois do último passo, o gene é
+/// sorteado de novo no domínio. Uma melhora reinicia a escada.
+///
+/// O estado de cada indivíduo fica num mapa indexado pelo genótipo, então
+/// indivíduos idênticos compartilham a mesma posição na escada. Para o mapa
+/// não crescer sem limite, `retain_population` deve ser chamado a cada
+/// geração com a população sobrevivente.
+#[derive(Debug, Clone, PartialEq)]
+pub struct VariableNeighborhoodMutation {
+    domain: IntegerDomain,
+    steps: Vec<u64>,
+    patience: usize,
+    ladder: HashMap<Vec<u64>, Rung>,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+struct Rung {
+    level: usize,
+    failures: usize,
+}
+
+impl VariableNeighborhoodMutation {
+    /// `patience` igual a zero é tratado como 1.
+    pub fn new(domain: IntegerDomain, steps: Vec<u64>, patience: usize) -> Self {
+        VariableNeighborhoodMutation { domain, steps, patience: patience.max(1), ladder: HashMap::new() }
+    }
+
+    /// Nível atual do indivíduo: índice em `steps`, ou `steps.len()` para o
+    /// sorteio uniforme.
+    pub fn level(&self, individual: &IntegerEncoding) -> usize {
+        self.ladder.get(&individual.0).map_or(0, |rung| rung.level)
+    }
+
+    /// Muta um gene escolhido ao acaso com a vizinhança do nível do indivíduo,
+    /// sempre dentro do domínio.
+    pub fn mutate<R: Rng + ?Sized>(&self, individual: &IntegerEncoding, rng: &mut R) -> IntegerEncoding {
+        let mut mutant = individual.clone();
+        if mutant.0.is_empty()
+        { return mutant }
+
+        let gene = &mut mutant.0[rng.gen_range(0..individual.0.len())];
+        *gene = match self.steps.get(self.level(individual)) {
+            Some(&step) if rng.gen_bool(0.5) => self.domain.clamp(gene.saturating_add(step)),
+            Some(&step) => self.domain.clamp(gene.saturating_sub(step)),
+            None => self.domain.random_gene(rng),
+        };
+        mutant
+    }
+
+    /// Registra o resultado de uma aplicação sobre `parent`. Uma melhora
+    /// descarta o estado do pai e deixa `child` no início da escada; caso
+    /// contrário, o pai sobe de nível a cada `patience` falhas.
+    pub fn record(&mut self, parent: &IntegerEncoding, child: &IntegerEncoding, improved: bool) {
+        if improved {
+            self.ladder.remove(&parent.0);
+            self.ladder.remove(&child.0);
+            return
+        }
+
+        let top = self.steps.len();
+        let rung = self.ladder.entry(parent.0.clone()).or_default();
+        rung.failures += 1;
+        if rung.failures == self.patience {
+            rung.level = (rung.level + 1).min(top);
+            rung.failures = 0;
+        }
+    }
+
+    /// Descarta o estado dos genótipos que não estão em `pop`, limitando o
+    /// mapa ao tamanho da população.
+    pub fn retain_population(&mut self, pop: &Population<IntegerEncoding>) {
+        let alive: HashSet<&Vec<u64>> = pop.get_individuals().iter().map(|individual| &individual.0).collect();
+        self.ladder.retain(|genotype, _| alive.contains(genotype));
+    }
+
+    /// Quantos genótipos têm estado guardado.
+    pub fn tracked(&self) -> usize {
+        self.ladder.len()
+    }
+}
+
+#[cfg(test)]
+mod mutation_tests {
+    use rand::{SeedableRng, rngs::StdRng};
+    use crate::population::Encoding;
+    use super::*;
+
+    fn mutation(patience: usize) -> VariableNeighborhoodMutation {
+        VariableNeighborhoodMutation::new(IntegerDomain::new(0, 100).unwrap(), vec![1, 10], patience)
+    }
+
+    #[test]
+    fn test_escalates_after_patience_failures() {
+        let mut vnm = mutation(3);
+        let parent = IntegerEncoding(vec![50, 50]);
+        let child = IntegerEncoding(vec![51, 50]);
+
+        for expected in [0, 0, 0, 1, 1, 1, 2, 2, 2, 2] {
+            assert_eq!(vnm.level(&parent), expected);
+            vnm.record(&parent, &child, false);
+        }
+        // nível final é o sorteio uniforme e não passa dele
+        assert_eq!(vnm.level(&parent), 2);
+    }
+
+    #[test]
+    fn test_improvement_resets_ladder() {
+        let mut vnm = mutation(1);
+        let parent = IntegerEncoding(vec![50, 50]);
+        let child = IntegerEncoding(vec![60, 50]);
+        vnm.record(&parent, &child, false);
+        vnm.record(&parent, &child, false);
+        assert_eq!(vnm.level(&parent), 2);
+
+        vnm.record(&parent, &child, true);
+        assert_eq!(vnm.level(&parent), 0);
+        assert_eq!(vnm.level(&child), 0);
+    }
+
+    #[test]
+    fn test_step_size_per_level() {
+        let mut rng = StdRng::seed_from_u64(5);
+        let mut vnm = mutation(1);
+        let parent = IntegerEncoding(vec![50, 50, 50]);
+        let other = IntegerEncoding(vec![0, 0, 0]);
+
+        for step in [1u64, 10] {
+            for _ in 0..50 {
+                let mutant = vnm.mutate(&parent, &mut rng);
+                assert_eq!(parent.distance(&mutant), step as f64);
+            }
+            vnm.record(&parent, &other, false);
+        }
+    }
+
+    #[test]
+    fn test_bounds_respected_at_every_level() {
+        let mut rng = StdRng::seed_from_u64(8);
+        let domain = IntegerDomain::new(3, 12).unwrap();
+        let mut vnm = VariableNeighborhoodMutation::new(domain, vec![1, 10], 1);
+        for parent in [IntegerEncoding(vec![3, 3]), IntegerEncoding(vec![12, 12]), IntegerEncoding(vec![7, 8])] {
+            for _ in 0..3 {
+                for _ in 0..100 {
+                    assert!(domain.is_within_bounds(&vnm.mutate(&parent, &mut rng)));
+                }
+                vnm.record(&parent, &parent, false);
+            }
+        }
+    }
+
+    #[test]
+    fn test_retain_population_evicts_dead_genotypes() {
+        let mut vnm = mutation(1);
+        let survivor = IntegerEncoding(vec![50, 50]);
+        let discarded = IntegerEncoding(vec![10, 10]);
+        vnm.record(&survivor, &discarded, false);
+        vnm.record(&discarded, &survivor, false);
+        assert_eq!(vnm.tracked(), 2);
+
+        vnm.retain_population(&Population(vec![survivor.clone(), IntegerEncoding(vec![0, 0])]));
+        assert_eq!(vnm.tracked(), 1);
+        assert_eq!(vnm.level(&survivor), 1);
+        assert_eq!(vnm.level(&discarded), 0);
+    }
+}
@@ -0,0 +1,358 @@
+use crate::population::{Encoding, Population};
+
+/// Espécie: um representante e os índices (na população) de seus membros.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Species<E: Encoding> {
+    pub representative: E,
+    pub members: Vec<usize>,
+}
+
+/// Agrupa a população em espécies (estilo NEAT): cada indivíduo entra na
+/// primeira espécie cujo representante está a distância menor que `delta`;
+/// se nenhuma serve, funda uma nova espécie e passa a representá-la.
+/// `representatives` são os representantes da geração anterior; espécies que
+/// ficaram sem membros são descartadas.
+pub fn speciate<E: Encoding>(pop: &Population<E>, representatives: &[E], delta: f64) -> Vec<Species<E>> {
+    speciate_with(pop, representatives, delta, E::distance)
+}
+
+/// Como `speciate`, mas com a métrica passada explicitamente no lugar de
+/// `Encoding::distance` (ex. uma `PermutationMetric`).
+pub fn speciate_with<E, D>(pop: &Population<E>, representatives: &[E], delta: f64, distance: D) -> Vec<Species<E>>
+where
+    E: Encoding,
+    D: Fn(&E, &E) -> f64,
+{
+    let mut species = assign(pop, representatives, delta, distance);
+    species.retain(|s| !s.members.is_empty());
+    species
+}
+
+/// Uma espécie por representante anterior, na mesma ordem e possivelmente
+/// vazia, seguida das espécies novas.
+fn assign<E, D>(pop: &Population<E>, representatives: &[E], delta: f64, distance: D) -> Vec<Species<E>>
+where
+    E: Encoding,
+    D: Fn(&E, &E) -> f64,
+{
+    let mut species: Vec<Species<E>> = representatives
+        .iter()
+        .map(|representative| Species { representative: representative.clone(), members: Vec::new() })
+        .collect();
+
+    for (index, individual) in pop.get_individuals().iter().enumerate() {
+        match species.iter_mut().find(|s| distance(&s.representative, individual) < delta) {
+            Some(s) => s.members.push(index),
+            None => species.push(Species { representative: individual.clone(), members: vec![index] }),
+        }
+    }
+    species
+}
+
+/// Especiação ao longo das gerações com eliminação de espécies estagnadas:
+/// uma espécie cujo melhor fitness não melhora por `patience` gerações
+/// seguidas é descartada, junto com seu representante. A espécie com o
+/// melhor indivíduo da geração nunca é descartada.
+#[derive(Debug, Clone)]
+pub struct SpeciesTracker<E: Encoding> {
+    delta: f64,
+    patience: usize,
+    tracked: Vec<TrackedSpecies<E>>,
+}
+
+#[derive(Debug, Clone)]
+struct TrackedSpecies<E> {
+    representative: E,
+    best: f64,
+    stagnant_for: usize,
+}
+
+impl<E: Encoding> SpeciesTracker<E> {
+    pub fn new(delta: f64, patience: usize) -> Self {
+        SpeciesTracker { delta, patience, tracked: Vec::new() }
+    }
+
+    /// Representantes das espécies vivas, na ordem em que são testadas.
+    pub fn representatives(&self) -> Vec<E> {
+        self.tracked.iter().map(|t| t.representative.clone()).collect()
+    }
+
+    /// Gerações sem melhora de cada espécie viva, na ordem de `representatives`.
+    pub fn stagnation(&self) -> Vec<usize> {
+        self.tracked.iter().map(|t| t.stagnant_for).collect()
+    }
+
+    /// Especia `pop` contra os representantes vivos, atualiza a estagnação com
+    /// `fitness` (maior é melhor) e devolve as espécies que sobrevivem. Os
+    /// membros das espécies descartadas não recebem descendentes.
+    pub fn update(&mut self, pop: &Population<E>, fitness: &[f64]) -> Vec<Species<E>> {
+        let species = assign(pop, &self.representatives(), self.delta, E::distance);
+        let previous = std::mem::take(&mut self.tracked);
+
+        // (espécie, registro, melhor fitness nesta geração)
+        let mut alive: Vec<(Species<E>, TrackedSpecies<E>, f64)> = Vec::new();
+        for (position, s) in species.into_iter().enumerate() {
+            let Some(best) = s.members.iter().map(|&member| fitness[member]).max_by(f64::total_cmp)
+            else { continue };
+
+            let record = match previous.get(position) {
+                Some(record) if best > record.best => TrackedSpecies { best, stagnant_for: 0, ..record.clone() },
+                Some(record) => TrackedSpecies { stagnant_for: record.stagnant_for + 1, ..record.clone() },
+                None => TrackedSpecies { representative: s.representative.clone(), best, stagnant_for: 0 },
+            };
+            alive.push((s, record, best));
+        }
+
+        let leader = (0..alive.len()).max_by(|&a, &b| alive[a].2.total_cmp(&alive[b].2));
+        let mut survivors = Vec::new();
+        for (position, (s, record, _)) in alive.into_iter().enumerate() {
+            if record.stagnant_for >= self.patience && Some(position) != leader
+            { continue }
+
+            survivors.push(s);
+            self.tracked.push(record);
+        }
+        survivors
+    }
+}
+
+/// Compartilhamento explícito de fitness: o fitness de cada indivíduo é
+/// dividido pelo tamanho de sua espécie.
+pub fn shared_fitness<E: Encoding>(fitness: &[f64], species: &[Species<E>]) -> Vec<f64> {
+    let mut adjusted = fitness.to_vec();
+    for s in species {
+        for &member in &s.members {
+            adjusted[member] = fitness[member] / s.members.len() as f64;
+        }
+    }
+    adjusted
+}
+
+/// Divide `total` descendentes entre as espécies proporcionalmente ao fitness
+/// ajustado médio de cada uma (não negativo, maior é melhor), pelo método dos
+/// maiores restos. A soma é sempre `total`; se todas as médias são nulas, a
+/// divisão é uniforme.
+pub fn allocate_offspring(mean_adjusted_fitness: &[f64], total: usize) -> Vec<usize> {
+    if mean_adjusted_fitness.is_empty()
+    { return Vec::new() }
+
+    let sum: f64 = mean_adjusted_fitness.iter().sum();
+    let shares: Vec<f64> = if sum > 0.0 {
+        mean_adjusted_fitness.iter().map(|f| f / sum * total as f64).collect()
+    } else {
+        vec![total as f64 / mean_adjusted_fitness.len() as f64; mean_adjusted_fitness.len()]
+    };
+
+    let mut allocation: Vec<usize> = shares.iter().map(|share| share.floor() as usize).collect();
+    let mut by_remainder: Vec<usize> = (0..shares.len()).collect();
+    by_remainder.sort_by(|&a, &b| (shares[b] - shares[b].floor()).total_cmp(&(shares[a] - shares[a].floor())).then(a.cmp(&b)));
+
+    let missing = total - allocation.iter().sum::<usize>();
+    for &index in by_remainder.iter().take(missing) {
+        allocation[index] += 1;
+    }
+    allocation
+}
+
+/// Clearing (Pétrowski): dentro de cada nicho, formado pelos indivíduos a
+/// distância menor que `sigma` do vencedor, só os `kappa` melhores mantêm o
+/// fitness; os demais recebem o pior valor possível antes da seleção.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Clearing {
+    pub sigma: f64,
+    pub kappa: usize,
+}
+
+/// Fitness após o clearing e o nicho (índice do vencedor) de cada indivíduo.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ClearingResult {
+    pub fitness: Vec<f64>,
+    pub niches: Vec<usize>,
+}
+
+impl Clearing {
+    /// Valor atribuído aos indivíduos eliminados.
+    pub const CLEARED: f64 = f64::NEG_INFINITY;
+
+    /// Aplica o clearing a `fitness` (maior é melhor). Os indivíduos são
+    /// visitados em ordem decrescente de fitness, com empates resolvidos pelo
+    /// menor índice; cada um ainda sem nicho funda um nicho e vence nele.
+    pub fn apply<E: Encoding>(&self, pop: &Population<E>, fitness: &[f64]) -> ClearingResult {
+        self.apply_with(pop, fitness, E::distance)
+    }
+
+    /// Como `apply`, mas com a métrica passada explicitamente.
+    pub fn apply_with<E, D>(&self, pop: &Population<E>, fitness: &[f64], distance: D) -> ClearingResult
+    where
+        E: Encoding,
+        D: Fn(&E, &E) -> f64,
+    {
+        let individuals = pop.get_individuals();
+        let mut order: Vec<usize> = (0..individuals.len()).collect();
+        order.sort_by(|&a, &b| fitness[b].total_cmp(&fitness[a]).then(a.cmp(&b)));
+
+        let mut cleared = fitness.to_vec();
+        let mut niches: Vec<Option<usize>> = vec![None; individuals.len()];
+        for (position, &winner) in order.iter().enumerate() {
+            if niches[winner].is_some()
+            { continue }
+
+            niches[winner] = Some(winner);
+            let mut kept = 1;
+            for &member in &order[position + 1..] {
+                if niches[member].is_some() || distance(&individuals[winner], &individuals[member]) >= self.sigma
+                { continue }
+
+                niches[member] = Some(winner);
+                if kept < self.kappa {
+                    kept += 1;
+                } else {
+                    cleared[member] = Self::CLEARED;
+                }
+            }
+        }
+
+        ClearingResult {
+            fitness: cleared,
+            niches: niches.into_iter().map(|niche| niche.expect("every individual has a niche")).collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod niching_tests {
+    use crate::population::BinaryEncoding;
+    use super::*;
+
+    fn bits(bitstring: &str) -> BinaryEncoding {
+        BinaryEncoding::from_bitstring(bitstring).unwrap()
+    }
+
+    #[test]
+    fn test_speciate_hand_built_population() {
+        let pop = Population(vec![bits("0000"), bits("1111"), bits("0001"), bits("1110"), bits("0011")]);
+        let species = speciate(&pop, &[], 2.0);
+
+        assert_eq!(species.len(), 3);
+        assert_eq!(species[0], Species { representative: bits("0000"), members: vec![0, 2] });
+        assert_eq!(species[1], Species { representative: bits("1111"), members: vec![1, 3] });
+        assert_eq!(species[2], Species { representative: bits("0011"), members: vec![4] });
+    }
+
+    #[test]
+    fn test_speciate_keeps_previous_representatives() {
+        let pop = Population(vec![bits("0001"), bits("1110")]);
+        let species = speciate(&pop, &[bits("1111"), bits("1010"), bits("0000")], 2.0);
+
+        // "1010" não recebe membros e é descartada
+        let representatives: Vec<_> = species.iter().map(|s| s.representative.clone()).collect();
+        assert_eq!(representatives, vec![bits("1111"), bits("0000")]);
+        assert_eq!(species[0].members, vec![1]);
+        assert_eq!(species[1].members, vec![0]);
+    }
+
+    #[test]
+    fn test_shared_fitness() {
+        let pop = Population(vec![bits("0000"), bits("1111"), bits("0001")]);
+        let species = speciate(&pop, &[], 2.0);
+        assert_eq!(shared_fitness(&[4.0, 3.0, 2.0], &species), vec![2.0, 3.0, 1.0]);
+    }
+
+    #[test]
+    fn test_allocate_offspring_sums_to_total() {
+        assert_eq!(allocate_offspring(&[1.0, 1.0, 1.0], 10), vec![4, 3, 3]);
+        assert_eq!(allocate_offspring(&[3.0, 1.0], 8), vec![6, 2]);
+        assert_eq!(allocate_offspring(&[0.0, 0.0], 5), vec![3, 2]);
+        assert!(allocate_offspring(&[], 5).is_empty());
+
+        for total in 0..50 {
+            let allocation = allocate_offspring(&[0.7, 1.3, 2.9, 0.1], total);
+            assert_eq!(allocation.iter().sum::<usize>(), total);
+        }
+    }
+
+    #[test]
+    fn test_clearing_hand_built_population() {
+        let pop = Population(vec![bits("0000"), bits("0001"), bits("1111"), bits("0011"), bits("1110")]);
+        let fitness = [5.0, 4.0, 3.0, 4.0, 1.0];
+        let result = Clearing { sigma: 2.0, kappa: 1 }.apply(&pop, &fitness);
+
+        // "0011" está a distância 2 de "0000", então funda o próprio nicho
+        assert_eq!(result.niches, vec![0, 0, 2, 3, 2]);
+        assert_eq!(result.fitness, vec![5.0, Clearing::CLEARED, 3.0, 4.0, Clearing::CLEARED]);
+    }
+
+    #[test]
+    fn test_clearing_ties_are_deterministic() {
+        let pop = Population(vec![bits("0001"), bits("0000")]);
+        let result = Clearing { sigma: 2.0, kappa: 1 }.apply(&pop, &[1.0, 1.0]);
+        assert_eq!(result.niches, vec![0, 0]);
+        assert_eq!(result.fitness, vec![1.0, Clearing::CLEARED]);
+    }
+
+    #[test]
+    fn test_clearing_large_kappa_keeps_everything() {
+        let pop = Population(vec![bits("0000"), bits("0001"), bits("0010")]);
+        let fitness = [3.0, 2.0, 1.0];
+        let result = Clearing { sigma: 4.0, kappa: 5 }.apply(&pop, &fitness);
+        assert_eq!(result.fitness, fitness.to_vec());
+        assert_eq!(result.niches, vec![0, 0, 0]);
+    }
+
+    #[test]
+    fn test_clearing_keeps_both_peaks() {
+        use crate::population::RealEncoding;
+
+        // dois picos: x = 0 (altura 1.0) e x = 10 (altura 0.9)
+        let xs: Vec<f64> = (0..=20).map(|i| i as f64 * 0.5).collect();
+        let fitness: Vec<f64> = xs.iter().map(|&x| (1.0 - x.abs() / 10.0).max(0.9 - (x - 10.0).abs() / 10.0)).collect();
+        let pop = Population(xs.iter().map(|&x| RealEncoding(vec![x])).collect());
+
+        let result = Clearing { sigma: 3.0, kappa: 1 }.apply(&pop, &fitness);
+        let mut survivors: Vec<(f64, f64)> = xs.iter().zip(&result.fitness)
+            .filter(|(_, f)| **f != Clearing::CLEARED)
+            .map(|(&x, &f)| (f, x))
+            .collect();
+        survivors.sort_by(|a, b| b.0.total_cmp(&a.0));
+
+        assert_eq!(survivors[0].1, 0.0);
+        assert_eq!(survivors[1].1, 10.0);
+    }
+
+    #[test]
+    fn test_stagnant_species_are_culled_after_patience() {
+        let pop = Population(vec![bits("0000"), bits("0001"), bits("1111"), bits("1110")]);
+        let mut tracker = SpeciesTracker::new(2.0, 2);
+
+        // a espécie de "0000" melhora a cada geração; a de "1111" não
+        let species = tracker.update(&pop, &[1.0, 0.5, 0.8, 0.2]);
+        assert_eq!(species.len(), 2);
+        assert_eq!(tracker.stagnation(), vec![0, 0]);
+
+        let species = tracker.update(&pop, &[2.0, 0.5, 0.8, 0.2]);
+        assert_eq!(species.len(), 2);
+        assert_eq!(tracker.stagnation(), vec![0, 1]);
+
+        let species = tracker.update(&pop, &[3.0, 0.5, 0.8, 0.7]);
+        assert_eq!(species, vec![Species { representative: bits("0000"), members: vec![0, 1] }]);
+        assert_eq!(tracker.representatives(), vec![bits("0000")]);
+
+        // sem o representante antigo, os indivíduos de "1111" fundam uma espécie nova
+        tracker.update(&pop, &[3.0, 0.5, 0.8, 0.7]);
+        assert_eq!(tracker.representatives(), vec![bits("0000"), bits("1111")]);
+        assert_eq!(tracker.stagnation(), vec![1, 0]);
+    }
+
+    #[test]
+    fn test_leading_species_is_never_culled() {
+        let pop = Population(vec![bits("0000"), bits("1111")]);
+        let mut tracker = SpeciesTracker::new(2.0, 1);
+        tracker.update(&pop, &[1.0, 2.0]);
+
+        // ambas estagnam; só a do melhor indivíduo sobrevive
+        let species = tracker.update(&pop, &[1.0, 2.0]);
+        assert_eq!(species.len(), 1);
+        assert_eq!(species[0].representative, bits("1111"));
+        assert_eq!(tracker.stagnation(), vec![1]);
+    }
+}
@@ -1,8 +1,14 @@
+use std::fmt;
 use std::io::{self, BufRead, BufReader, Read};
 use std::num::ParseIntError;
-use std::collections::HashSet;
-use crate::alg::Objective;
-use crate::population::{self, *};
+use std::collections::{HashMap, HashSet};
+use std::collections::hash_map::Entry;
+use std::sync::Arc;
+use crate::alg::{Objective, ScalarObjective};
+use crate::alg::parallel::EvalPool;
+use rand::Rng;
+use rand::seq::index;
+use crate::population::*;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum Literal {
@@ -10,10 +16,73 @@ pub enum Literal {
     NegatedVar(u64),
 }
 
+/// Atribuição de valores às variáveis de uma fórmula, indexada a partir de 0.
+pub trait Valoration {
+    fn num_vars(&self) -> usize;
+    fn value(&self, index: usize) -> Option<bool>;
+}
+
+impl Valoration for [bool] {
+    fn num_vars(&self) -> usize {
+        self.len()
+    }
+
+    fn value(&self, index: usize) -> Option<bool> {
+        self.get(index).copied()
+    }
+}
+
+impl<const N: usize> Valoration for [bool; N] {
+    fn num_vars(&self) -> usize {
+        N
+    }
+
+    fn value(&self, index: usize) -> Option<bool> {
+        self.get(index).copied()
+    }
+}
+
+impl Valoration for Vec<bool> {
+    fn num_vars(&self) -> usize {
+        self.len()
+    }
+
+    fn value(&self, index: usize) -> Option<bool> {
+        self.get(index).copied()
+    }
+}
+
+impl Valoration for BinaryEncoding {
+    fn num_vars(&self) -> usize {
+        self.len()
+    }
+
+    fn value(&self, index: usize) -> Option<bool> {
+        (index < self.len()).then(|| self.get(index))
+    }
+}
+
+impl Literal {
+    /// Literal no formato DIMACS: `v` ou `-v`.
+    pub fn to_dimacs(&self) -> i64 {
+        match self {
+            Literal::Var(var) => *var as i64,
+            Literal::NegatedVar(var) => -(*var as i64),
+        }
+    }
+
+    pub(crate) fn is_satisfied_by<V: Valoration + ?Sized>(&self, valoration: &V) -> Option<bool> {
+        match self {
+            Literal::Var(var) => valoration.value(*var as usize - 1),
+            Literal::NegatedVar(var) => valoration.value(*var as usize - 1).map(|value| !value),
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct Clause(Vec<Literal>);
 impl Clause {
-    fn literals(&self) -> &[Literal] {
+    pub(crate) fn literals(&self) -> &[Literal] {
         &self.0
     }
 
@@ -21,21 +90,27 @@ impl Clause {
         self.0.iter().cloned().collect()
     }
 
-    fn evaluate(&self, valoration: &[bool]) -> Option<bool> {
-        let evaluation_opt: Option<Vec<bool>> = self.literals()
-            .iter()
-            .map(|literal| {
-                match literal {
-                    Literal::Var(index) => valoration.get((index - 1) as usize).copied(),
-                    Literal::NegatedVar(index) => valoration.get((index - 1) as usize).map(|v| !*v)
-                }
-            })
-            .collect();
+    /// Literais como inteiros DIMACS, ordenados e sem repetição: cláusulas
+    /// iguais (como conjuntos) têm a mesma chave.
+    fn key(&self) -> Vec<i64> {
+        let mut key: Vec<i64> = self.0.iter().map(Literal::to_dimacs).collect();
+        key.sort_unstable();
+        key.dedup();
+        key
+    }
 
-        match evaluation_opt {
-            Some(evaluation) => Some(evaluation.iter().any(|v| *v)),
-            None => None
+    /// Para no primeiro literal verdadeiro, sem alocar. `None` se um literal
+    /// anterior a ele referencia uma variável fora da valoração.
+    fn evaluate<V: Valoration + ?Sized>(&self, valoration: &V) -> Option<bool> {
+        for literal in self.literals() {
+            let value = match literal {
+                Literal::Var(index) => valoration.value((index - 1) as usize)?,
+                Literal::NegatedVar(index) => !valoration.value((index - 1) as usize)?,
+            };
+            if value
+            { return Some(true) }
         }
+        Some(false)
     }
 }
 
@@ -50,8 +125,11 @@ impl PartialEq for Clause {
 #[derive(Debug)]
 pub struct Formula {
     num_vars: u64,
+    /// Total de cláusulas, contando as repetições.
     num_clauses: u64,
     clauses: Vec<Clause>,
+    /// Quantas vezes cada cláusula aparece, depois de `collapse_duplicates`.
+    multiplicities: Option<Vec<u64>>,
 }
 
 #[derive(Debug)]
@@ -66,6 +144,31 @@ pub enum FormulaParsingError {
     VarOutOfBounds,
 }
 
+impl fmt::Display for FormulaParsingError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FormulaParsingError::IO(err) => write!(f, "could not read formula: {}", err),
+            FormulaParsingError::Parsing(err) => write!(f, "invalid integer in formula: {}", err),
+            FormulaParsingError::NoHeader => write!(f, "missing \"p cnf\" header"),
+            FormulaParsingError::InvalidHeader => write!(f, "invalid \"p cnf\" header"),
+            FormulaParsingError::EmptyClause => write!(f, "empty clause"),
+            FormulaParsingError::InconsistentNumOfVars => write!(f, "number of variables differs from the header"),
+            FormulaParsingError::InconsistentNumOfClauses => write!(f, "number of clauses differs from the header"),
+            FormulaParsingError::VarOutOfBounds => write!(f, "variable index exceeds the header's variable count"),
+        }
+    }
+}
+
+impl std::error::Error for FormulaParsingError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            FormulaParsingError::IO(err) => Some(err),
+            FormulaParsingError::Parsing(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
 impl From<io::Error> for FormulaParsingError {
     fn from(value: io::Error) -> Self {
         FormulaParsingError::IO(value)
@@ -85,32 +188,62 @@ pub struct FormulaEvaluation {
     pub num_false: usize,
 }
 
+#[derive(Debug, PartialEq)]
+pub struct FormulaStats {
+    pub num_vars: u64,
+    pub num_clauses: u64,
+    /// Cláusulas distintas; difere de `num_clauses` após `collapse_duplicates`.
+    /// As medidas de literais e comprimentos são sobre as distintas.
+    pub num_unique_clauses: u64,
+    pub num_literals: usize,
+    pub min_clause_len: usize,
+    pub max_clause_len: usize,
+    pub mean_clause_len: f64,
+}
+
+impl fmt::Display for FormulaStats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "variables: {}", self.num_vars)?;
+        if self.num_unique_clauses == self.num_clauses {
+            writeln!(f, "clauses: {}", self.num_clauses)?;
+        } else {
+            writeln!(f, "clauses: {} ({} unique)", self.num_clauses, self.num_unique_clauses)?;
+        }
+        writeln!(f, "literals: {}", self.num_literals)?;
+        write!(f, "clause length: min {} / max {} / mean {:.2}", self.min_clause_len, self.max_clause_len, self.mean_clause_len)
+    }
+}
+
 impl Formula {
-    pub fn evaluate(&self, valoration: &[bool]) -> Option<FormulaEvaluation> {
-        if self.num_vars as usize != valoration.len()
-        { return None }
+    pub fn stats(&self) -> FormulaStats {
+        let lengths = self.clauses.iter().map(|clause| clause.literals().len());
+        let num_literals: usize = lengths.clone().sum();
+        FormulaStats {
+            num_vars: self.num_vars,
+            num_clauses: self.num_clauses,
+            num_unique_clauses: self.clauses.len() as u64,
+            num_literals,
+            min_clause_len: lengths.clone().min().unwrap_or(0),
+            max_clause_len: lengths.max().unwrap_or(0),
+            mean_clause_len: num_literals as f64 / self.clauses.len().max(1) as f64,
+        }
+    }
 
-        let evaluation_opt: Option<Vec<bool>> = self.clauses
-            .iter()
-            .map(|clause| clause.evaluate(valoration))
-            .collect();
+    pub fn evaluate<V: Valoration + ?Sized>(&self, valoration: &V) -> Option<FormulaEvaluation> {
+        if self.num_vars as usize != valoration.num_vars()
+        { return None }
 
-        match evaluation_opt {
-            Some(evaluation) => {
-                let num_true = evaluation
-                    .iter()
-                    .fold(0u64, |num_true, &v| if v { num_true + 1 } else { num_true });
-                
-                let formula_evaluation = FormulaEvaluation {
-                        solved: num_true == self.num_clauses,
-                        num_true: num_true as usize,
-                        num_false: (self.num_clauses - num_true) as usize
-                    };
-
-                return Some(formula_evaluation)
-            },
-            None => return None
+        let mut num_true = 0u64;
+        for (index, clause) in self.clauses.iter().enumerate() {
+            if clause.evaluate(valoration)?
+            { num_true += self.multiplicity(index) }
         }
+
+        Some(FormulaEvaluation {
+            solved: num_true == self.num_clauses,
+            num_true: num_true as usize,
+            num_false: (self.num_clauses - num_true) as usize
+        })
     }
 
     pub fn parse_from_dimacs_cnf(reader: impl Read) -> Result<Formula, FormulaParsingError> {
@@ -187,23 +320,170 @@ impl Formula {
             num_vars,
             num_clauses,
             clauses,
+            multiplicities: None,
         })
     }
 
+    /// Une cláusulas idênticas (como conjuntos de literais) em uma só, com a
+    /// multiplicidade igual ao número de ocorrências. A avaliação continua
+    /// contando cada ocorrência, então `num_false` não muda; apenas o número
+    /// de cláusulas distintas diminui. A primeira ocorrência mantém sua posição.
+    pub fn collapse_duplicates(&mut self) {
+        let mut positions: HashMap<Vec<i64>, usize> = HashMap::new();
+        let mut clauses = Vec::new();
+        let mut multiplicities: Vec<u64> = Vec::new();
+
+        for (index, clause) in std::mem::take(&mut self.clauses).into_iter().enumerate() {
+            let multiplicity = self.multiplicity(index);
+            match positions.entry(clause.key()) {
+                Entry::Occupied(position) => multiplicities[*position.get()] += multiplicity,
+                Entry::Vacant(position) => {
+                    position.insert(clauses.len());
+                    clauses.push(clause);
+                    multiplicities.push(multiplicity);
+                },
+            }
+        }
+
+        self.clauses = clauses;
+        self.multiplicities = Some(multiplicities);
+    }
+
+    /// Ocorrências da cláusula `index` de `get_clauses` (1 se a fórmula não
+    /// foi colapsada).
+    pub fn multiplicity(&self, index: usize) -> u64 {
+        self.multiplicities.as_ref().map_or(1, |multiplicities| multiplicities[index])
+    }
+
+    pub fn get_multiplicities(&self) -> Option<&[u64]> {
+        self.multiplicities.as_deref()
+    }
+
     pub fn get_num_vars(&self) -> u64 {
         self.num_vars
     }
 
+    /// Total de cláusulas, contando as repetições.
     pub fn get_num_clauses(&self) -> u64 {
         self.num_clauses
     }
 
+    pub fn get_num_unique_clauses(&self) -> u64 {
+        self.clauses.len() as u64
+    }
+
     pub fn get_clauses(&self) -> &[Clause] {
         &self.clauses
     }
 }
 
-pub struct SATObjective { pub formula: Formula }
+/// Peso de uma cláusula falsa no score escalar do `SATObjective`. Cláusulas
+/// curtas são mais difíceis de satisfazer por acaso, então pesam mais.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ScoreMode {
+    /// Cada cláusula falsa vale 1.
+    #[default]
+    FalseCount,
+    /// `2^(1 - |c|)`: 1 para cláusulas unitárias, 1/4 para 3-cláusulas. Com
+    /// mais de 1023 literais o peso seria subnormal ou zero; fica limitado a
+    /// `f64::MIN_POSITIVE` para continuar positivo.
+    LengthWeighted,
+    /// `1 / (1 + log2 |c|)`: decai mais devagar que `LengthWeighted`.
+    LogLengthWeighted,
+}
+
+impl ScoreMode {
+    pub fn clause_weight(self, len: usize) -> f64 {
+        match self {
+            ScoreMode::FalseCount => 1.0,
+            ScoreMode::LengthWeighted => 2f64.powi(1 - len.min(1 << 16) as i32).max(f64::MIN_POSITIVE),
+            ScoreMode::LogLengthWeighted => 1.0 / (1.0 + (len as f64).log2()),
+        }
+    }
+}
+
+/// A fórmula fica em um `Arc`, então clonar o objetivo (para ilhas ou
+/// execuções paralelas) não copia as cláusulas.
+///
+/// `Objective::eval` sempre conta cláusulas falsas; o `ScoreMode` vale para o
+/// score escalar (`eval_scalar`, `weighted_score`). Como todo peso é positivo,
+/// o score ponderado é zero exatamente quando a fórmula é satisfeita.
+#[derive(Debug, Clone)]
+pub struct SATObjective {
+    pub formula: Arc<Formula>,
+    mode: ScoreMode,
+    /// Peso de cada cláusula (já multiplicado pela multiplicidade), calculado
+    /// na construção; `None` no modo `FalseCount`.
+    weights: Option<Arc<[f64]>>,
+}
+
+impl SATObjective {
+    pub fn new(formula: Formula) -> Self {
+        Self::shared(Arc::new(formula))
+    }
+
+    pub fn shared(formula: Arc<Formula>) -> Self {
+        SATObjective { formula, mode: ScoreMode::FalseCount, weights: None }
+    }
+
+    pub fn with_mode(mut self, mode: ScoreMode) -> Self {
+        self.weights = (mode != ScoreMode::FalseCount).then(|| {
+            self.formula.get_clauses()
+                .iter()
+                .enumerate()
+                .map(|(index, clause)| mode.clause_weight(clause.literals().len()) * self.formula.multiplicity(index) as f64)
+                .collect()
+        });
+        self.mode = mode;
+        self
+    }
+
+    pub fn mode(&self) -> ScoreMode {
+        self.mode
+    }
+
+    /// Score escalar segundo o modo (menor é melhor).
+    pub fn weighted_score(&self, ind: &BinaryEncoding) -> Option<f64> {
+        let Some(weights) = &self.weights else { return self.eval_individual(ind).map(|num_false| num_false as f64) };
+        if self.formula.get_num_vars() as usize != ind.num_vars()
+        { return None }
+
+        self.formula.get_clauses()
+            .iter()
+            .zip(weights.iter())
+            .map(|(clause, weight)| clause.evaluate(ind).map(|satisfied| if satisfied { 0.0 } else { *weight }))
+            .sum()
+    }
+
+    /// Escreve em `scores` o score escalar de cada indivíduo (como
+    /// `eval_scalar`), reaproveitando a capacidade do buffer entre gerações.
+    /// Retorna `None` se algum indivíduo tem a dimensão errada; nesse caso o
+    /// conteúdo de `scores` não é especificado.
+    pub fn eval_into(&self, pop: &Population<BinaryEncoding>, scores: &mut Vec<f64>) -> Option<()> {
+        scores.clear();
+        scores.reserve(pop.get_individuals().len());
+        for ind in pop.get_individuals() {
+            scores.push(self.weighted_score(ind)?);
+        }
+        Some(())
+    }
+
+    /// Se `ind` satisfaz a fórmula, independentemente do modo.
+    pub fn is_solved(&self, ind: &BinaryEncoding) -> Option<bool> {
+        self.formula.evaluate(ind).map(|evaluation| evaluation.solved)
+    }
+
+    fn eval_individual(&self, ind: &BinaryEncoding) -> Option<usize> {
+        self.formula.evaluate(ind).map(|evaluation| evaluation.num_false)
+    }
+
+    /// Avalia a população no `pool` (em paralelo com a feature `parallel`,
+    /// na thread atual sem ela). A ordem dos scores é a mesma da população.
+    pub fn eval_parallel(&self, pop: &Population<BinaryEncoding>, pool: &EvalPool) -> Option<Vec<usize>> {
+        pool.map(pop.get_individuals(), |ind| self.eval_individual(ind)).into_iter().collect()
+    }
+}
+
 impl Objective<BinaryEncoding> for SATObjective {
     type Output = Option<Vec<usize>>;
 
@@ -212,18 +492,308 @@ impl Objective<BinaryEncoding> for SATObjective {
 
         let scores: Option<Vec<usize>> = individuals
             .iter()
-            .map(|ind: &BinaryEncoding| ind.to_bool_slice())
-            .map(|val| {
-                let result = self.formula.evaluate(val);
-                if let Some(evaluation) = result
-                { Some(evaluation.num_false) }
-                else 
-                { None } 
-            })
+            .map(|ind: &BinaryEncoding| self.eval_individual(ind))
             .collect();
 
         scores
     }
+
+    fn required_dimension(&self) -> Option<usize> {
+        Some(self.formula.get_num_vars() as usize)
+    }
+}
+
+impl ScalarObjective<BinaryEncoding> for SATObjective {
+    fn eval_scalar(&self, pop: &Population<BinaryEncoding>) -> Option<Vec<f64>> {
+        let mut scores = Vec::new();
+        self.eval_into(pop, &mut scores).map(|()| scores)
+    }
+
+    fn required_dimension(&self) -> Option<usize> {
+        Objective::required_dimension(self)
+    }
+}
+
+/// Número de bits ligados de cada indivíduo.
+pub struct OneMaxObjective;
+impl Objective<BinaryEncoding> for OneMaxObjective {
+    type Output = Vec<usize>;
+
+    fn eval(&self, pop: &Population<BinaryEncoding>) -> Self::Output {
+        pop.get_individuals().iter().map(BinaryEncoding::count_ones).collect()
+    }
+}
+
+impl ScalarObjective<BinaryEncoding> for OneMaxObjective {
+    fn eval_scalar(&self, pop: &Population<BinaryEncoding>) -> Option<Vec<f64>> {
+        Some(self.eval(pop).into_iter().map(|score| score as f64).collect())
+    }
+}
+
+/// Parâmetro inválido na construção de um objetivo combinatório.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ObjectiveParameterError {
+    /// Quadrado latino de ordem zero.
+    ZeroOrder,
+    /// Aresta com uma ponta fora de `0..num_vertices`.
+    EdgeOutOfRange { edge: (usize, usize), num_vertices: usize },
+    /// Aresta de um vértice para ele mesmo, que nunca pode ser satisfeita.
+    SelfLoop { vertex: usize },
+}
+
+impl fmt::Display for ObjectiveParameterError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ObjectiveParameterError::ZeroOrder => write!(f, "latin square order must be greater than 0"),
+            ObjectiveParameterError::EdgeOutOfRange { edge: (u, v), num_vertices } =>
+                write!(f, "edge ({}, {}) references a vertex outside 0..{}", u, v, num_vertices),
+            ObjectiveParameterError::SelfLoop { vertex } => write!(f, "self-loop on vertex {}", vertex),
+        }
+    }
+}
+
+impl std::error::Error for ObjectiveParameterError {}
+
+/// Coloração de grafos com alfabetos categóricos: o gene `v` é a cor do
+/// vértice `v`, e cada vértice pode ter um número diferente de cores
+/// permitidas (dado pelo [`CategoricalDomain`]). O score é o número de
+/// arestas com as duas pontas da mesma cor mais o número de vértices com cor
+/// fora do seu alfabeto (a minimizar).
+#[derive(Debug, Clone, PartialEq)]
+pub struct GraphColoringObjective {
+    domain: CategoricalDomain,
+    edges: Vec<(usize, usize)>,
+}
+
+impl GraphColoringObjective {
+    /// Um vértice por gene do domínio. Falha em arestas com pontas fora do
+    /// grafo ou em laços.
+    pub fn new(domain: CategoricalDomain, edges: Vec<(usize, usize)>) -> Result<Self, ObjectiveParameterError> {
+        let num_vertices = domain.dim();
+        for &(u, v) in &edges {
+            if u >= num_vertices || v >= num_vertices
+            { return Err(ObjectiveParameterError::EdgeOutOfRange { edge: (u, v), num_vertices }) }
+            if u == v
+            { return Err(ObjectiveParameterError::SelfLoop { vertex: u }) }
+        }
+        Ok(GraphColoringObjective { domain, edges })
+    }
+
+    pub fn num_vertices(&self) -> usize {
+        self.domain.dim()
+    }
+
+    pub fn edges(&self) -> &[(usize, usize)] {
+        &self.edges
+    }
+
+    /// Cores permitidas por vértice, para construir o gerador da população.
+    pub fn domain(&self) -> &CategoricalDomain {
+        &self.domain
+    }
+
+    /// `None` se a coloração não tem uma cor por vértice.
+    pub fn conflicts(&self, coloring: &CategoricalEncoding) -> Option<usize> {
+        if coloring.0.len() != self.num_vertices()
+        { return None }
+
+        let clashes = self.edges.iter().filter(|&&(u, v)| coloring.0[u] == coloring.0[v]).count();
+        let out_of_domain = coloring.0.iter().zip(self.domain.cardinalities()).filter(|&(&color, &colors)| color >= colors).count();
+        Some(clashes + out_of_domain)
+    }
+}
+
+impl Objective<CategoricalEncoding> for GraphColoringObjective {
+    type Output = Option<Vec<usize>>;
+
+    fn eval(&self, pop: &Population<CategoricalEncoding>) -> Self::Output {
+        pop.get_individuals().iter().map(|coloring| self.conflicts(coloring)).collect()
+    }
+
+    fn required_dimension(&self) -> Option<usize> {
+        Some(self.num_vertices())
+    }
+}
+
+/// Quadrado latino n×n lido linha a linha de um CategoricalEncoding com
+/// cardinalidade `n` em cada gene. O score é o número de repetições em
+/// linhas e colunas (`n - símbolos distintos` em cada uma) mais o número de
+/// células com símbolo fora de `0..n`; zero indica um quadrado latino válido.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LatinSquareObjective {
+    n: usize,
+}
+
+impl LatinSquareObjective {
+    pub fn new(n: usize) -> Result<Self, ObjectiveParameterError> {
+        if n == 0
+        { return Err(ObjectiveParameterError::ZeroOrder) }
+
+        Ok(LatinSquareObjective { n })
+    }
+
+    pub fn n(&self) -> usize {
+        self.n
+    }
+
+    /// Domínio dos genes: `n²` células com `n` símbolos cada.
+    pub fn domain(&self) -> CategoricalDomain {
+        CategoricalDomain::new(vec![self.n as u32; self.n * self.n]).expect("n is validated in the constructor")
+    }
+
+    /// `None` se o genoma não tem `n²` genes.
+    pub fn violations(&self, grid: &CategoricalEncoding) -> Option<usize> {
+        let n = self.n;
+        if grid.0.len() != n * n
+        { return None }
+
+        let duplicates = |symbols: HashSet<u32>| n - symbols.len();
+        let rows: usize = (0..n).map(|row| duplicates((0..n).map(|col| grid.0[row * n + col]).collect())).sum();
+        let cols: usize = (0..n).map(|col| duplicates((0..n).map(|row| grid.0[row * n + col]).collect())).sum();
+        let out_of_range = grid.0.iter().filter(|&&cell| cell as usize >= n).count();
+        Some(rows + cols + out_of_range)
+    }
+
+    pub fn is_solution(&self, grid: &CategoricalEncoding) -> bool {
+        self.violations(grid) == Some(0)
+    }
+
+    /// Exibe o genoma como a grade n×n, uma linha por linha do quadrado.
+    pub fn grid<'a>(&self, individual: &'a CategoricalEncoding) -> LatinSquareGrid<'a> {
+        LatinSquareGrid { n: self.n, cells: &individual.0 }
+    }
+}
+
+impl Objective<CategoricalEncoding> for LatinSquareObjective {
+    type Output = Option<Vec<usize>>;
+
+    fn eval(&self, pop: &Population<CategoricalEncoding>) -> Self::Output {
+        pop.get_individuals().iter().map(|grid| self.violations(grid)).collect()
+    }
+
+    fn required_dimension(&self) -> Option<usize> {
+        Some(self.n * self.n)
+    }
+}
+
+pub struct LatinSquareGrid<'a> {
+    n: usize,
+    cells: &'a [u32],
+}
+
+impl fmt::Display for LatinSquareGrid<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let width = self.n.saturating_sub(1).to_string().len();
+        for row in self.cells.chunks(self.n.max(1)) {
+            let cells: Vec<String> = row.iter().map(|cell| format!("{:>width$}", cell)).collect();
+            writeln!(f, "{}", cells.join(" "))?;
+        }
+        Ok(())
+    }
+}
+
+/// Estimativa do número de cláusulas falsas a partir de uma amostra.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SampledScore {
+    pub num_false: f64,
+    /// Erro padrão da estimativa (com correção de população finita); zero
+    /// quando a amostra cobre todas as cláusulas.
+    pub standard_error: f64,
+}
+
+/// Avalia os indivíduos contra uma amostra de `sample_size` cláusulas,
+/// reamostrada a cada `resample_every` gerações. `eval` devolve a estimativa
+/// do número de cláusulas falsas na fórmula inteira; `exact` avalia a
+/// fórmula completa, para o melhor indivíduo reportado.
+#[derive(Debug, Clone)]
+pub struct SampledSATObjective {
+    formula: Arc<Formula>,
+    resample_every: usize,
+    sample: Vec<usize>,
+}
+
+impl SampledSATObjective {
+    /// `sample_size` é limitado ao número de cláusulas e `resample_every`
+    /// vale no mínimo 1.
+    pub fn new<R: Rng + ?Sized>(formula: Arc<Formula>, sample_size: usize, resample_every: usize, rng: &mut R) -> Self {
+        let sample_size = sample_size.min(formula.get_clauses().len());
+        let mut objective = SampledSATObjective { formula, resample_every: resample_every.max(1), sample: Vec::with_capacity(sample_size) };
+        objective.sample = index::sample(rng, objective.formula.get_clauses().len(), sample_size).into_vec();
+        objective
+    }
+
+    pub fn sample(&self) -> &[usize] {
+        &self.sample
+    }
+
+    pub fn resample<R: Rng + ?Sized>(&mut self, rng: &mut R) {
+        self.sample = index::sample(rng, self.formula.get_clauses().len(), self.sample.len()).into_vec();
+    }
+
+    /// Chamado no início de cada geração; reamostra quando `generation` é
+    /// múltiplo de `resample_every` e retorna se houve reamostragem.
+    pub fn on_generation<R: Rng + ?Sized>(&mut self, generation: usize, rng: &mut R) -> bool {
+        let due = generation.is_multiple_of(self.resample_every);
+        if due { self.resample(rng) }
+        due
+    }
+
+    pub fn exact(&self, ind: &BinaryEncoding) -> Option<usize> {
+        self.formula.evaluate(ind).map(|evaluation| evaluation.num_false)
+    }
+
+    pub fn estimate(&self, ind: &BinaryEncoding) -> Option<SampledScore> {
+        if self.formula.get_num_vars() as usize != ind.num_vars()
+        { return None }
+
+        let clauses = self.formula.get_clauses();
+        let (total, n) = (clauses.len() as f64, self.sample.len() as f64);
+        if self.sample.is_empty()
+        { return Some(SampledScore { num_false: 0.0, standard_error: 0.0 }) }
+
+        // ocorrências falsas de cada cláusula amostrada (0 ou a multiplicidade)
+        let false_counts = self.sample
+            .iter()
+            .map(|&clause| clauses[clause].evaluate(ind).map(|satisfied| if satisfied { 0.0 } else { self.formula.multiplicity(clause) as f64 }))
+            .collect::<Option<Vec<f64>>>()?;
+        let mean = false_counts.iter().sum::<f64>() / n;
+        let variance = (false_counts.iter().map(|count| count * count).sum::<f64>() / n - mean * mean).max(0.0);
+        let correction = if total > 1.0 { (total - n) / (total - 1.0) } else { 0.0 };
+
+        Some(SampledScore {
+            num_false: mean * total,
+            standard_error: total * (variance / n * correction).sqrt(),
+        })
+    }
+}
+
+impl Objective<BinaryEncoding> for SampledSATObjective {
+    type Output = Option<Vec<f64>>;
+
+    fn eval(&self, pop: &Population<BinaryEncoding>) -> Self::Output {
+        pop.get_individuals().iter().map(|ind| self.estimate(ind).map(|score| score.num_false)).collect()
+    }
+
+    fn required_dimension(&self) -> Option<usize> {
+        Some(self.formula.get_num_vars() as usize)
+    }
+}
+
+/// Fórmula 3-SAT aleatória (literais podem se repetir numa cláusula), para
+/// os testes deste módulo e de `sat_init`.
+#[cfg(test)]
+impl Formula {
+    pub(crate) fn random_3sat<R: Rng + ?Sized>(num_vars: u64, num_clauses: usize, rng: &mut R) -> Formula {
+        let mut dimacs = format!("p cnf {} {}\n", num_vars, num_clauses);
+        for _ in 0..num_clauses {
+            for _ in 0..3 {
+                let var = rng.gen_range(1..=num_vars) as i64;
+                dimacs.push_str(&format!("{} ", if rng.gen_bool(0.5) { var } else { -var }));
+            }
+            dimacs.push_str("0\n");
+        }
+        Formula::parse_from_dimacs_cnf(io::Cursor::new(dimacs)).unwrap()
+    }
 }
 
 #[cfg(test)]
@@ -239,7 +809,7 @@ mod sat_objective_tests {
             1 -3 0
             2 3 0
             %"#;
-        let expected_clauses = vec![
+        let expected_clauses = [
             Clause(vec![Var(1), NegatedVar(3)]),
             Clause(vec![Var(2), Var(3)])
         ];
@@ -346,4 +916,412 @@ mod sat_objective_tests {
             _ => panic!("Expected an error")
         }
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_formula_stats() {
+        let dimacs_cnf =
+            r#"p cnf 3 3
+            1 -3 0
+            2 3 -1 0
+            2 0
+            %"#;
+        let formula = Formula::parse_from_dimacs_cnf(Cursor::new(dimacs_cnf)).unwrap();
+        assert_eq!(formula.stats(), FormulaStats {
+            num_vars: 3,
+            num_clauses: 3,
+            num_unique_clauses: 3,
+            num_literals: 6,
+            min_clause_len: 1,
+            max_clause_len: 3,
+            mean_clause_len: 2.0,
+        });
+    }
+
+    #[test]
+    fn test_boxed_scalar_objective_by_name() {
+        let dimacs_cnf =
+            r#"p cnf 3 2
+            1 -3 0
+            2 3 0
+            %"#;
+        let formula = Formula::parse_from_dimacs_cnf(Cursor::new(dimacs_cnf)).unwrap();
+        let mut available: Vec<(&str, Box<dyn ScalarObjective<BinaryEncoding>>)> = vec![
+            ("onemax", Box::new(OneMaxObjective)),
+            ("sat", Box::new(SATObjective::new(formula))),
+        ];
+        let pop = Population(vec![
+            BinaryEncoding::from_bools(&[true, true, false]),
+            BinaryEncoding::from_bools(&[false, false, true]),
+        ]);
+
+        let position = available.iter().position(|(name, _)| *name == "onemax").unwrap();
+        let (_, onemax) = available.swap_remove(position);
+        assert_eq!(onemax.eval(&pop), Some(vec![2.0, 1.0]));
+
+        let (_, sat) = available.pop().unwrap();
+        assert_eq!(sat.eval(&pop), Some(vec![0.0, 1.0]));
+    }
+
+    #[test]
+    fn test_formula_is_send_and_sync() {
+        fn assert_send_sync<T: Send + Sync>() {}
+        assert_send_sync::<Formula>();
+        assert_send_sync::<SATObjective>();
+    }
+
+    #[test]
+    fn test_sampled_sat_converges_to_exact() {
+        use rand::{SeedableRng, rngs::StdRng};
+
+        let mut rng = StdRng::seed_from_u64(7);
+        let formula = Arc::new(Formula::random_3sat(50, 400, &mut rng));
+        let pop = Population((0..30).map(|_| BinaryEncoding::from_bools(&(0..50).map(|_| rng.gen_bool(0.5)).collect::<Vec<_>>())).collect());
+
+        let mean_error = |sample_size: usize, rng: &mut StdRng| {
+            let objective = SampledSATObjective::new(Arc::clone(&formula), sample_size, 1, rng);
+            pop.get_individuals()
+                .iter()
+                .map(|ind| (objective.estimate(ind).unwrap().num_false - objective.exact(ind).unwrap() as f64).abs())
+                .sum::<f64>() / pop.get_individuals().len() as f64
+        };
+
+        assert!(mean_error(40, &mut rng) > mean_error(300, &mut rng));
+
+        let full = SampledSATObjective::new(Arc::clone(&formula), 1000, 1, &mut rng);
+        assert_eq!(full.sample().len(), 400);
+        for ind in pop.get_individuals() {
+            let score = full.estimate(ind).unwrap();
+            assert!((score.num_false - full.exact(ind).unwrap() as f64).abs() < 1e-9);
+            assert_eq!(score.standard_error, 0.0);
+        }
+    }
+
+    /// Fórmula 3-SAT em que cada cláusula aparece de 1 a 3 vezes, com os
+    /// literais das repetições embaralhados.
+    fn repeated_3sat<R: Rng>(num_vars: u64, num_distinct: usize, rng: &mut R) -> (Formula, u64) {
+        use rand::seq::SliceRandom;
+
+        let mut clauses = Vec::new();
+        for _ in 0..num_distinct {
+            let clause: Vec<i64> = (0..3)
+                .map(|_| {
+                    let var = rng.gen_range(1..=num_vars) as i64;
+                    if rng.gen_bool(0.5) { var } else { -var }
+                })
+                .collect();
+            for _ in 0..rng.gen_range(1..=3) {
+                let mut copy = clause.clone();
+                copy.shuffle(rng);
+                clauses.push(copy);
+            }
+        }
+        clauses.shuffle(rng);
+
+        let mut dimacs = format!("p cnf {} {}\n", num_vars, clauses.len());
+        for clause in &clauses {
+            let literals: Vec<String> = clause.iter().map(i64::to_string).collect();
+            dimacs.push_str(&format!("{} 0\n", literals.join(" ")));
+        }
+        (Formula::parse_from_dimacs_cnf(Cursor::new(dimacs)).unwrap(), clauses.len() as u64)
+    }
+
+    #[test]
+    fn test_collapsed_formula_evaluates_like_expanded() {
+        use rand::{SeedableRng, rngs::StdRng};
+
+        let mut rng = StdRng::seed_from_u64(11);
+        let (expanded, total) = repeated_3sat(10, 60, &mut rng);
+        let mut collapsed = repeated_3sat(10, 60, &mut StdRng::seed_from_u64(11)).0;
+        collapsed.collapse_duplicates();
+
+        assert_eq!(collapsed.get_num_clauses(), total);
+        assert!(collapsed.get_num_unique_clauses() < total);
+        assert_eq!(collapsed.get_multiplicities().unwrap().iter().sum::<u64>(), total);
+        assert_eq!(expanded.get_multiplicities(), None);
+
+        let pop = BinaryPopGenerator::new(10, 200).gen_pop();
+        for ind in pop.get_individuals() {
+            assert_eq!(collapsed.evaluate(ind), expanded.evaluate(ind));
+        }
+        let (collapsed, expanded) = (SATObjective::new(collapsed), SATObjective::new(expanded));
+        assert_eq!(collapsed.eval(&pop), expanded.eval(&pop));
+
+        // amostra completa: a estimativa ponderada é exata
+        let sampled = SampledSATObjective::new(collapsed.formula.clone(), usize::MAX, 1, &mut rng);
+        for ind in pop.get_individuals() {
+            let estimate = sampled.estimate(ind).unwrap();
+            assert!((estimate.num_false - sampled.exact(ind).unwrap() as f64).abs() < 1e-9);
+            assert_eq!(estimate.standard_error, 0.0);
+        }
+    }
+
+    #[test]
+    fn test_collapse_duplicates_counts() {
+        let dimacs_cnf =
+            r#"p cnf 3 5
+            1 -3 0
+            2 3 0
+            -3 1 0
+            1 -3 1 0
+            2 3 0
+            %"#;
+        let mut formula = Formula::parse_from_dimacs_cnf(Cursor::new(dimacs_cnf)).unwrap();
+        formula.collapse_duplicates();
+        assert_eq!(formula.get_multiplicities(), Some([3, 2].as_slice()));
+
+        let stats = formula.stats();
+        assert_eq!((stats.num_clauses, stats.num_unique_clauses), (5, 2));
+        assert!(stats.to_string().contains("clauses: 5 (2 unique)"));
+
+        // x1 = x3 = falso viola só `2 3`, que conta duas vezes
+        let evaluation = formula.evaluate(&[false, false, false]).unwrap();
+        assert_eq!(evaluation, FormulaEvaluation { solved: false, num_true: 3, num_false: 2 });
+        assert!(formula.evaluate(&[true, true, false]).unwrap().solved);
+
+        // colapsar de novo preserva as multiplicidades
+        formula.collapse_duplicates();
+        assert_eq!(formula.get_multiplicities(), Some([3, 2].as_slice()));
+    }
+
+    #[test]
+    fn test_sampled_sat_resampling_cadence() {
+        use rand::{SeedableRng, rngs::StdRng};
+
+        let mut rng = StdRng::seed_from_u64(3);
+        let formula = Arc::new(Formula::random_3sat(20, 200, &mut rng));
+        let mut objective = SampledSATObjective::new(formula, 10, 3, &mut rng);
+
+        let resampled: Vec<usize> = (0..10).filter(|&generation| objective.on_generation(generation, &mut rng)).collect();
+        assert_eq!(resampled, vec![0, 3, 6, 9]);
+
+        let before = objective.sample().to_vec();
+        assert!(!objective.on_generation(4, &mut rng));
+        assert_eq!(objective.sample(), before.as_slice());
+    }
+
+    fn mixed_length_formula() -> Formula {
+        let dimacs_cnf =
+            r#"p cnf 4 4
+            1 0
+            2 3 0
+            -1 -2 -3 4 0
+            -1 -2 -3 0
+            %"#;
+        Formula::parse_from_dimacs_cnf(Cursor::new(dimacs_cnf)).unwrap()
+    }
+
+    #[test]
+    fn test_length_weighted_scores() {
+        let formula = Arc::new(mixed_length_formula());
+        let modes = [ScoreMode::FalseCount, ScoreMode::LengthWeighted, ScoreMode::LogLengthWeighted];
+        let objectives = modes.map(|mode| SATObjective::shared(formula.clone()).with_mode(mode));
+        let scores = |bits: [bool; 4]| objectives.each_ref().map(|objective| objective.weighted_score(&BinaryEncoding::from_bools(&bits)).unwrap());
+        let log3 = 1.0 / (1.0 + 3f64.log2());
+
+        // falsifica só a cláusula unitária
+        assert_eq!(scores([false, true, false, false]), [1.0, 1.0, 1.0]);
+        // só `2 3`
+        assert_eq!(scores([true, false, false, false]), [1.0, 0.5, 0.5]);
+        // `1` e `2 3`
+        assert_eq!(scores([false, false, false, false]), [2.0, 1.5, 1.5]);
+        // as duas cláusulas longas
+        let [count, weighted, log_weighted] = scores([true, true, true, false]);
+        assert_eq!((count, weighted), (2.0, 0.375));
+        assert!((log_weighted - (1.0 / 3.0 + log3)).abs() < 1e-12);
+
+        assert_eq!(objectives[1].mode(), ScoreMode::LengthWeighted);
+        assert_eq!(objectives[1].weighted_score(&BinaryEncoding::zeros(3)), None);
+    }
+
+    #[test]
+    fn test_score_mode_changes_ordering() {
+        let formula = Arc::new(mixed_length_formula());
+        let pop = Population(vec![
+            BinaryEncoding::from_bools(&[false, true, false, false]),
+            BinaryEncoding::from_bools(&[true, true, true, false]),
+        ]);
+
+        // contando cláusulas, o primeiro é melhor; ponderando, o segundo
+        let count = SATObjective::shared(formula.clone()).eval_scalar(&pop).unwrap();
+        assert!(count[0] < count[1]);
+        for mode in [ScoreMode::LengthWeighted, ScoreMode::LogLengthWeighted] {
+            let weighted = SATObjective::shared(formula.clone()).with_mode(mode).eval_scalar(&pop).unwrap();
+            assert!(weighted[1] < weighted[0], "{:?}: {:?}", mode, weighted);
+        }
+    }
+
+    #[test]
+    fn test_length_weight_stays_positive_for_long_clauses() {
+        assert_eq!(ScoreMode::LengthWeighted.clause_weight(3), 0.25);
+        assert_eq!(ScoreMode::LengthWeighted.clause_weight(1023), f64::MIN_POSITIVE);
+        for len in [1024, 1076, 5000, usize::MAX] {
+            assert_eq!(ScoreMode::LengthWeighted.clause_weight(len), f64::MIN_POSITIVE);
+        }
+    }
+
+    #[test]
+    fn test_score_mode_keeps_solved_exact() {
+        let formula = Arc::new(mixed_length_formula());
+        let solution = BinaryEncoding::from_bools(&[true, true, false, false]);
+        let unsolved = BinaryEncoding::from_bools(&[true, true, true, false]);
+        for mode in [ScoreMode::FalseCount, ScoreMode::LengthWeighted, ScoreMode::LogLengthWeighted] {
+            let objective = SATObjective::shared(formula.clone()).with_mode(mode);
+            assert_eq!(objective.is_solved(&solution), Some(true));
+            assert_eq!(objective.weighted_score(&solution), Some(0.0));
+            assert_eq!(objective.is_solved(&unsolved), Some(false));
+            assert!(objective.weighted_score(&unsolved).unwrap() > 0.0);
+            // `eval` continua contando cláusulas falsas
+            assert_eq!(objective.eval(&Population(vec![solution.clone(), unsolved.clone()])), Some(vec![0, 2]));
+        }
+    }
+
+    #[test]
+    fn test_weighted_score_counts_multiplicity() {
+        let dimacs_cnf =
+            r#"p cnf 2 3
+            1 2 0
+            2 1 0
+            -1 0
+            %"#;
+        let mut formula = Formula::parse_from_dimacs_cnf(Cursor::new(dimacs_cnf)).unwrap();
+        formula.collapse_duplicates();
+        let objective = SATObjective::new(formula).with_mode(ScoreMode::LengthWeighted);
+        assert_eq!(objective.weighted_score(&BinaryEncoding::from_bools(&[false, false])), Some(1.0));
+        assert_eq!(objective.weighted_score(&BinaryEncoding::from_bools(&[true, false])), Some(1.0));
+    }
+
+    /// Avaliação anterior, que coletava os valores de cada cláusula.
+    fn collecting_num_false(formula: &Formula, ind: &BinaryEncoding) -> Option<usize> {
+        if formula.get_num_vars() as usize != ind.num_vars()
+        { return None }
+        let satisfied: Option<Vec<bool>> = formula.get_clauses()
+            .iter()
+            .map(|clause| {
+                let values: Option<Vec<bool>> = clause.literals()
+                    .iter()
+                    .map(|literal| match literal {
+                        Literal::Var(index) => ind.value((index - 1) as usize),
+                        Literal::NegatedVar(index) => ind.value((index - 1) as usize).map(|v| !v),
+                    })
+                    .collect();
+                values.map(|values| values.iter().any(|v| *v))
+            })
+            .collect();
+        satisfied.map(|satisfied| satisfied.iter().filter(|v| !**v).count())
+    }
+
+    #[test]
+    fn test_streaming_evaluation_matches_collecting() {
+        use rand::{SeedableRng, rngs::StdRng};
+
+        let mut rng = StdRng::seed_from_u64(17);
+        let formula = Arc::new(Formula::random_3sat(30, 150, &mut rng));
+        let objective = SATObjective::shared(formula.clone());
+        let mut buffer = Vec::new();
+        for _ in 0..5 {
+            let pop = BinaryPopGenerator::new(30, 50).gen_pop();
+            let expected: Vec<usize> = pop.get_individuals().iter().map(|ind| collecting_num_false(&formula, ind).unwrap()).collect();
+            assert_eq!(objective.eval(&pop), Some(expected.clone()));
+
+            objective.eval_into(&pop, &mut buffer).unwrap();
+            assert_eq!(buffer, expected.iter().map(|&n| n as f64).collect::<Vec<_>>());
+            assert_eq!(objective.eval_scalar(&pop), Some(buffer.clone()));
+        }
+
+        let pop = Population(vec![BinaryEncoding::zeros(30), BinaryEncoding::zeros(29)]);
+        assert_eq!(objective.eval(&pop), None);
+        assert_eq!(objective.eval_into(&pop, &mut buffer), None);
+        assert_eq!(collecting_num_false(&formula, &pop.get_individuals()[1]), None);
+    }
+
+    #[test]
+    fn test_sampled_sat_dimension_mismatch() {
+        use rand::{SeedableRng, rngs::StdRng};
+
+        let dimacs = "p cnf 5 3\n1 -2 3 0\n-3 4 5 0\n2 -4 -5 0\n";
+        let formula = Arc::new(Formula::parse_from_dimacs_cnf(Cursor::new(dimacs)).unwrap());
+        let objective = SampledSATObjective::new(formula, 5, 1, &mut StdRng::seed_from_u64(3));
+        assert_eq!(objective.estimate(&BinaryEncoding::zeros(4)), None);
+        assert_eq!(objective.required_dimension(), Some(5));
+    }
+
+    #[test]
+    fn test_graph_coloring_with_heterogeneous_domains() {
+        // triângulo 0-1-2 mais o vértice 3 ligado a 0; o vértice 3 só admite a cor 0
+        let domain = CategoricalDomain::new(vec![3, 3, 3, 1]).unwrap();
+        let objective = GraphColoringObjective::new(domain.clone(), vec![(0, 1), (1, 2), (0, 2), (0, 3)]).unwrap();
+
+        let proper = CategoricalEncoding(vec![1, 2, 0, 0]);
+        let clashing = CategoricalEncoding(vec![0, 2, 0, 0]);
+        assert!(domain.contains(&proper) && domain.contains(&clashing));
+        assert_eq!(objective.eval(&Population(vec![proper, clashing])), Some(vec![0, 2]));
+        assert_eq!(Objective::required_dimension(&objective), Some(4));
+
+        // fora do domínio: o vértice 3 com a cor 1 não colide com ninguém, mas conta
+        assert_eq!(objective.conflicts(&CategoricalEncoding(vec![0, 1, 2, 1])), Some(1));
+
+        // busca exaustiva: só as colorações dentro do domínio podem ser próprias
+        let proper_count = (0..81u32)
+            .map(|code| CategoricalEncoding(vec![code % 3, code / 3 % 3, code / 9 % 3, code / 27]))
+            .filter(|coloring| objective.conflicts(coloring) == Some(0))
+            .count();
+        assert_eq!(proper_count, 4);
+    }
+
+    #[test]
+    fn test_graph_coloring_validation() {
+        let domain = CategoricalDomain::new(vec![2; 3]).unwrap();
+        assert_eq!(
+            GraphColoringObjective::new(domain.clone(), vec![(0, 1), (1, 3)]),
+            Err(ObjectiveParameterError::EdgeOutOfRange { edge: (1, 3), num_vertices: 3 })
+        );
+        assert_eq!(GraphColoringObjective::new(domain.clone(), vec![(2, 2)]), Err(ObjectiveParameterError::SelfLoop { vertex: 2 }));
+
+        let objective = GraphColoringObjective::new(domain, vec![(0, 1), (1, 2)]).unwrap();
+        assert_eq!(objective.conflicts(&CategoricalEncoding(vec![0, 1])), None);
+        assert_eq!(objective.eval(&Population(vec![CategoricalEncoding(vec![0, 1, 0]), CategoricalEncoding(vec![0])])), None);
+    }
+
+    #[test]
+    fn test_latin_square_objective() {
+        let objective = LatinSquareObjective::new(4).unwrap();
+        let square = CategoricalEncoding(vec![
+            0, 1, 2, 3,
+            1, 0, 3, 2,
+            2, 3, 0, 1,
+            3, 2, 1, 0,
+        ]);
+        assert!(objective.domain().contains(&square));
+        assert!(objective.is_solution(&square));
+
+        // trocar a célula (0, 0) de 0 para 1 repete o 1 na linha 0 e na coluna 0
+        let mut broken = square.clone();
+        broken.0[0] = 1;
+        assert_eq!(objective.violations(&broken), Some(2));
+        assert!(!objective.is_solution(&broken));
+
+        // trocar duas células da mesma linha mantém a linha e quebra duas colunas
+        let mut swapped = square.clone();
+        swapped.0.swap(0, 1);
+        assert_eq!(objective.violations(&swapped), Some(2));
+
+        assert_eq!(objective.eval(&Population(vec![square.clone(), broken])), Some(vec![0, 2]));
+        assert_eq!(objective.violations(&CategoricalEncoding(vec![0; 15])), None);
+        assert_eq!(Objective::required_dimension(&objective), Some(16));
+
+        assert_eq!(objective.grid(&square).to_string(), "0 1 2 3\n1 0 3 2\n2 3 0 1\n3 2 1 0\n");
+    }
+
+    #[test]
+    fn test_latin_square_symbols_out_of_range() {
+        let objective = LatinSquareObjective::new(2).unwrap();
+        // linhas e colunas sem repetição, mas com símbolos fora do alfabeto
+        let grid = CategoricalEncoding(vec![0, 5, 5, 0]);
+        assert_eq!(objective.violations(&grid), Some(2));
+        assert!(!objective.is_solution(&grid));
+        assert!(!objective.domain().contains(&grid));
+        assert!(objective.is_solution(&CategoricalEncoding(vec![0, 1, 1, 0])));
+
+        assert_eq!(LatinSquareObjective::new(0), Err(ObjectiveParameterError::ZeroOrder));
+    }
+}
@@ -0,0 +1,144 @@
+use std::fmt;
+use super::ScalarObjective;
+use crate::population::{Encoding, Population};
+
+#[derive(Debug)]
+pub enum EvalPoolError {
+    /// `threads = 0`; para o padrão do rayon, use `None`.
+    ZeroThreads,
+    #[cfg(feature = "parallel")]
+    Build(rayon::ThreadPoolBuildError),
+}
+
+impl fmt::Display for EvalPoolError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            EvalPoolError::ZeroThreads => write!(f, "the evaluation pool needs at least one thread"),
+            #[cfg(feature = "parallel")]
+            EvalPoolError::Build(err) => write!(f, "could not build the evaluation thread pool: {}", err),
+        }
+    }
+}
+
+impl std::error::Error for EvalPoolError {}
+
+/// Pool de avaliação, construído uma vez e reutilizado a cada geração. Com
+/// a feature `parallel`, `threads = None` usa o pool global do rayon e
+/// `Some(n)` um pool próprio de `n` threads; sem ela, tudo roda na thread
+/// atual e `threads` é ignorado.
+pub struct EvalPool {
+    #[cfg(feature = "parallel")]
+    pool: Option<rayon::ThreadPool>,
+}
+
+impl EvalPool {
+    pub fn new(threads: Option<usize>) -> Result<Self, EvalPoolError> {
+        if threads == Some(0)
+        { return Err(EvalPoolError::ZeroThreads) }
+
+        #[cfg(feature = "parallel")]
+        {
+            let pool = threads
+                .map(|threads| rayon::ThreadPoolBuilder::new().num_threads(threads).build())
+                .transpose()
+                .map_err(EvalPoolError::Build)?;
+            Ok(EvalPool { pool })
+        }
+        #[cfg(not(feature = "parallel"))]
+        Ok(EvalPool {})
+    }
+
+    /// Threads que executam `map`.
+    pub fn threads(&self) -> usize {
+        #[cfg(feature = "parallel")]
+        return self.pool.as_ref().map_or_else(rayon::current_num_threads, rayon::ThreadPool::current_num_threads);
+        #[cfg(not(feature = "parallel"))]
+        1
+    }
+
+    /// Aplica `f` a cada item, preservando a ordem.
+    pub fn map<T, U, F>(&self, items: &[T], f: F) -> Vec<U>
+    where
+        T: Sync,
+        U: Send,
+        F: Fn(&T) -> U + Send + Sync,
+    {
+        #[cfg(feature = "parallel")]
+        {
+            use rayon::prelude::*;
+
+            let run = || items.par_iter().map(&f).collect();
+            match &self.pool {
+                Some(pool) => pool.install(run),
+                None => run(),
+            }
+        }
+        #[cfg(not(feature = "parallel"))]
+        items.iter().map(f).collect()
+    }
+}
+
+/// Avalia a população com qualquer `ScalarObjective`, dividindo-a em blocos
+/// avaliados em paralelo no `pool`. Os scores saem na ordem da população;
+/// `None` se a avaliação de algum bloco falha.
+pub fn evaluate_population_parallel<E, O>(objective: &O, pop: &Population<E>, pool: &EvalPool) -> Option<Vec<f64>>
+where
+    E: Encoding + Send + Sync,
+    O: ScalarObjective<E> + Sync + ?Sized,
+{
+    let individuals = pop.get_individuals();
+    // alguns blocos por thread, para equilibrar indivíduos de custo desigual
+    let chunk_size = individuals.len().div_ceil(4 * pool.threads()).max(1);
+    let chunks: Vec<&[E]> = individuals.chunks(chunk_size).collect();
+    let scores: Option<Vec<Vec<f64>>> = pool
+        .map(&chunks, |chunk| objective.eval_scalar(&Population(chunk.to_vec())))
+        .into_iter()
+        .collect();
+    scores.map(|scores| scores.concat())
+}
+
+#[cfg(test)]
+mod parallel_tests {
+    use std::io::Cursor;
+    use rand::{SeedableRng, rngs::StdRng};
+    use crate::alg::objectives::{Formula, OneMaxObjective, SATObjective};
+    use crate::population::{BinaryEncoding, BinaryPopGenerator, PopGenerator};
+    use super::*;
+
+    fn formula() -> Formula {
+        let dimacs = "p cnf 6 5\n1 -3 0\n2 3 -5 0\n-1 4 6 0\n-4 5 -2 0\n3 -6 0\n";
+        Formula::parse_from_dimacs_cnf(Cursor::new(dimacs)).unwrap()
+    }
+
+    #[test]
+    fn test_parallel_helper_matches_serial() {
+        let population = BinaryPopGenerator::new(6, 301).gen_pop_with(&mut StdRng::seed_from_u64(8));
+        let objectives: Vec<Box<dyn ScalarObjective<BinaryEncoding> + Sync>> = vec![
+            Box::new(SATObjective::new(formula())),
+            Box::new(OneMaxObjective),
+        ];
+        for threads in [None, Some(1), Some(3)] {
+            let pool = EvalPool::new(threads).unwrap();
+            for objective in &objectives {
+                let serial = objective.eval_scalar(&population).unwrap();
+                assert_eq!(evaluate_population_parallel(objective.as_ref(), &population, &pool).unwrap(), serial);
+            }
+        }
+
+        let empty = Population(Vec::new());
+        assert_eq!(evaluate_population_parallel(&OneMaxObjective, &empty, &EvalPool::new(None).unwrap()), Some(vec![]));
+    }
+
+    #[test]
+    fn test_parallel_helper_propagates_failures() {
+        let mut population = BinaryPopGenerator::new(6, 40).gen_pop_with(&mut StdRng::seed_from_u64(2));
+        population.0[17] = BinaryEncoding::zeros(5);
+        let pool = EvalPool::new(Some(2)).unwrap();
+        assert_eq!(evaluate_population_parallel(&SATObjective::new(formula()), &population, &pool), None);
+    }
+
+    #[test]
+    fn test_zero_threads_is_rejected() {
+        assert!(matches!(EvalPool::new(Some(0)), Err(EvalPoolError::ZeroThreads)));
+    }
+}
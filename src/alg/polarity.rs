@@ -0,0 +1,147 @@
+use std::io::{self, Write};
+use crate::population::{BinaryEncoding, Population};
+
+/// Acompanha, geração a geração, a fração da população que atribui
+/// verdadeiro a cada variável. Com `keep_history` guarda a matriz
+/// gerações × variáveis; sem ele guarda só a última geração e a entropia
+/// média de cada variável, com memória proporcional ao número de variáveis.
+#[derive(Debug, Clone, Default)]
+pub struct PolarityObserver {
+    keep_history: bool,
+    history: Vec<Vec<f64>>,
+    last: Vec<f64>,
+    entropy_sum: Vec<f64>,
+    generations: usize,
+}
+
+/// Entropia binária (em bits) de uma variável verdadeira com frequência `p`.
+pub fn binary_entropy(p: f64) -> f64 {
+    [p, 1.0 - p].iter().filter(|&&q| q > 0.0).map(|q| -q * q.log2()).sum()
+}
+
+impl PolarityObserver {
+    pub fn new(keep_history: bool) -> Self {
+        PolarityObserver { keep_history, ..Self::default() }
+    }
+
+    /// Uma população vazia não tem frequências e é ignorada: não conta como
+    /// geração nem altera o que já foi registrado.
+    pub fn record(&mut self, pop: &Population<BinaryEncoding>) {
+        if pop.is_empty()
+        { return }
+
+        let size = pop.len() as f64;
+        let frequencies: Vec<f64> = pop.column_ones().into_iter().map(|ones| ones as f64 / size).collect();
+
+        self.entropy_sum.resize(frequencies.len(), 0.0);
+        self.entropy_sum.iter_mut().zip(&frequencies).for_each(|(sum, &p)| *sum += binary_entropy(p));
+        self.generations += 1;
+        if self.keep_history {
+            self.history.push(frequencies.clone());
+        }
+        self.last = frequencies;
+    }
+
+    pub fn generations(&self) -> usize {
+        self.generations
+    }
+
+    /// Frequências da última geração registrada.
+    pub fn frequencies(&self) -> &[f64] {
+        &self.last
+    }
+
+    /// Uma linha por geração; vazio sem `keep_history`.
+    pub fn history(&self) -> &[Vec<f64>] {
+        &self.history
+    }
+
+    /// Entropia de cada variável na última geração; 0 para variáveis
+    /// convergidas.
+    pub fn entropies(&self) -> Vec<f64> {
+        self.last.iter().map(|&p| binary_entropy(p)).collect()
+    }
+
+    /// Entropia de cada variável, média sobre as gerações registradas.
+    pub fn mean_entropies(&self) -> Vec<f64> {
+        self.entropy_sum.iter().map(|sum| sum / self.generations.max(1) as f64).collect()
+    }
+
+    /// Escreve o histórico em CSV: cabeçalho `generation,x1,...,xn` e uma
+    /// linha por geração.
+    pub fn write_csv<W: Write>(&self, mut writer: W) -> io::Result<()> {
+        write!(writer, "generation")?;
+        for var in 1..=self.last.len() {
+            write!(writer, ",x{}", var)?;
+        }
+        writeln!(writer)?;
+
+        for (generation, frequencies) in self.history.iter().enumerate() {
+            write!(writer, "{}", generation)?;
+            for p in frequencies {
+                write!(writer, ",{}", p)?;
+            }
+            writeln!(writer)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod polarity_tests {
+    use super::*;
+
+    fn population(bitstrings: &[&str]) -> Population<BinaryEncoding> {
+        Population(bitstrings.iter().map(|bits| BinaryEncoding::from_bitstring(bits).unwrap()).collect())
+    }
+
+    #[test]
+    fn test_polarity_frequencies() {
+        let mut observer = PolarityObserver::new(true);
+        observer.record(&population(&["110", "100", "101", "100"]));
+        assert_eq!(observer.frequencies(), &[1.0, 0.25, 0.25]);
+
+        let entropies = observer.entropies();
+        assert_eq!(entropies[0], 0.0);
+        assert!((entropies[1] - 0.811278124).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_polarity_mean_entropy_without_history() {
+        let mut observer = PolarityObserver::new(false);
+        observer.record(&population(&["10", "01"]));
+        observer.record(&population(&["11", "11"]));
+        assert_eq!(observer.generations(), 2);
+        assert!(observer.history().is_empty());
+        assert_eq!(observer.mean_entropies(), vec![0.5, 0.5]);
+        assert_eq!(observer.entropies(), vec![0.0, 0.0]);
+    }
+
+    #[test]
+    fn test_polarity_csv_dimensions() {
+        let mut observer = PolarityObserver::new(true);
+        for _ in 0..3 {
+            observer.record(&population(&["1010", "0110"]));
+        }
+        let mut output = Vec::new();
+        observer.write_csv(&mut output).unwrap();
+        let output = String::from_utf8(output).unwrap();
+        let rows: Vec<&str> = output.lines().collect();
+
+        assert_eq!(rows.len(), 1 + 3);
+        assert_eq!(rows[0], "generation,x1,x2,x3,x4");
+        assert!(rows[1..].iter().all(|row| row.split(',').count() == 1 + 4));
+        assert_eq!(rows[1], "0,0.5,0.5,1,0");
+    }
+
+    #[test]
+    fn test_polarity_ignores_empty_population() {
+        let mut observer = PolarityObserver::new(true);
+        observer.record(&population(&["10", "01"]));
+        observer.record(&population(&[]));
+        assert_eq!(observer.generations(), 1);
+        assert_eq!(observer.history().len(), 1);
+        assert_eq!(observer.frequencies(), &[0.5, 0.5]);
+        assert_eq!(observer.mean_entropies(), vec![1.0, 1.0]);
+    }
+}
@@ -0,0 +1,129 @@
+use crate::population::{BinaryEncoding, Genome};
+
+/// Polimento final do melhor indivíduo: busca local que percorre a vizinhança
+/// de um bit (e, com `pairs`, de dois bits) aceitando cada melhora assim que
+/// encontrada, até uma passada inteira sem melhora (ótimo local) ou até gastar
+/// `max_evals` avaliações. Scores maiores são melhores.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FinalPolish {
+    pub pairs: bool,
+    pub max_evals: u64,
+}
+
+/// Resultado do polimento, reportado à parte das estatísticas da execução.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PolishResult {
+    pub individual: BinaryEncoding,
+    pub score: f64,
+    pub evaluations: u64,
+    pub improvements: usize,
+    /// Se terminou num ótimo local (e não por falta de orçamento).
+    pub local_optimum: bool,
+}
+
+impl FinalPolish {
+    pub fn new(pairs: bool, max_evals: u64) -> Self {
+        FinalPolish { pairs, max_evals }
+    }
+
+    /// Polimento a partir de `start`, cujo score já conhecido é `start_score`
+    /// (não é reavaliado). `score` avalia um indivíduo.
+    pub fn polish<F>(&self, start: &BinaryEncoding, start_score: f64, mut score: F) -> PolishResult
+    where
+        F: FnMut(&BinaryEncoding) -> f64
+    {
+        let mut result = PolishResult {
+            individual: start.clone(),
+            score: start_score,
+            evaluations: 0,
+            improvements: 0,
+            local_optimum: false,
+        };
+        let len = start.len();
+
+        loop {
+            let single = (0..len).map(|i| vec![i]);
+            let pairs = self.pairs
+                .then(|| (0..len).flat_map(|i| (i + 1..len).map(move |j| vec![i, j])))
+                .into_iter()
+                .flatten();
+
+            let mut improved = false;
+            for bits in single.chain(pairs) {
+                if result.evaluations >= self.max_evals
+                { return result }
+
+                bits.iter().for_each(|&bit| result.individual.flip(bit));
+                let candidate = score(&result.individual);
+                result.evaluations += 1;
+                if candidate > result.score {
+                    result.score = candidate;
+                    result.improvements += 1;
+                    improved = true;
+                } else {
+                    bits.iter().for_each(|&bit| result.individual.flip(bit));
+                }
+            }
+
+            if !improved {
+                result.local_optimum = true;
+                return result
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod polish_tests {
+    use super::*;
+
+    fn one_max(individual: &BinaryEncoding) -> f64 {
+        individual.count_ones() as f64
+    }
+
+    #[test]
+    fn test_polish_reaches_one_max_optimum() {
+        let start = BinaryEncoding::from_bitstring("1110111011").unwrap();
+        let result = FinalPolish::new(false, 1000).polish(&start, one_max(&start), one_max);
+        assert_eq!(result.individual, BinaryEncoding::from_bitstring("1111111111").unwrap());
+        assert_eq!(result.score, 10.0);
+        assert_eq!(result.improvements, 2);
+        assert!(result.local_optimum);
+        // o ponto de partida não é alterado
+        assert_eq!(start.count_ones(), 8);
+    }
+
+    #[test]
+    fn test_polish_respects_budget() {
+        let start = BinaryEncoding::zeros(50);
+        let mut calls = 0;
+        let result = FinalPolish::new(true, 7).polish(&start, 0.0, |individual| {
+            calls += 1;
+            one_max(individual)
+        });
+        assert_eq!(calls, 7);
+        assert_eq!(result.evaluations, 7);
+        assert!(!result.local_optimum);
+        assert_eq!(result.score, 7.0);
+    }
+
+    #[test]
+    fn test_pairs_escape_single_flip_optimum() {
+        // só melhora quando os dois primeiros bits são ligados juntos
+        let score = |individual: &BinaryEncoding| match (individual.get(0), individual.get(1)) {
+            (true, true) => 2.0,
+            (false, false) => 1.0,
+            _ => 0.0,
+        };
+        let start = BinaryEncoding::zeros(4);
+
+        let single = FinalPolish::new(false, 100).polish(&start, 1.0, score);
+        assert!(single.local_optimum);
+        assert_eq!(single.improvements, 0);
+        assert_eq!(single.evaluations, 4);
+
+        let pairs = FinalPolish::new(true, 100).polish(&start, 1.0, score);
+        assert_eq!(pairs.score, 2.0);
+        assert!(pairs.individual.get(0) && pairs.individual.get(1));
+    }
+}
@@ -0,0 +1,132 @@
+use std::fmt;
+use rand::Rng;
+use crate::population::{BinaryEncoding, Encoding, Genome, PopGenerator, Population};
+
+/// Estratégia de reinicialização da população num restart. Qualquer
+/// `PopGenerator` serve, descartando o melhor indivíduo: ele gera
+/// populações com o `rng` dado até somar `pop_size` indivíduos e o excedente
+/// é descartado.
+pub trait Reinitialization<E: Encoding> {
+    fn reinitialize<R: Rng + ?Sized>(&self, best: &E, pop_size: usize, rng: &mut R) -> Population<E>;
+}
+
+impl<P: PopGenerator> Reinitialization<P::E> for P {
+    fn reinitialize<R: Rng + ?Sized>(&self, _best: &P::E, pop_size: usize, rng: &mut R) -> Population<P::E> {
+        let mut individuals = Vec::with_capacity(pop_size);
+        while individuals.len() < pop_size {
+            let batch = self.gen_pop_with(rng).0;
+            assert!(!batch.is_empty(), "generator produced an empty population");
+            individuals.extend(batch);
+        }
+        individuals.truncate(pop_size);
+        Population(individuals)
+    }
+}
+
+/// Restart com perturbação do elite: mantém uma cópia exata do melhor e
+/// preenche o resto com cópias dele em que cada bit é invertido com
+/// probabilidade `rate`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PerturbedRestart {
+    rate: f64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum RestartParameterError {
+    /// `rate` fora de `[0, 1]` (ou NaN).
+    RateOutOfRange { rate: f64 },
+}
+
+impl fmt::Display for RestartParameterError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RestartParameterError::RateOutOfRange { rate } => write!(f, "perturbation rate {} must be within [0, 1]", rate),
+        }
+    }
+}
+
+impl std::error::Error for RestartParameterError {}
+
+impl PerturbedRestart {
+    pub fn new(rate: f64) -> Result<Self, RestartParameterError> {
+        if !(0.0..=1.0).contains(&rate)
+        { return Err(RestartParameterError::RateOutOfRange { rate }) }
+
+        Ok(PerturbedRestart { rate })
+    }
+
+    pub fn rate(&self) -> f64 {
+        self.rate
+    }
+}
+
+impl Reinitialization<BinaryEncoding> for PerturbedRestart {
+    fn reinitialize<R: Rng + ?Sized>(&self, best: &BinaryEncoding, pop_size: usize, rng: &mut R) -> Population<BinaryEncoding> {
+        let perturbed = (1..pop_size).map(|_| {
+            let mut copy = best.clone();
+            (0..copy.len()).filter(|_| rng.gen_bool(self.rate)).for_each(|bit| copy.flip(bit));
+            copy
+        });
+
+        Population(std::iter::once(best.clone()).take(pop_size).chain(perturbed).collect())
+    }
+}
+
+#[cfg(test)]
+mod restart_tests {
+    use rand::{SeedableRng, rngs::StdRng};
+    use crate::population::BinaryPopGenerator;
+    use super::*;
+
+    #[test]
+    fn test_perturbed_restart_keeps_one_elite() {
+        let mut rng = StdRng::seed_from_u64(11);
+        let dim = 100;
+        let best = BinaryPopGenerator::new(dim, 1).gen_pop().0.remove(0);
+        let pop = PerturbedRestart::new(0.2).unwrap().reinitialize(&best, 50, &mut rng);
+
+        assert_eq!(pop.len(), 50);
+        assert_eq!(pop.get_individuals().iter().filter(|ind| **ind == best).count(), 1);
+        assert_eq!(pop.get_individuals()[0], best);
+
+        // Binomial(100, 0.2): média 20, desvio padrão 4
+        let distances: Vec<usize> = pop.get_individuals()[1..].iter().map(|ind| ind.hamming_distance(&best)).collect();
+        assert!(distances.iter().all(|&d| (4..=36).contains(&d)));
+        let mean = distances.iter().sum::<usize>() as f64 / distances.len() as f64;
+        assert!((mean - 20.0).abs() < 2.0);
+    }
+
+    #[test]
+    fn test_perturbed_restart_population_size() {
+        let mut rng = StdRng::seed_from_u64(1);
+        let best = BinaryEncoding::zeros(8);
+        for pop_size in [0, 1, 7] {
+            assert_eq!(PerturbedRestart::new(0.5).unwrap().reinitialize(&best, pop_size, &mut rng).len(), pop_size);
+        }
+    }
+
+    #[test]
+    fn test_perturbed_restart_rejects_invalid_rate() {
+        assert_eq!(PerturbedRestart::new(1.5), Err(RestartParameterError::RateOutOfRange { rate: 1.5 }));
+        assert_eq!(PerturbedRestart::new(-0.1), Err(RestartParameterError::RateOutOfRange { rate: -0.1 }));
+        assert!(PerturbedRestart::new(f64::NAN).is_err());
+        assert_eq!(PerturbedRestart::new(0.0).unwrap().rate(), 0.0);
+    }
+
+    #[test]
+    fn test_generator_as_reinitialization() {
+        let generator = BinaryPopGenerator::new(8, 12);
+        let best = BinaryEncoding::zeros(8);
+        for pop_size in [0, 5, 12, 30] {
+            let pop = generator.reinitialize(&best, pop_size, &mut StdRng::seed_from_u64(1));
+            assert_eq!(pop.len(), pop_size);
+            assert!(pop.get_individuals().iter().all(|ind| ind.len() == 8));
+        }
+
+        // o rng do chamador determina a população
+        let first = generator.reinitialize(&best, 30, &mut StdRng::seed_from_u64(2));
+        let second = generator.reinitialize(&best, 30, &mut StdRng::seed_from_u64(2));
+        assert_eq!(first.0, second.0);
+        assert_eq!(first.0[..12], generator.gen_pop_with(&mut StdRng::seed_from_u64(2)).0[..]);
+    }
+}
@@ -0,0 +1,108 @@
+use std::sync::Arc;
+use rand::Rng;
+use rand::seq::SliceRandom;
+use crate::alg::objectives::{Formula, Literal};
+use crate::population::{BinaryEncoding, PopGenerator, Population};
+
+/// Inicialização construtiva para SAT: percorre as cláusulas em ordem
+/// aleatória e, para cada uma ainda não satisfeita pela atribuição parcial,
+/// fixa um de seus literais livres (sorteado) de modo a satisfazê-la. As
+/// variáveis que sobram são sorteadas no fim.
+pub struct ClauseCoveringPopGenerator {
+    formula: Arc<Formula>,
+    pop_size: usize,
+}
+
+impl ClauseCoveringPopGenerator {
+    pub fn new(formula: Arc<Formula>, pop_size: usize) -> Self {
+        ClauseCoveringPopGenerator { formula, pop_size }
+    }
+
+    pub fn construct<R: Rng + ?Sized>(&self, rng: &mut R) -> BinaryEncoding {
+        let num_vars = self.formula.get_num_vars() as usize;
+        let mut assignment: Vec<Option<bool>> = vec![None; num_vars];
+        let mut order: Vec<usize> = (0..self.formula.get_clauses().len()).collect();
+        order.shuffle(rng);
+
+        // (índice da variável, valor que satisfaz o literal)
+        let satisfying = |literal: &Literal| match *literal {
+            Literal::Var(var) => (var as usize - 1, true),
+            Literal::NegatedVar(var) => (var as usize - 1, false),
+        };
+
+        for clause in order.into_iter().map(|index| &self.formula.get_clauses()[index]) {
+            let literals: Vec<(usize, bool)> = clause.literals().iter().map(satisfying).collect();
+            if literals.iter().any(|&(var, value)| assignment[var] == Some(value))
+            { continue }
+
+            let free: Vec<&(usize, bool)> = literals.iter().filter(|(var, _)| assignment[*var].is_none()).collect();
+            if let Some(&&(var, value)) = free.choose(rng)
+            { assignment[var] = Some(value) }
+        }
+
+        let bits: Vec<bool> = assignment.into_iter().map(|value| value.unwrap_or_else(|| rng.r#gen())).collect();
+        BinaryEncoding::from_bools(&bits)
+    }
+}
+
+impl PopGenerator for ClauseCoveringPopGenerator {
+    type E = BinaryEncoding;
+
+    fn dimension(&self) -> usize {
+        self.formula.get_num_vars() as usize
+    }
+
+    fn gen_pop_with<R: Rng + ?Sized>(&self, rng: &mut R) -> Population<BinaryEncoding> {
+        Population((0..self.pop_size).map(|_| self.construct(rng)).collect())
+    }
+}
+
+#[cfg(test)]
+mod sat_init_tests {
+    use std::io::Cursor;
+    use rand::{SeedableRng, rngs::StdRng};
+    use crate::alg::Objective;
+    use crate::alg::objectives::SATObjective;
+    use crate::population::{BinaryPopGenerator, Genome};
+    use super::*;
+
+    fn formula(dimacs_cnf: &str) -> Arc<Formula> {
+        Arc::new(Formula::parse_from_dimacs_cnf(Cursor::new(dimacs_cnf)).unwrap())
+    }
+
+    #[test]
+    fn test_clauses_with_free_literals_are_satisfied() {
+        // cada cláusula tem uma variável só sua (3, 4, 5), então sempre há
+        // um literal livre quando ela é visitada
+        let formula = formula("p cnf 5 3\n1 -2 3 0\n-1 2 -4 0\n1 2 5 0\n");
+        let generator = ClauseCoveringPopGenerator::new(formula.clone(), 50);
+        assert_eq!(generator.dimension(), 5);
+
+        let pop = generator.gen_pop();
+        assert!(pop.get_individuals().iter().all(|ind| ind.len() == 5));
+        assert!(SATObjective::shared(formula).eval(&pop).unwrap().iter().all(|&num_false| num_false == 0));
+    }
+
+    #[test]
+    fn test_reproducible_under_seed() {
+        let formula = Arc::new(Formula::random_3sat(30, 120, &mut StdRng::seed_from_u64(1)));
+        let generator = ClauseCoveringPopGenerator::new(formula, 10);
+        assert_eq!(
+            generator.gen_pop_with(&mut StdRng::seed_from_u64(5)),
+            generator.gen_pop_with(&mut StdRng::seed_from_u64(5))
+        );
+    }
+
+    #[test]
+    fn test_fewer_false_clauses_than_uniform() {
+        let mut rng = StdRng::seed_from_u64(2);
+        let formula = Arc::new(Formula::random_3sat(100, 430, &mut rng));
+        let objective = SATObjective::shared(formula.clone());
+        let mean = |scores: Vec<usize>| scores.iter().sum::<usize>() as f64 / scores.len() as f64;
+
+        let covering = mean(objective.eval(&ClauseCoveringPopGenerator::new(formula, 50).gen_pop_with(&mut rng)).unwrap());
+        let uniform = mean(objective.eval(&BinaryPopGenerator::new(100, 50).gen_pop()).unwrap());
+        // uniforme: cada cláusula é falsa com probabilidade 1/8 (~54 de 430)
+        assert!(covering < 0.75 * uniform, "covering {} vs uniform {}", covering, uniform);
+    }
+}
@@ -0,0 +1,193 @@
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::path::Path;
+use crate::alg::objectives::{Formula, Literal, Valoration};
+
+/// Resultado da verificação de uma atribuição contra uma fórmula. Os
+/// índices são de `Formula::get_clauses`: depois de `collapse_duplicates`,
+/// cada cláusula distinta aparece uma vez e suas ocorrências são dadas por
+/// `Formula::multiplicity`.
+#[derive(Debug, Clone, PartialEq)]
+pub enum SatVerification {
+    /// Para cada cláusula (na ordem da fórmula), o literal que a satisfaz.
+    Satisfied { witnesses: Vec<Literal> },
+    /// Índices (a partir de 0) das cláusulas violadas.
+    Partial { violated: Vec<usize> },
+}
+
+/// Artefatos de uma atribuição (tipicamente o melhor indivíduo de uma
+/// execução): o modelo no formato de solução das competições SAT e a
+/// verificação cláusula a cláusula.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SatRunReport {
+    pub assignment: Vec<bool>,
+    pub verification: SatVerification,
+}
+
+impl SatRunReport {
+    /// Retorna `None` se a atribuição não tem uma variável por variável da
+    /// fórmula.
+    pub fn new<V: Valoration + ?Sized>(assignment: &V, formula: &Formula) -> Option<Self> {
+        if assignment.num_vars() != formula.get_num_vars() as usize
+        { return None }
+
+        let mut witnesses = Vec::with_capacity(formula.get_clauses().len());
+        let mut violated = Vec::new();
+        for (index, clause) in formula.get_clauses().iter().enumerate() {
+            let mut witness = None;
+            for literal in clause.literals() {
+                if literal.is_satisfied_by(assignment)? {
+                    witness = Some(*literal);
+                    break;
+                }
+            }
+            match witness {
+                Some(literal) => witnesses.push(literal),
+                None => violated.push(index),
+            }
+        }
+
+        Some(SatRunReport {
+            assignment: (0..assignment.num_vars()).map(|var| assignment.value(var)).collect::<Option<_>>()?,
+            verification: if violated.is_empty() {
+                SatVerification::Satisfied { witnesses }
+            } else {
+                SatVerification::Partial { violated }
+            },
+        })
+    }
+
+    pub fn is_satisfied(&self) -> bool {
+        matches!(self.verification, SatVerification::Satisfied { .. })
+    }
+
+    /// Formato de solução: `s SATISFIABLE` (ou `s UNKNOWN` para uma
+    /// atribuição parcial) seguido das linhas `v` terminadas em `0`.
+    pub fn write_model_to<W: Write>(&self, mut writer: W) -> io::Result<()> {
+        writeln!(writer, "s {}", if self.is_satisfied() { "SATISFIABLE" } else { "UNKNOWN" })?;
+        let literals: Vec<i64> = self.assignment
+            .iter()
+            .enumerate()
+            .map(|(var, &value)| if value { var as i64 + 1 } else { -(var as i64 + 1) })
+            .collect();
+        for line in literals.chunks(10) {
+            write!(writer, "v")?;
+            for literal in line {
+                write!(writer, " {}", literal)?;
+            }
+            writeln!(writer)?;
+        }
+        writeln!(writer, "v 0")
+    }
+
+    /// CSV `clause,literal`: o literal DIMACS que satisfaz cada cláusula, ou
+    /// `clause,violated` com as cláusulas violadas de um relatório parcial.
+    pub fn write_witness_csv_to<W: Write>(&self, mut writer: W) -> io::Result<()> {
+        match &self.verification {
+            SatVerification::Satisfied { witnesses } => {
+                writeln!(writer, "clause,literal")?;
+                for (clause, literal) in witnesses.iter().enumerate() {
+                    writeln!(writer, "{},{}", clause, literal.to_dimacs())?;
+                }
+            },
+            SatVerification::Partial { violated } => {
+                writeln!(writer, "clause,violated")?;
+                for clause in violated {
+                    writeln!(writer, "{},true", clause)?;
+                }
+            },
+        }
+        Ok(())
+    }
+
+    pub fn write_model(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        let mut writer = BufWriter::new(File::create(path)?);
+        self.write_model_to(&mut writer)?;
+        writer.flush()
+    }
+
+    pub fn write_witness_csv(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        let mut writer = BufWriter::new(File::create(path)?);
+        self.write_witness_csv_to(&mut writer)?;
+        writer.flush()
+    }
+}
+
+#[cfg(test)]
+mod sat_report_tests {
+    use std::io::Cursor;
+    use crate::population::BinaryEncoding;
+    use super::*;
+
+    fn formula() -> Formula {
+        let dimacs_cnf =
+            r#"p cnf 3 3
+            1 -3 0
+            2 3 0
+            1 2 0
+            %"#;
+        Formula::parse_from_dimacs_cnf(Cursor::new(dimacs_cnf)).unwrap()
+    }
+
+    #[test]
+    fn test_report_witnesses() {
+        let report = SatRunReport::new(&[false, true, false], &formula()).unwrap();
+        assert_eq!(report.verification, SatVerification::Satisfied {
+            witnesses: vec![Literal::NegatedVar(3), Literal::Var(2), Literal::Var(2)],
+        });
+    }
+
+    #[test]
+    fn test_report_partial() {
+        let assignment = BinaryEncoding::from_bools(&[false, false, true]);
+        let report = SatRunReport::new(&assignment, &formula()).unwrap();
+        assert_eq!(report.verification, SatVerification::Partial { violated: vec![0, 2] });
+        assert!(SatRunReport::new(&[true, true], &formula()).is_none());
+
+        let mut output = Vec::new();
+        report.write_witness_csv_to(&mut output).unwrap();
+        assert_eq!(String::from_utf8(output).unwrap(), "clause,violated\n0,true\n2,true\n");
+    }
+
+    #[test]
+    fn test_report_files_parse_back() {
+        let dir = tempfile::tempdir().unwrap();
+        let report = SatRunReport::new(&[false, true, false], &formula()).unwrap();
+        report.write_model(dir.path().join("model.sol")).unwrap();
+        report.write_witness_csv(dir.path().join("witness.csv")).unwrap();
+
+        let model = std::fs::read_to_string(dir.path().join("model.sol")).unwrap();
+        assert_eq!(model.lines().next(), Some("s SATISFIABLE"));
+        let literals: Vec<i64> = model
+            .lines()
+            .filter_map(|line| line.strip_prefix("v"))
+            .flat_map(|values| values.split_whitespace().map(|value| value.parse::<i64>().unwrap()))
+            .collect();
+        assert_eq!(literals, vec![-1, 2, -3, 0]);
+        let parsed: Vec<bool> = literals.iter().filter(|&&literal| literal != 0).map(|&literal| literal > 0).collect();
+        assert_eq!(parsed, report.assignment);
+
+        let witness = std::fs::read_to_string(dir.path().join("witness.csv")).unwrap();
+        assert_eq!(witness, "clause,literal\n0,-3\n1,2\n2,2\n");
+    }
+
+    #[test]
+    fn test_report_indices_after_collapse() {
+        let dimacs_cnf =
+            r#"p cnf 3 4
+            1 -3 0
+            2 3 0
+            -3 1 0
+            1 2 0
+            %"#;
+        let mut formula = Formula::parse_from_dimacs_cnf(Cursor::new(dimacs_cnf)).unwrap();
+        formula.collapse_duplicates();
+
+        // `1 -3` e `-3 1` viram a cláusula 0, com duas ocorrências
+        let report = SatRunReport::new(&[false, false, true], &formula).unwrap();
+        assert_eq!(report.verification, SatVerification::Partial { violated: vec![0, 2] });
+        let occurrences: u64 = [0, 2].iter().map(|&index| formula.multiplicity(index)).sum();
+        assert_eq!(occurrences, 3);
+        assert_eq!(occurrences as usize, formula.evaluate(&[false, false, true]).unwrap().num_false);
+    }
+}
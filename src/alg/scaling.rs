@@ -0,0 +1,133 @@
+use std::cmp::Ordering;
+use super::{Fitness, Objective, RawScores};
+use crate::population::Encoding;
+
+/// Transformação de uma etapa de [`FitnessPipeline`]. Todas assumem scores
+/// de maximização.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Scaling {
+    /// Inverte o sinal, transformando minimização em maximização.
+    Negate,
+    /// Truncamento sigma de Goldberg: `max(0, f - (média - c·desvio))`.
+    SigmaTruncation { c: f64 },
+    /// Substitui cada score pelo seu posto (1 para o pior); empates recebem o
+    /// posto médio.
+    Rank,
+}
+
+impl Scaling {
+    pub fn apply(&self, scores: &[f64]) -> Vec<f64> {
+        match self {
+            Scaling::Negate => scores.iter().map(|score| -score).collect(),
+            Scaling::SigmaTruncation { c } => {
+                if scores.is_empty()
+                { return Vec::new() }
+                let n = scores.len() as f64;
+                let mean = scores.iter().sum::<f64>() / n;
+                let deviation = (scores.iter().map(|score| (score - mean).powi(2)).sum::<f64>() / n).sqrt();
+                let floor = mean - c * deviation;
+                scores.iter().map(|score| (score - floor).max(0.0)).collect()
+            },
+            Scaling::Rank => average_ranks(scores),
+        }
+    }
+}
+
+/// Postos de 1 a n na ordem de `total_cmp`, com o posto médio nos empates.
+/// NaN forma o seu próprio grupo (acima de `+∞`) em vez de travar o
+/// agrupamento, já que `NaN == NaN` é falso.
+pub fn average_ranks(scores: &[f64]) -> Vec<f64> {
+    let mut order: Vec<usize> = (0..scores.len()).collect();
+    order.sort_by(|&i, &j| scores[i].total_cmp(&scores[j]));
+    let mut ranks = vec![0.0; scores.len()];
+    let mut start = 0;
+    while start < order.len() {
+        let first = scores[order[start]];
+        let end = start + order[start..].iter().take_while(|&&i| scores[i].total_cmp(&first) == Ordering::Equal).count();
+        let rank = (start + 1 + end) as f64 / 2.0;
+        for &i in &order[start..end] {
+            ranks[i] = rank;
+        }
+        start = end;
+    }
+    ranks
+}
+
+/// Sequência de transformações entre a saída bruta do objetivo e a seleção,
+/// aplicadas na ordem em que foram dadas.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct FitnessPipeline {
+    pub steps: Vec<Scaling>,
+}
+
+impl FitnessPipeline {
+    pub fn new(steps: Vec<Scaling>) -> Self {
+        FitnessPipeline { steps }
+    }
+
+    pub fn apply(&self, scores: &[f64]) -> Vec<f64> {
+        self.steps.iter().fold(scores.to_vec(), |scores, step| step.apply(&scores))
+    }
+}
+
+impl FromIterator<Scaling> for FitnessPipeline {
+    fn from_iter<I: IntoIterator<Item = Scaling>>(iter: I) -> Self {
+        FitnessPipeline::new(iter.into_iter().collect())
+    }
+}
+
+impl<E, O> Fitness<E, O> for FitnessPipeline
+where
+    E: Encoding,
+    O: Objective<E>,
+    O::Output: RawScores,
+{
+    type Score = f64;
+
+    fn eval(&self, raw: &O::Output) -> Option<Vec<f64>> {
+        raw.scores().map(|scores| self.apply(&scores))
+    }
+}
+
+#[cfg(test)]
+mod scaling_tests {
+    use super::*;
+
+    #[test]
+    fn test_rank_averages_ties() {
+        assert_eq!(Scaling::Rank.apply(&[3.0, 1.0, 3.0, 2.0]), vec![3.5, 1.0, 3.5, 2.0]);
+    }
+
+    #[test]
+    fn test_rank_with_nan_terminates() {
+        assert_eq!(Scaling::Rank.apply(&[1.0, f64::NAN, 2.0]), vec![1.0, 3.0, 2.0]);
+        assert_eq!(Scaling::Rank.apply(&[f64::NAN, 1.0, f64::NAN]), vec![2.5, 1.0, 2.5]);
+        assert_eq!(Scaling::Rank.apply(&[f64::NAN]), vec![1.0]);
+    }
+
+    #[test]
+    fn test_sigma_truncation() {
+        // média 5, desvio 2: piso em 5 - 1·2 = 3
+        assert_eq!(Scaling::SigmaTruncation { c: 1.0 }.apply(&[3.0, 7.0, 3.0, 7.0]), vec![0.0, 4.0, 0.0, 4.0]);
+        assert_eq!(Scaling::SigmaTruncation { c: 2.0 }.apply(&[3.0, 7.0, 3.0, 7.0]), vec![2.0, 6.0, 2.0, 6.0]);
+        assert!(Scaling::SigmaTruncation { c: 2.0 }.apply(&[]).is_empty());
+    }
+
+    #[test]
+    fn test_pipeline_matches_manual_composition() {
+        let scores = [4.0, -1.0, 10.0, 2.0, 2.0];
+        let pipeline = FitnessPipeline::new(vec![Scaling::Negate, Scaling::SigmaTruncation { c: 1.5 }, Scaling::Rank]);
+        let manual = Scaling::Rank.apply(&Scaling::SigmaTruncation { c: 1.5 }.apply(&Scaling::Negate.apply(&scores)));
+        assert_eq!(pipeline.apply(&scores), manual);
+        assert_eq!(FitnessPipeline::default().apply(&scores), scores);
+    }
+
+    #[test]
+    fn test_pipeline_order_matters() {
+        let scores = [1.0, 2.0, 3.0];
+        let negate_then_rank = FitnessPipeline::new(vec![Scaling::Negate, Scaling::Rank]);
+        let rank_then_negate = FitnessPipeline::new(vec![Scaling::Rank, Scaling::Negate]);
+        assert_eq!(negate_then_rank.apply(&scores), vec![3.0, 2.0, 1.0]);
+        assert_eq!(rank_then_negate.apply(&scores), vec![-1.0, -2.0, -3.0]);
+    }
+}
@@ -0,0 +1,149 @@
+use std::fmt;
+
+/// O que fazer com scores NaN ou infinitos logo após a avaliação.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum NonFinitePolicy {
+    /// Interrompe com erro no primeiro score não finito.
+    Fail,
+    /// Substitui o score pelo valor dado (o pior caso do problema) e registra
+    /// o incidente. Os scores substituídos entram nas estatísticas como
+    /// qualquer outro.
+    Penalize(f64),
+}
+
+/// Score não finito do indivíduo `index`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct NonFiniteScore {
+    pub index: usize,
+    pub value: f64,
+}
+
+impl fmt::Display for NonFiniteScore {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "individual {} has non-finite score {}", self.index, self.value)
+    }
+}
+
+impl std::error::Error for NonFiniteScore {}
+
+impl NonFinitePolicy {
+    /// Aplica a política a `scores`. Com `Penalize`, devolve os incidentes
+    /// (índice e valor original) na ordem dos indivíduos; com `Fail`, não
+    /// altera nada e devolve o primeiro score não finito como erro.
+    pub fn apply(&self, scores: &mut [f64]) -> Result<Vec<NonFiniteScore>, NonFiniteScore> {
+        let incidents: Vec<NonFiniteScore> = scores
+            .iter()
+            .enumerate()
+            .filter(|(_, score)| !score.is_finite())
+            .map(|(index, &value)| NonFiniteScore { index, value })
+            .collect();
+
+        match *self {
+            NonFinitePolicy::Fail => match incidents.first() {
+                Some(&incident) => Err(incident),
+                None => Ok(incidents),
+            },
+            NonFinitePolicy::Penalize(penalty) => {
+                incidents.iter().for_each(|incident| scores[incident.index] = penalty);
+                Ok(incidents)
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod score_guard_tests {
+    use rand::{SeedableRng, rngs::StdRng};
+    use crate::alg::Objective;
+    use crate::alg::ranking::StochasticRanking;
+    use crate::alg::scaling::Scaling;
+    use crate::stats::{ComparisonResult, ScoreDistribution, compare_runs};
+    use crate::population::{BinaryEncoding, Population};
+    use super::*;
+
+    /// Objetivo com defeito: NaN para o indivíduo todo zeros.
+    struct BuggyObjective;
+
+    impl Objective<BinaryEncoding> for BuggyObjective {
+        type Output = Vec<f64>;
+
+        fn eval(&self, pop: &Population<BinaryEncoding>) -> Vec<f64> {
+            pop.get_individuals()
+                .iter()
+                .map(|ind| match ind.count_ones() {
+                    0 => f64::NAN,
+                    ones => ones as f64,
+                })
+                .collect()
+        }
+    }
+
+    fn population() -> Population<BinaryEncoding> {
+        Population(vec![
+            BinaryEncoding::from_bitstring("101").unwrap(),
+            BinaryEncoding::zeros(3),
+            BinaryEncoding::from_bitstring("111").unwrap(),
+        ])
+    }
+
+    #[test]
+    fn test_fail_policy_names_individual() {
+        let mut scores = BuggyObjective.eval(&population());
+        let err = NonFinitePolicy::Fail.apply(&mut scores).unwrap_err();
+        assert_eq!(err.index, 1);
+        assert!(err.value.is_nan());
+        assert_eq!(err.to_string(), "individual 1 has non-finite score NaN");
+    }
+
+    #[test]
+    fn test_penalize_policy_records_incidents() {
+        let mut scores = BuggyObjective.eval(&population());
+        let incidents = NonFinitePolicy::Penalize(-1.0).apply(&mut scores).unwrap();
+        assert_eq!(scores, vec![2.0, -1.0, 3.0]);
+        assert_eq!(incidents.len(), 1);
+        assert_eq!(incidents[0].index, 1);
+
+        // o score penalizado entra na média como qualquer outro
+        let mean = scores.iter().sum::<f64>() / scores.len() as f64;
+        assert_eq!(mean, 4.0 / 3.0);
+    }
+
+    #[test]
+    fn test_finite_scores_pass_unchanged() {
+        let mut scores = vec![1.0, f64::MAX, -0.0];
+        assert_eq!(NonFinitePolicy::Fail.apply(&mut scores), Ok(vec![]));
+        assert_eq!(NonFinitePolicy::Penalize(0.0).apply(&mut scores), Ok(vec![]));
+        assert_eq!(scores, vec![1.0, f64::MAX, -0.0]);
+
+        let mut scores = vec![f64::INFINITY, f64::NEG_INFINITY];
+        let incidents = NonFinitePolicy::Penalize(0.0).apply(&mut scores).unwrap();
+        assert_eq!(incidents.iter().map(|incident| incident.index).collect::<Vec<_>>(), vec![0, 1]);
+    }
+
+    #[test]
+    fn test_nan_scores_reach_ranking_and_summaries_unguarded() {
+        // sem a política, NaN é ordenado por total_cmp como maior que +∞
+        let scores = BuggyObjective.eval(&population());
+        assert_eq!(Scaling::Rank.apply(&scores), vec![1.0, 3.0, 2.0]);
+
+        let mut rng = StdRng::seed_from_u64(0);
+        assert_eq!(StochasticRanking::new(0.45, 10).unwrap().order(&scores, &[0.0; 3], &mut rng), vec![1, 2, 0]);
+
+        let distribution = ScoreDistribution::new(&[1.0, f64::NAN, 2.0, 3.0], None).unwrap();
+        assert!(distribution.mean.is_nan());
+        assert_eq!(distribution.quantiles[..2], [1.75, 2.5]);
+
+        let comparison = compare_runs(&[1.0, f64::NAN, 2.0, 3.0], &[4.0, 5.0, 6.0, f64::NAN]);
+        assert!(matches!(comparison, ComparisonResult::Compared(result) if result.u == 3.5));
+    }
+
+    #[test]
+    fn test_penalized_nan_ranks_as_worst() {
+        let mut scores = BuggyObjective.eval(&population());
+        NonFinitePolicy::Penalize(f64::MIN).apply(&mut scores).unwrap();
+        assert_eq!(Scaling::Rank.apply(&scores), vec![2.0, 1.0, 3.0]);
+
+        let mut rng = StdRng::seed_from_u64(0);
+        assert_eq!(StochasticRanking::new(0.45, 10).unwrap().order(&scores, &[0.0; 3], &mut rng), vec![2, 0, 1]);
+    }
+}
@@ -0,0 +1,194 @@
+use rand::SeedableRng;
+use rand::rngs::StdRng;
+
+/// Papel de um fluxo de aleatoriedade dentro de uma geração.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum StreamRole {
+    Initialization,
+    Selection,
+    Crossover,
+    Mutation,
+    Evaluation,
+    Restart,
+}
+
+impl StreamRole {
+    fn id(self) -> u64 {
+        self as u64 + 1
+    }
+}
+
+/// Fluxos de números aleatórios determinísticos derivados de uma semente
+/// mestra. Cada tupla `(execução, geração, papel[, índice])` semeia um
+/// `StdRng` próprio a partir de um hash da tupla (SplitMix64 encadeado), sem
+/// estado compartilhado: a sequência de um fluxo não depende de quantos
+/// outros foram usados antes, nem de qual thread o consome. Avaliar ou variar
+/// indivíduos em paralelo com `individual_stream` produz, portanto, o mesmo
+/// resultado que a execução serial, com qualquer número de threads.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RngStreams {
+    seed: u64,
+}
+
+const POPULATION_STREAM: u64 = 1;
+const INDIVIDUAL_STREAM: u64 = 2;
+
+fn split_mix(mut z: u64) -> u64 {
+    z = z.wrapping_add(0x9E37_79B9_7F4A_7C15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^ (z >> 31)
+}
+
+impl RngStreams {
+    pub fn new(seed: u64) -> Self {
+        RngStreams { seed }
+    }
+
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    /// Semente do fluxo: cada componente da tupla é misturado ao hash do
+    /// anterior, então tuplas diferentes dão sementes independentes. Os
+    /// fluxos de população (`index == None`) e de indivíduo recebem marcas
+    /// distintas, e o de indivíduo tem um componente a mais, para que nenhum
+    /// índice coincida com o fluxo da população.
+    pub fn stream_seed(&self, run: u64, generation: u64, role: StreamRole, index: Option<u64>) -> u64 {
+        let tuple = [run, generation, role.id()];
+        let components = match index {
+            None => [&tuple[..], &[POPULATION_STREAM]].concat(),
+            Some(index) => [&tuple[..], &[INDIVIDUAL_STREAM, index]].concat(),
+        };
+        components
+            .into_iter()
+            .fold(split_mix(self.seed), |hash, component| split_mix(hash ^ split_mix(component)))
+    }
+
+    /// Fluxo de um papel na geração, para operadores que consomem a
+    /// população inteira de forma serial (ex. seleção, ou
+    /// `PopGenerator::gen_pop_with` com `StreamRole::Initialization`).
+    pub fn stream(&self, run: u64, generation: u64, role: StreamRole) -> StdRng {
+        StdRng::seed_from_u64(self.stream_seed(run, generation, role, None))
+    }
+
+    /// Fluxo de um indivíduo (ou par de pais), para trabalho paralelo.
+    pub fn individual_stream(&self, run: u64, generation: u64, role: StreamRole, index: u64) -> StdRng {
+        StdRng::seed_from_u64(self.stream_seed(run, generation, role, Some(index)))
+    }
+}
+
+#[cfg(test)]
+mod substreams_tests {
+    use std::collections::HashSet;
+    use rand::Rng;
+    use crate::alg::crossover::PositionBasedCrossover;
+    use crate::population::{BinaryPopGenerator, IntPermEncoding, PopGenerator};
+    use super::*;
+
+    fn draws(mut rng: StdRng) -> Vec<u64> {
+        (0..8).map(|_| rng.r#gen()).collect()
+    }
+
+    fn parents(n: usize) -> Vec<(IntPermEncoding, IntPermEncoding)> {
+        (0..n).map(|i| {
+            let a: Vec<usize> = (0..20).map(|gene| (gene * 7 + i) % 20).collect();
+            let b: Vec<usize> = (0..20).rev().collect();
+            (IntPermEncoding(a), IntPermEncoding(b))
+        }).collect()
+    }
+
+    fn offspring_serial(streams: &RngStreams, generation: u64) -> Vec<IntPermEncoding> {
+        let pbx = PositionBasedCrossover::new(5).unwrap();
+        parents(64).iter().enumerate().map(|(index, (a, b))| {
+            let mut rng = streams.individual_stream(0, generation, StreamRole::Crossover, index as u64);
+            pbx.crossover(a, b, &mut rng)
+        }).collect()
+    }
+
+    #[test]
+    fn test_streams_are_reproducible() {
+        let streams = RngStreams::new(42);
+        assert_eq!(draws(streams.stream(1, 2, StreamRole::Mutation)), draws(RngStreams::new(42).stream(1, 2, StreamRole::Mutation)));
+
+        // consumir outros fluxos antes não altera a sequência
+        let _ = draws(streams.stream(1, 2, StreamRole::Selection));
+        assert_eq!(draws(streams.stream(1, 2, StreamRole::Mutation)), draws(RngStreams::new(42).stream(1, 2, StreamRole::Mutation)));
+        assert_eq!(offspring_serial(&streams, 3), offspring_serial(&streams, 3));
+    }
+
+    #[test]
+    fn test_different_tuples_give_different_streams() {
+        let streams = RngStreams::new(7);
+        let roles = [
+            StreamRole::Initialization,
+            StreamRole::Selection,
+            StreamRole::Crossover,
+            StreamRole::Mutation,
+            StreamRole::Evaluation,
+            StreamRole::Restart,
+        ];
+        let mut seeds = HashSet::new();
+        let mut first_draws = HashSet::new();
+        for run in 0..4 {
+            for generation in 0..10 {
+                for role in roles {
+                    seeds.insert(streams.stream_seed(run, generation, role, None));
+                    first_draws.insert(draws(streams.stream(run, generation, role)));
+                    for index in [0, 1, 2, u64::MAX] {
+                        seeds.insert(streams.stream_seed(run, generation, role, Some(index)));
+                        first_draws.insert(draws(streams.individual_stream(run, generation, role, index)));
+                    }
+                }
+            }
+        }
+        assert_eq!(seeds.len(), 4 * 10 * roles.len() * 5);
+        assert_eq!(first_draws.len(), seeds.len());
+
+        // o fluxo da população não coincide com nenhum fluxo de indivíduo
+        assert_ne!(draws(streams.stream(0, 0, StreamRole::Mutation)), draws(streams.individual_stream(0, 0, StreamRole::Mutation, u64::MAX)));
+
+        // componentes trocados de lugar não colidem
+        assert_ne!(streams.stream_seed(1, 2, StreamRole::Selection, Some(0)), streams.stream_seed(2, 1, StreamRole::Selection, Some(0)));
+        assert_ne!(streams.stream_seed(0, 0, StreamRole::Selection, Some(0)), RngStreams::new(8).stream_seed(0, 0, StreamRole::Selection, Some(0)));
+    }
+
+    #[test]
+    fn test_initialization_stream_reproduces_population() {
+        let streams = RngStreams::new(99);
+        let generator = BinaryPopGenerator::new(40, 10);
+        let first = generator.gen_pop_with(&mut streams.stream(3, 0, StreamRole::Initialization));
+        let second = generator.gen_pop_with(&mut streams.stream(3, 0, StreamRole::Initialization));
+        assert_eq!(first.0, second.0);
+        assert_ne!(first.0, generator.gen_pop_with(&mut streams.stream(4, 0, StreamRole::Initialization)).0);
+    }
+
+    #[test]
+    fn test_stream_draws_look_uniform() {
+        // média de bits ligados entre fluxos vizinhos perto de 1/2
+        let streams = RngStreams::new(0);
+        let ones: u32 = (0..1000).map(|index| streams.individual_stream(0, 0, StreamRole::Mutation, index).r#gen::<u64>().count_ones()).sum();
+        let mean = ones as f64 / 64_000.0;
+        assert!((mean - 0.5).abs() < 0.01, "{}", mean);
+    }
+
+    #[cfg(feature = "parallel")]
+    #[test]
+    fn test_parallel_matches_serial_for_any_thread_count() {
+        use rayon::prelude::*;
+
+        let streams = RngStreams::new(2024);
+        let pbx = PositionBasedCrossover::new(5).unwrap();
+        let serial = offspring_serial(&streams, 3);
+        for threads in [1, 2, 4, 8] {
+            let pool = rayon::ThreadPoolBuilder::new().num_threads(threads).build().unwrap();
+            let parallel: Vec<IntPermEncoding> = pool.install(|| {
+                parents(64).par_iter().enumerate().map(|(index, (a, b))| {
+                    let mut rng = streams.individual_stream(0, 3, StreamRole::Crossover, index as u64);
+                    pbx.crossover(a, b, &mut rng)
+                }).collect()
+            });
+            assert_eq!(parallel, serial, "{} threads", threads);
+        }
+    }
+}
@@ -0,0 +1,170 @@
+use std::collections::VecDeque;
+use rand::Rng;
+use rand::seq::SliceRandom;
+use crate::population::Encoding;
+
+/// Modelo barato do objetivo, usado para pré-selecionar descendentes antes
+/// da avaliação real. Scores maiores são melhores.
+pub trait Surrogate<E: Encoding> {
+    fn predict(&self, ind: &E) -> f64;
+    fn update(&mut self, ind: &E, true_score: f64);
+}
+
+/// Média dos scores dos `k` vizinhos mais próximos (por
+/// `Encoding::distance`) entre os indivíduos já avaliados. Sem dados, prevê
+/// `NaN`, que a pré-seleção trata como o melhor valor possível.
+///
+/// Guarda no máximo `capacity` avaliações; ao encher, descarta a mais antiga
+/// (FIFO), de modo que o modelo acompanha a região que a busca explora agora
+/// e o custo de `predict` não cresce com a execução.
+#[derive(Debug, Clone)]
+pub struct KnnSurrogate<E: Encoding> {
+    k: usize,
+    capacity: usize,
+    samples: VecDeque<(E, f64)>,
+}
+
+impl<E: Encoding> KnnSurrogate<E> {
+    /// `k` e `capacity` são ao menos 1.
+    pub fn new(k: usize, capacity: usize) -> Self {
+        let capacity = capacity.max(1);
+        KnnSurrogate { k: k.max(1), capacity, samples: VecDeque::with_capacity(capacity) }
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    pub fn len(&self) -> usize {
+        self.samples.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.samples.is_empty()
+    }
+}
+
+impl<E: Encoding> Surrogate<E> for KnnSurrogate<E> {
+    fn predict(&self, ind: &E) -> f64 {
+        if self.samples.is_empty()
+        { return f64::NAN }
+
+        let mut neighbors: Vec<(f64, f64)> = self.samples.iter().map(|(sample, score)| (sample.distance(ind), *score)).collect();
+        neighbors.sort_by(|a, b| a.0.total_cmp(&b.0));
+
+        // pontos idênticos a um já avaliado recebem o score dele
+        let exact: Vec<f64> = neighbors.iter().take_while(|(distance, _)| *distance == 0.0).map(|(_, score)| *score).collect();
+        let scores: Vec<f64> = if exact.is_empty() {
+            neighbors.iter().take(self.k).map(|(_, score)| *score).collect()
+        } else {
+            exact
+        };
+        scores.iter().sum::<f64>() / scores.len() as f64
+    }
+
+    fn update(&mut self, ind: &E, true_score: f64) {
+        if self.samples.len() >= self.capacity
+        { self.samples.pop_front(); }
+        self.samples.push_back((ind.clone(), true_score));
+    }
+}
+
+/// Resultado da pré-seleção: quem deve ser avaliado de verdade e o score
+/// estimado dos demais.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Prescreen {
+    /// Índices (nos descendentes) a avaliar com o objetivo real.
+    pub evaluate: Vec<usize>,
+    /// Índices restantes com o score previsto pelo surrogate.
+    pub estimated: Vec<(usize, f64)>,
+}
+
+/// Ordena os descendentes pelo surrogate e escolhe para avaliação real os
+/// `ceil(keep_fraction * n)` melhores mais `exploration` sorteados entre os
+/// demais.
+pub fn prescreen<E, S, R>(surrogate: &S, offspring: &[E], keep_fraction: f64, exploration: usize, rng: &mut R) -> Prescreen
+where
+    E: Encoding,
+    S: Surrogate<E> + ?Sized,
+    R: Rng + ?Sized,
+{
+    let predictions: Vec<f64> = offspring.iter().map(|ind| surrogate.predict(ind)).collect();
+    let mut ranked: Vec<usize> = (0..offspring.len()).collect();
+    ranked.sort_by(|&a, &b| predictions[b].total_cmp(&predictions[a]).then(a.cmp(&b)));
+
+    let keep = ((keep_fraction.clamp(0.0, 1.0) * offspring.len() as f64).ceil() as usize).min(offspring.len());
+    let mut rest = ranked.split_off(keep);
+    rest.shuffle(rng);
+    let explored = rest.split_off(rest.len().saturating_sub(exploration));
+
+    let mut evaluate = ranked;
+    evaluate.extend(explored);
+    evaluate.sort();
+    rest.sort();
+
+    Prescreen {
+        evaluate,
+        estimated: rest.into_iter().map(|index| (index, predictions[index])).collect(),
+    }
+}
+
+#[cfg(test)]
+mod surrogate_tests {
+    use crate::population::RealEncoding;
+    use super::*;
+
+    fn point(x: f64) -> RealEncoding {
+        RealEncoding(vec![x])
+    }
+
+    #[test]
+    fn test_knn_exact_match_returns_training_score() {
+        let mut surrogate = KnnSurrogate::new(3, 100);
+        assert!(surrogate.predict(&point(0.0)).is_nan());
+
+        for (x, score) in [(0.0, 1.0), (1.0, 5.0), (2.0, 9.0), (10.0, 100.0)] {
+            surrogate.update(&point(x), score);
+        }
+        assert_eq!(surrogate.predict(&point(1.0)), 5.0);
+        // vizinhos de 1.4: 1.0, 2.0 e 0.0
+        assert_eq!(surrogate.predict(&point(1.4)), 5.0);
+        assert_eq!(surrogate.predict(&point(9.0)), (100.0 + 9.0 + 5.0) / 3.0);
+    }
+
+    #[test]
+    fn test_knn_archive_evicts_oldest() {
+        let mut surrogate = KnnSurrogate::new(1, 3);
+        for x in 0..5 {
+            surrogate.update(&point(x as f64), x as f64);
+        }
+        assert_eq!(surrogate.len(), 3);
+        // 0.0 e 1.0 saíram: o vizinho mais próximo de 0.0 agora é 2.0
+        assert_eq!(surrogate.predict(&point(0.0)), 2.0);
+        assert_eq!(surrogate.predict(&point(4.0)), 4.0);
+        assert_eq!(KnnSurrogate::<RealEncoding>::new(1, 0).capacity(), 1);
+    }
+
+    #[test]
+    fn test_prescreen_evaluation_counts() {
+        let mut surrogate = KnnSurrogate::new(1, 100);
+        for x in 0..10 {
+            surrogate.update(&point(x as f64), x as f64);
+        }
+        let offspring: Vec<RealEncoding> = (0..10).map(|x| point(x as f64)).collect();
+        let mut rng = rand::thread_rng();
+
+        let result = prescreen(&surrogate, &offspring, 0.3, 0, &mut rng);
+        assert_eq!(result.evaluate, vec![7, 8, 9]);
+        assert_eq!(result.estimated.len(), 7);
+        assert!(result.estimated.iter().all(|&(index, score)| score == index as f64));
+
+        let result = prescreen(&surrogate, &offspring, 0.25, 2, &mut rng);
+        assert_eq!(result.evaluate.len(), 3 + 2);
+        assert!(result.evaluate.ends_with(&[8, 9]));
+        assert_eq!(result.evaluate.len() + result.estimated.len(), 10);
+
+        let result = prescreen(&surrogate, &offspring, 0.9, 5, &mut rng);
+        assert_eq!(result.evaluate.len(), 10);
+        assert!(result.estimated.is_empty());
+    }
+}
@@ -0,0 +1,296 @@
+use std::collections::VecDeque;
+use std::fmt;
+
+/// Critério de parada por convergência: ajusta uma reta (mínimos quadrados)
+/// ao melhor score até então das últimas `window` gerações e para quando a
+/// melhora projetada até o fim do orçamento, `inclinação · gerações
+/// restantes`, fica abaixo de `min_projected_improvement`. Scores maiores são
+/// melhores. Antes de a janela encher, nunca para.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConvergenceTermination {
+    window: u64,
+    pub min_projected_improvement: f64,
+    best: VecDeque<f64>,
+}
+
+impl ConvergenceTermination {
+    /// Janelas menores que 2 são tratadas como 2, o mínimo para uma reta.
+    pub fn new(window: u64, min_projected_improvement: f64) -> Self {
+        let window = window.max(2);
+        ConvergenceTermination { window, min_projected_improvement, best: VecDeque::with_capacity(window as usize) }
+    }
+
+    pub fn window(&self) -> u64 {
+        self.window
+    }
+
+    /// Registra o melhor score da geração e diz se a execução deve parar.
+    pub fn update(&mut self, best_so_far: f64, remaining_generations: u64) -> bool {
+        if self.best.len() as u64 >= self.window
+        { self.best.pop_front(); }
+        self.best.push_back(best_so_far);
+
+        match self.slope() {
+            Some(slope) => slope * (remaining_generations as f64) < self.min_projected_improvement,
+            None => false,
+        }
+    }
+
+    /// Inclinação da reta ajustada à janela, por geração; `None` até a janela
+    /// encher.
+    pub fn slope(&self) -> Option<f64> {
+        if (self.best.len() as u64) < self.window
+        { return None }
+
+        let n = self.best.len() as f64;
+        let mean_x = (n - 1.0) / 2.0;
+        let mean_y = self.best.iter().sum::<f64>() / n;
+        let (covariance, variance) = self.best.iter().enumerate().fold((0.0, 0.0), |(cov, var), (x, y)| {
+            let dx = x as f64 - mean_x;
+            (cov + dx * (y - mean_y), var + dx * dx)
+        });
+        Some(covariance / variance)
+    }
+}
+
+/// Estado da execução consultado pelos critérios de parada.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RunProgress {
+    pub generation: u64,
+    pub evaluations: u64,
+    /// Melhor score até então (maior é melhor).
+    pub best_score: f64,
+    /// Gerações consecutivas sem melhora do melhor score.
+    pub stagnant_generations: u64,
+    pub elapsed_seconds: f64,
+}
+
+impl Default for RunProgress {
+    fn default() -> Self {
+        RunProgress {
+            generation: 0,
+            evaluations: 0,
+            best_score: f64::NEG_INFINITY,
+            stagnant_generations: 0,
+            elapsed_seconds: 0.0,
+        }
+    }
+}
+
+impl RunProgress {
+    /// Registra o fim de uma geração com seu melhor score e o total de
+    /// avaliações e de segundos até então.
+    pub fn advance(&mut self, best: f64, evaluations: u64, elapsed_seconds: f64) {
+        self.generation += 1;
+        self.evaluations = evaluations;
+        self.elapsed_seconds = elapsed_seconds;
+        if best > self.best_score {
+            self.best_score = best;
+            self.stagnant_generations = 0;
+        } else {
+            self.stagnant_generations += 1;
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum TerminationCriterion {
+    MaxGenerations(u64),
+    /// Para quando o melhor score alcança o alvo.
+    TargetScore(f64),
+    /// Para após tantas gerações sem melhora.
+    Stagnation(u64),
+    MaxSeconds(f64),
+}
+
+impl TerminationCriterion {
+    /// Nome estável, igual à chave usada na configuração.
+    pub fn name(&self) -> &'static str {
+        match self {
+            TerminationCriterion::MaxGenerations(_) => "max_generations",
+            TerminationCriterion::TargetScore(_) => "target_score",
+            TerminationCriterion::Stagnation(_) => "stagnation",
+            TerminationCriterion::MaxSeconds(_) => "max_seconds",
+        }
+    }
+
+    pub fn is_met(&self, progress: &RunProgress) -> bool {
+        match *self {
+            TerminationCriterion::MaxGenerations(max) => progress.generation >= max,
+            TerminationCriterion::TargetScore(target) => progress.best_score >= target,
+            TerminationCriterion::Stagnation(generations) => progress.stagnant_generations >= generations,
+            TerminationCriterion::MaxSeconds(max) => progress.elapsed_seconds >= max,
+        }
+    }
+}
+
+/// Combinação de critérios de parada.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Termination {
+    Criterion(TerminationCriterion),
+    /// Para quando qualquer um dos membros para.
+    Any(Vec<Termination>),
+    /// Para quando todos os membros param na mesma geração.
+    All(Vec<Termination>),
+}
+
+/// Critério que disparou e em que ponto da execução.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TerminationReason {
+    pub criterion: String,
+    pub generation: u64,
+    pub evaluations: u64,
+}
+
+impl fmt::Display for TerminationReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} at generation {} ({} evaluations)", self.criterion, self.generation, self.evaluations)
+    }
+}
+
+impl Termination {
+    /// Nome estável: o do critério, ou `any(...)`/`all(...)` com os nomes dos
+    /// membros.
+    pub fn name(&self) -> String {
+        let group = |mode: &str, members: &[Termination]| {
+            let names: Vec<String> = members.iter().map(Termination::name).collect();
+            format!("{}({})", mode, names.join(", "))
+        };
+        match self {
+            Termination::Criterion(criterion) => criterion.name().to_string(),
+            Termination::Any(members) => group("any", members),
+            Termination::All(members) => group("all", members),
+        }
+    }
+
+    /// `Some` com o critério que disparou, se a execução deve parar. Em `Any`,
+    /// vale o primeiro membro satisfeito, na ordem da configuração; um grupo
+    /// `All` é reportado pelo seu nome. Grupos vazios nunca param.
+    pub fn check(&self, progress: &RunProgress) -> Option<TerminationReason> {
+        let reason = |criterion: String| TerminationReason {
+            criterion,
+            generation: progress.generation,
+            evaluations: progress.evaluations,
+        };
+        match self {
+            Termination::Criterion(criterion) =>
+                criterion.is_met(progress).then(|| reason(criterion.name().to_string())),
+            Termination::Any(members) => members.iter().find_map(|member| member.check(progress)),
+            Termination::All(members) => {
+                let met = !members.is_empty() && members.iter().all(|member| member.check(progress).is_some());
+                met.then(|| reason(self.name()))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod termination_tests {
+    use super::*;
+
+    #[test]
+    fn test_slope_matches_least_squares() {
+        let mut criterion = ConvergenceTermination::new(5, 0.0);
+        for best in [1.0, 2.0, 4.0, 4.0] {
+            criterion.update(best, 100);
+            assert_eq!(criterion.slope(), None);
+        }
+        criterion.update(7.0, 100);
+        assert!((criterion.slope().unwrap() - 1.4).abs() < 1e-12);
+
+        // a janela desliza: [2, 4, 4, 7, 7]
+        criterion.update(7.0, 100);
+        assert!((criterion.slope().unwrap() - 1.3).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_linear_improvement_never_stops() {
+        let mut criterion = ConvergenceTermination::new(10, 5.0);
+        let horizon = 1000;
+        for generation in 0..500 {
+            assert!(!criterion.update(0.5 * generation as f64, horizon - generation));
+        }
+    }
+
+    #[test]
+    fn test_plateau_stops_at_first_full_window() {
+        let mut criterion = ConvergenceTermination::new(10, 1.0);
+        let stops: Vec<bool> = (0..20).map(|generation| criterion.update(42.0, 1000 - generation)).collect();
+        assert_eq!(stops.iter().position(|&stop| stop), Some(9));
+        assert_eq!(criterion.slope(), Some(0.0));
+    }
+
+    #[test]
+    fn test_minimum_window() {
+        let mut criterion = ConvergenceTermination::new(0, 1.0);
+        assert_eq!(criterion.window(), 2);
+        assert!(!criterion.update(1.0, 10));
+        assert!(criterion.update(1.0, 10));
+    }
+
+    fn history(bests: &[f64]) -> Vec<RunProgress> {
+        let mut progress = RunProgress::default();
+        bests.iter().enumerate().map(|(generation, &best)| {
+            progress.advance(best, 100 * (generation as u64 + 1), 0.0);
+            progress.clone()
+        }).collect()
+    }
+
+    fn first_stop(termination: &Termination, bests: &[f64]) -> Option<TerminationReason> {
+        history(bests).iter().find_map(|progress| termination.check(progress))
+    }
+
+    #[test]
+    fn test_progress_tracks_stagnation() {
+        let progress = history(&[1.0, 3.0, 3.0, 2.0, 4.0, 4.0]);
+        let stagnant: Vec<u64> = progress.iter().map(|p| p.stagnant_generations).collect();
+        assert_eq!(stagnant, vec![0, 0, 1, 2, 0, 1]);
+        assert_eq!(progress[5].best_score, 4.0);
+        assert_eq!(progress[5].generation, 6);
+    }
+
+    #[test]
+    fn test_any_reports_first_met_criterion() {
+        use TerminationCriterion::*;
+        let termination = Termination::Any(vec![
+            Termination::Criterion(TargetScore(10.0)),
+            Termination::Criterion(MaxGenerations(5)),
+        ]);
+        let reason = first_stop(&termination, &[1.0, 2.0, 3.0, 4.0, 5.0, 6.0]).unwrap();
+        assert_eq!(reason, TerminationReason { criterion: "max_generations".to_string(), generation: 5, evaluations: 500 });
+
+        let reason = first_stop(&termination, &[1.0, 12.0, 3.0]).unwrap();
+        assert_eq!((reason.criterion.as_str(), reason.generation), ("target_score", 2));
+    }
+
+    #[test]
+    fn test_nested_all_group() {
+        use TerminationCriterion::*;
+        // alvo OU (estagnou E pelo menos 4 gerações)
+        let termination = Termination::Any(vec![
+            Termination::Criterion(TargetScore(100.0)),
+            Termination::All(vec![
+                Termination::Criterion(Stagnation(2)),
+                Termination::Criterion(MaxGenerations(4)),
+            ]),
+        ]);
+        assert_eq!(termination.name(), "any(target_score, all(stagnation, max_generations))");
+
+        // estagna cedo, mas o grupo só dispara ao atingir 4 gerações
+        let reason = first_stop(&termination, &[5.0, 5.0, 5.0, 5.0, 5.0]).unwrap();
+        assert_eq!(reason, TerminationReason { criterion: "all(stagnation, max_generations)".to_string(), generation: 4, evaluations: 400 });
+
+        // melhora contínua: a estagnação nunca é atingida
+        assert_eq!(first_stop(&termination, &[1.0, 2.0, 3.0, 4.0, 5.0, 6.0]), None);
+
+        // o alvo dispara sozinho
+        assert_eq!(first_stop(&termination, &[1.0, 100.0]).unwrap().criterion, "target_score");
+    }
+
+    #[test]
+    fn test_empty_groups_never_stop() {
+        let progress = history(&[1.0, 1.0, 1.0]);
+        assert_eq!(Termination::Any(vec![]).check(&progress[2]), None);
+        assert_eq!(Termination::All(vec![]).check(&progress[2]), None);
+    }
+}
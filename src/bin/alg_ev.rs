@@ -0,0 +1,60 @@
+use std::fs::File;
+use std::process::ExitCode;
+use gen_alg::alg::objectives::Formula;
+use gen_alg::runtime::error::{load_config, RuntimeError, EXIT_CONFIG_ERROR};
+
+const USAGE: &str = "usage:
+    alg_ev validate --config <path>
+    alg_ev stats --cnf <path>";
+
+fn option_value<'a>(args: &'a [String], name: &str) -> Option<&'a str> {
+    args.iter()
+        .position(|arg| arg == name)
+        .and_then(|index| args.get(index + 1))
+        .map(String::as_str)
+}
+
+fn validate(config_path: &str) -> ExitCode {
+    match load_config(config_path) {
+        Ok(_) => {
+            println!("{}: ok", config_path);
+            ExitCode::SUCCESS
+        },
+        Err(err) => {
+            eprintln!("{}", err);
+            ExitCode::from(err.exit_code())
+        }
+    }
+}
+
+fn stats(cnf_path: &str) -> ExitCode {
+    let formula = File::open(cnf_path)
+        .map_err(Into::into)
+        .and_then(Formula::parse_from_dimacs_cnf)
+        .map_err(|error| RuntimeError::Formula { path: cnf_path.into(), error });
+
+    match formula {
+        Ok(formula) => {
+            println!("{}", formula.stats());
+            ExitCode::SUCCESS
+        },
+        Err(err) => {
+            eprintln!("{}", err);
+            ExitCode::from(err.exit_code())
+        }
+    }
+}
+
+fn main() -> ExitCode {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let command = args.first().map(String::as_str);
+
+    match (command, option_value(&args, "--config"), option_value(&args, "--cnf")) {
+        (Some("validate"), Some(config_path), _) => validate(config_path),
+        (Some("stats"), _, Some(cnf_path)) => stats(cnf_path),
+        _ => {
+            eprintln!("{}", USAGE);
+            ExitCode::from(EXIT_CONFIG_ERROR)
+        }
+    }
+}
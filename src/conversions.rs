@@ -0,0 +1,263 @@
+use std::fmt;
+use crate::population::{BinaryEncoding, Genome, IntPermEncoding, IntegerEncoding, RealEncoding};
+
+/// Maior número de bits por gene real: a precisão da mantissa de um f64.
+pub const MAX_REAL_BITS: usize = 53;
+
+#[derive(Debug, PartialEq)]
+pub enum ConversionError {
+    /// Número de bits por gene fora de `1..=max`.
+    InvalidWidth { bits: usize, max: usize },
+    /// Gene que não cabe em `bits` bits.
+    GeneOverflow { index: usize, gene: u64, bits: usize },
+    /// Comprimento da codificação binária não é múltiplo dos bits por gene.
+    LengthNotMultiple { len: usize, bits: usize },
+    /// `lower >= upper` ou limites não finitos.
+    InvalidBounds,
+    NonFiniteGene { index: usize },
+    /// Não é uma permutação de `0..n`.
+    InvalidPermutation,
+    /// O elemento `index` excede `n - 1 - index`.
+    InvalidInversionSequence { index: usize },
+}
+
+impl fmt::Display for ConversionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ConversionError::InvalidWidth { bits, max } => write!(f, "{} bits per gene is outside 1..={}", bits, max),
+            ConversionError::GeneOverflow { index, gene, bits } => write!(f, "gene {} ({}) does not fit in {} bits", index, gene, bits),
+            ConversionError::LengthNotMultiple { len, bits } => write!(f, "{} bits cannot be split into genes of {} bits", len, bits),
+            ConversionError::InvalidBounds => write!(f, "bounds must be finite with lower < upper"),
+            ConversionError::NonFiniteGene { index } => write!(f, "gene {} is not finite", index),
+            ConversionError::InvalidPermutation => write!(f, "not a permutation of 0..n"),
+            ConversionError::InvalidInversionSequence { index } => write!(f, "inversion sequence element {} is out of range", index),
+        }
+    }
+}
+
+impl std::error::Error for ConversionError {}
+
+fn check_width(bits: usize, max: usize) -> Result<(), ConversionError> {
+    if bits == 0 || bits > max
+    { return Err(ConversionError::InvalidWidth { bits, max }) }
+    Ok(())
+}
+
+fn check_bounds((lower, upper): (f64, f64)) -> Result<(), ConversionError> {
+    if !lower.is_finite() || !upper.is_finite() || lower >= upper
+    { return Err(ConversionError::InvalidBounds) }
+    Ok(())
+}
+
+/// Concatena os genes com `bits` bits cada, o mais significativo primeiro.
+fn pack(genes: impl ExactSizeIterator<Item = u64>, bits: usize) -> BinaryEncoding {
+    let mut encoding = BinaryEncoding::zeros(genes.len() * bits);
+    for (index, gene) in genes.enumerate() {
+        for shift in (0..bits).filter(|shift| gene >> shift & 1 == 1) {
+            encoding.set(index * bits + bits - 1 - shift, true);
+        }
+    }
+    encoding
+}
+
+/// Inverso de `pack`.
+fn unpack(encoding: &BinaryEncoding, bits: usize) -> Result<Vec<u64>, ConversionError> {
+    if !encoding.len().is_multiple_of(bits)
+    { return Err(ConversionError::LengthNotMultiple { len: encoding.len(), bits }) }
+
+    Ok((0..encoding.len() / bits)
+        .map(|index| (0..bits).fold(0u64, |gene, bit| (gene << 1) | encoding.get(index * bits + bit) as u64))
+        .collect())
+}
+
+fn max_level(bits: usize) -> u64 {
+    u64::MAX >> (64 - bits)
+}
+
+impl IntegerEncoding {
+    /// Codifica cada gene em binário com `bits_per_gene` bits (o mais
+    /// significativo primeiro), na ordem dos genes.
+    pub fn to_binary(&self, bits_per_gene: usize) -> Result<BinaryEncoding, ConversionError> {
+        check_width(bits_per_gene, 64)?;
+        if let Some((index, &gene)) = self.0.iter().enumerate().find(|(_, gene)| **gene > max_level(bits_per_gene))
+        { return Err(ConversionError::GeneOverflow { index, gene, bits: bits_per_gene }) }
+
+        Ok(pack(self.0.iter().copied(), bits_per_gene))
+    }
+
+    pub fn from_binary(encoding: &BinaryEncoding, bits_per_gene: usize) -> Result<Self, ConversionError> {
+        check_width(bits_per_gene, 64)?;
+        unpack(encoding, bits_per_gene).map(IntegerEncoding)
+    }
+}
+
+impl RealEncoding {
+    /// Discretiza cada gene de `bounds` em `2^bits` níveis igualmente
+    /// espaçados (os extremos incluídos), arredondando para o mais próximo.
+    /// Genes fora dos limites são saturados.
+    pub fn quantize(&self, bounds: (f64, f64), bits: usize) -> Result<BinaryEncoding, ConversionError> {
+        check_width(bits, MAX_REAL_BITS)?;
+        check_bounds(bounds)?;
+        if let Some(index) = self.0.iter().position(|gene| !gene.is_finite())
+        { return Err(ConversionError::NonFiniteGene { index }) }
+
+        let (lower, upper) = bounds;
+        let levels = max_level(bits) as f64;
+        let genes = self.0.iter().map(|gene| ((gene.clamp(lower, upper) - lower) / (upper - lower) * levels).round() as u64);
+        Ok(pack(genes, bits))
+    }
+
+    /// Inverso de `quantize`: `quantize(dequantize(b)) == b`, e
+    /// `dequantize(quantize(x))` difere de `x` em no máximo meio passo.
+    pub fn dequantize(encoding: &BinaryEncoding, bounds: (f64, f64), bits: usize) -> Result<Self, ConversionError> {
+        check_width(bits, MAX_REAL_BITS)?;
+        check_bounds(bounds)?;
+
+        let (lower, upper) = bounds;
+        let levels = max_level(bits) as f64;
+        let genes = unpack(encoding, bits)?;
+        Ok(RealEncoding(genes.into_iter().map(|level| lower + level as f64 / levels * (upper - lower)).collect()))
+    }
+}
+
+impl IntPermEncoding {
+    /// Código de Lehmer: o elemento `i` conta os elementos à direita da
+    /// posição `i` menores que ela, e fica em `0..=n-1-i`. Como o intervalo
+    /// de cada posição é fixo, crossovers comuns (ex. um ponto) sobre a
+    /// sequência sempre produzem uma sequência válida.
+    pub fn to_inversion_sequence(&self) -> Result<IntegerEncoding, ConversionError> {
+        let n = self.len();
+        let mut seen = vec![false; n];
+        for &element in &self.0 {
+            if element >= n || seen[element]
+            { return Err(ConversionError::InvalidPermutation) }
+            seen[element] = true;
+        }
+
+        Ok(IntegerEncoding(self.0
+            .iter()
+            .enumerate()
+            .map(|(i, &element)| self.0[i + 1..].iter().filter(|&&other| other < element).count() as u64)
+            .collect()))
+    }
+
+    pub fn from_inversion_sequence(sequence: &IntegerEncoding) -> Result<Self, ConversionError> {
+        let n = sequence.len();
+        let mut remaining: Vec<usize> = (0..n).collect();
+        let mut permutation = Vec::with_capacity(n);
+        for (index, &code) in sequence.0.iter().enumerate() {
+            if code >= remaining.len() as u64
+            { return Err(ConversionError::InvalidInversionSequence { index }) }
+            permutation.push(remaining.remove(code as usize));
+        }
+        Ok(IntPermEncoding(permutation))
+    }
+}
+
+#[cfg(test)]
+mod conversion_tests {
+    use rand::prelude::*;
+    use super::*;
+
+    #[test]
+    fn test_integer_binary_round_trip() {
+        let mut rng = rand::thread_rng();
+        for bits in [1, 5, 17, 63, 64] {
+            let genes: Vec<u64> = (0..10).map(|_| rng.gen_range(0..=max_level(bits))).collect();
+            let integer = IntegerEncoding(genes);
+            let binary = integer.to_binary(bits).unwrap();
+            assert_eq!(binary.len(), 10 * bits);
+            assert_eq!(IntegerEncoding::from_binary(&binary, bits).unwrap(), integer);
+        }
+
+        let binary = IntegerEncoding(vec![5, 2]).to_binary(3).unwrap();
+        assert_eq!(binary.to_bitstring(), "101010");
+    }
+
+    #[test]
+    fn test_integer_binary_errors() {
+        assert_eq!(
+            IntegerEncoding(vec![3, 8]).to_binary(3),
+            Err(ConversionError::GeneOverflow { index: 1, gene: 8, bits: 3 })
+        );
+        assert_eq!(IntegerEncoding(vec![1]).to_binary(0), Err(ConversionError::InvalidWidth { bits: 0, max: 64 }));
+        assert_eq!(
+            IntegerEncoding::from_binary(&BinaryEncoding::zeros(7), 3),
+            Err(ConversionError::LengthNotMultiple { len: 7, bits: 3 })
+        );
+    }
+
+    #[test]
+    fn test_real_quantization_round_trip() {
+        let mut rng = rand::thread_rng();
+        let bounds = (-5.0, 3.0);
+        for bits in [1, 8, 20, MAX_REAL_BITS] {
+            let step = (bounds.1 - bounds.0) / max_level(bits) as f64;
+            let real = RealEncoding((0..10).map(|_| rng.gen_range(bounds.0..=bounds.1)).collect());
+
+            let binary = real.quantize(bounds, bits).unwrap();
+            let restored = RealEncoding::dequantize(&binary, bounds, bits).unwrap();
+            for (x, y) in real.0.iter().zip(&restored.0) {
+                assert!((x - y).abs() <= step / 2.0 + 1e-12);
+            }
+            assert_eq!(restored.quantize(bounds, bits).unwrap(), binary);
+        }
+    }
+
+    #[test]
+    fn test_real_quantization_errors() {
+        let real = RealEncoding(vec![0.0, f64::NAN]);
+        assert_eq!(real.quantize((0.0, 1.0), 8), Err(ConversionError::NonFiniteGene { index: 1 }));
+        assert_eq!(real.quantize((1.0, 1.0), 8), Err(ConversionError::InvalidBounds));
+        assert_eq!(real.quantize((0.0, 1.0), 54), Err(ConversionError::InvalidWidth { bits: 54, max: MAX_REAL_BITS }));
+
+        // saturação nos limites
+        let binary = RealEncoding(vec![-1.0, 2.0]).quantize((0.0, 1.0), 4).unwrap();
+        assert_eq!(binary.to_bitstring(), "00001111");
+    }
+
+    #[test]
+    fn test_inversion_sequence_round_trip() {
+        let mut rng = rand::thread_rng();
+        for n in [0, 1, 2, 10, 50] {
+            let mut elements: Vec<usize> = (0..n).collect();
+            elements.shuffle(&mut rng);
+            let permutation = IntPermEncoding(elements);
+            let sequence = permutation.to_inversion_sequence().unwrap();
+            assert!(sequence.0.iter().enumerate().all(|(i, &code)| code as usize <= n - 1 - i));
+            assert_eq!(IntPermEncoding::from_inversion_sequence(&sequence).unwrap(), permutation);
+        }
+
+        assert_eq!(IntPermEncoding(vec![2, 0, 3, 1]).to_inversion_sequence().unwrap(), IntegerEncoding(vec![2, 0, 1, 0]));
+    }
+
+    #[test]
+    fn test_inversion_sequence_errors() {
+        assert_eq!(IntPermEncoding(vec![0, 0, 1]).to_inversion_sequence(), Err(ConversionError::InvalidPermutation));
+        assert_eq!(IntPermEncoding(vec![0, 3]).to_inversion_sequence(), Err(ConversionError::InvalidPermutation));
+        assert_eq!(
+            IntPermEncoding::from_inversion_sequence(&IntegerEncoding(vec![1, 2, 0])),
+            Err(ConversionError::InvalidInversionSequence { index: 1 })
+        );
+    }
+
+    #[test]
+    fn test_one_point_crossover_on_inversion_sequences() {
+        let mut rng = rand::thread_rng();
+        let n = 12;
+        for _ in 0..100 {
+            let mut a: Vec<usize> = (0..n).collect();
+            let mut b: Vec<usize> = (0..n).collect();
+            a.shuffle(&mut rng);
+            b.shuffle(&mut rng);
+            let a = IntPermEncoding(a).to_inversion_sequence().unwrap();
+            let b = IntPermEncoding(b).to_inversion_sequence().unwrap();
+
+            let cut = rng.gen_range(0..=n);
+            let child = IntegerEncoding(a.0[..cut].iter().chain(&b.0[cut..]).copied().collect());
+            let mut permutation = IntPermEncoding::from_inversion_sequence(&child).unwrap().0;
+            permutation.sort();
+            assert_eq!(permutation, (0..n).collect::<Vec<_>>());
+        }
+    }
+}
@@ -1,3 +1,7 @@
 pub mod alg;
+pub mod conversions;
+pub mod permutation_metrics;
 pub mod population;
-pub mod runtime;
\ No newline at end of file
+pub mod stats;
+#[cfg(feature = "serde")]
+pub mod runtime;
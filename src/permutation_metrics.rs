@@ -0,0 +1,242 @@
+use std::sync::atomic::{AtomicU8, Ordering};
+use crate::population::IntPermEncoding;
+
+/// Métrica entre permutações. `IntPermEncoding::distance` usa a métrica
+/// padrão do processo ([`default_metric`]); quem precisa de outra numa única
+/// chamada a passa explicitamente (ex. `speciate_with` e
+/// `Clearing::apply_with` em `alg::niching`).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum PermutationMetric {
+    /// Número de posições com elementos diferentes.
+    #[default]
+    Mismatch,
+    /// Número de pares discordantes.
+    KendallTau,
+    /// Número mínimo de transposições entre as permutações.
+    Cayley,
+}
+
+static DEFAULT_METRIC: AtomicU8 = AtomicU8::new(PermutationMetric::Mismatch as u8);
+
+impl PermutationMetric {
+    /// `None` se `a` e `b` não são permutações do mesmo intervalo contíguo.
+    pub fn distance(self, a: &IntPermEncoding, b: &IntPermEncoding) -> Option<usize> {
+        match self {
+            PermutationMetric::Mismatch => mismatch(a, b),
+            PermutationMetric::KendallTau => kendall_tau(a, b),
+            PermutationMetric::Cayley => cayley_distance(a, b),
+        }
+    }
+
+    fn from_u8(value: u8) -> Self {
+        match value {
+            1 => PermutationMetric::KendallTau,
+            2 => PermutationMetric::Cayley,
+            _ => PermutationMetric::Mismatch,
+        }
+    }
+}
+
+/// Métrica padrão de `IntPermEncoding::distance`, compartilhada entre threads
+/// (e portanto pelo compartilhamento de fitness e pelo crowding).
+pub fn default_metric() -> PermutationMetric {
+    PermutationMetric::from_u8(DEFAULT_METRIC.load(Ordering::Relaxed))
+}
+
+/// Vale para todo o processo, inclusive execuções já em andamento.
+pub fn set_default_metric(metric: PermutationMetric) {
+    DEFAULT_METRIC.store(metric as u8, Ordering::Relaxed);
+}
+
+/// Só exige o mesmo comprimento, já que compara posição a posição.
+pub fn mismatch(a: &IntPermEncoding, b: &IntPermEncoding) -> Option<usize> {
+    if a.0.len() != b.0.len()
+    { return None }
+
+    Some(a.0.iter().zip(&b.0).filter(|(x, y)| x != y).count())
+}
+
+/// Posição em `b` de cada elemento de `a`, isto é, `b⁻¹ ∘ a`. Aceita
+/// permutações de qualquer intervalo contíguo (ex. 1-indexadas); `None` se
+/// os comprimentos diferem, se `b` não cobre um intervalo contíguo ou se `a`
+/// não é uma permutação dos mesmos elementos.
+fn relative(a: &IntPermEncoding, b: &IntPermEncoding) -> Option<Vec<usize>> {
+    if a.0.len() != b.0.len()
+    { return None }
+
+    let n = b.0.len();
+    let base = b.0.iter().copied().min().unwrap_or(0);
+    let mut position = vec![usize::MAX; n];
+    for (i, &value) in b.0.iter().enumerate() {
+        let slot = position.get_mut(value - base)?;
+        if *slot != usize::MAX
+        { return None }
+        *slot = i;
+    }
+
+    let mut seen = vec![false; n];
+    a.0.iter()
+        .map(|&value| {
+            let offset = value.checked_sub(base)?;
+            let first = !std::mem::replace(seen.get_mut(offset)?, true);
+            first.then(|| position[offset])
+        })
+        .collect()
+}
+
+/// Número de pares ordenados de forma diferente nas duas permutações, em
+/// O(n log n) contando inversões com merge sort. Vai de 0 a n(n-1)/2.
+pub fn kendall_tau(a: &IntPermEncoding, b: &IntPermEncoding) -> Option<usize> {
+    let mut sequence = relative(a, b)?;
+    let mut buffer = vec![0; sequence.len()];
+    Some(count_inversions(&mut sequence, &mut buffer))
+}
+
+fn count_inversions(values: &mut [usize], buffer: &mut [usize]) -> usize {
+    let n = values.len();
+    if n < 2
+    { return 0 }
+
+    let mid = n / 2;
+    let mut inversions = count_inversions(&mut values[..mid], &mut buffer[..mid])
+        + count_inversions(&mut values[mid..], &mut buffer[mid..]);
+
+    let (mut i, mut j) = (0, mid);
+    for slot in buffer[..n].iter_mut() {
+        if j == n || (i < mid && values[i] <= values[j]) {
+            *slot = values[i];
+            i += 1;
+        } else {
+            // todos os restantes da metade esquerda são maiores que values[j]
+            inversions += mid - i;
+            *slot = values[j];
+            j += 1;
+        }
+    }
+    values.copy_from_slice(&buffer[..n]);
+    inversions
+}
+
+/// `n` menos o número de ciclos de `a ∘ b⁻¹`. Vai de 0 a n - 1.
+pub fn cycle_distance(a: &IntPermEncoding, b: &IntPermEncoding) -> Option<usize> {
+    let sigma = relative(a, b)?;
+    let mut visited = vec![false; sigma.len()];
+    let mut cycles = 0;
+    for start in 0..sigma.len() {
+        if visited[start]
+        { continue }
+        cycles += 1;
+        let mut i = start;
+        while !visited[i] {
+            visited[i] = true;
+            i = sigma[i];
+        }
+    }
+    Some(sigma.len() - cycles)
+}
+
+/// Número mínimo de transposições que levam `a` em `b`; coincide com a
+/// distância de ciclos.
+pub fn cayley_distance(a: &IntPermEncoding, b: &IntPermEncoding) -> Option<usize> {
+    cycle_distance(a, b)
+}
+
+#[cfg(test)]
+mod permutation_metrics_tests {
+    use rand::seq::SliceRandom;
+    use rand::thread_rng;
+    use super::*;
+
+    fn perm(values: &[usize]) -> IntPermEncoding {
+        IntPermEncoding(values.to_vec())
+    }
+
+    fn naive_kendall_tau(a: &IntPermEncoding, b: &IntPermEncoding) -> usize {
+        let sigma = relative(a, b).unwrap();
+        (0..sigma.len())
+            .flat_map(|i| (i + 1..sigma.len()).map(move |j| (i, j)))
+            .filter(|&(i, j)| sigma[i] > sigma[j])
+            .count()
+    }
+
+    #[test]
+    fn test_known_values() {
+        let a = perm(&[0, 1, 2, 3]);
+        let b = perm(&[1, 0, 3, 2]);
+        assert_eq!(kendall_tau(&a, &b), Some(2));
+        assert_eq!(cycle_distance(&a, &b), Some(2));
+
+        let b = perm(&[1, 2, 3, 0]);
+        assert_eq!(kendall_tau(&a, &b), Some(3));
+        assert_eq!(cycle_distance(&a, &b), Some(3));
+        assert_eq!(mismatch(&a, &b), Some(4));
+
+        let a = perm(&[2, 0, 1, 4, 3]);
+        let b = perm(&[0, 2, 1, 3, 4]);
+        assert_eq!(kendall_tau(&a, &b), Some(2));
+        assert_eq!(cayley_distance(&a, &b), Some(2));
+    }
+
+    #[test]
+    fn test_extremes() {
+        let n = 9;
+        let identity = IntPermEncoding((0..n).collect());
+        let reverse = IntPermEncoding((0..n).rev().collect());
+
+        assert_eq!(kendall_tau(&identity, &identity), Some(0));
+        assert_eq!(cycle_distance(&identity, &identity), Some(0));
+        assert_eq!(kendall_tau(&identity, &reverse), Some(n * (n - 1) / 2));
+        // a reversa é um produto de n/2 transposições disjuntas
+        assert_eq!(cycle_distance(&identity, &reverse), Some(n / 2));
+    }
+
+    #[test]
+    fn test_one_based_permutations() {
+        let a = perm(&[1, 2, 3, 4]);
+        let b = perm(&[4, 3, 2, 1]);
+        assert_eq!(kendall_tau(&a, &b), Some(6));
+        assert_eq!(cycle_distance(&a, &b), Some(2));
+    }
+
+    #[test]
+    fn test_kendall_tau_matches_naive() {
+        let mut rng = thread_rng();
+        for n in [0, 1, 2, 5, 17, 64] {
+            for _ in 0..20 {
+                let mut a: Vec<usize> = (0..n).collect();
+                let mut b = a.clone();
+                a.shuffle(&mut rng);
+                b.shuffle(&mut rng);
+                let (a, b) = (IntPermEncoding(a), IntPermEncoding(b));
+                assert_eq!(kendall_tau(&a, &b), Some(naive_kendall_tau(&a, &b)));
+                assert_eq!(kendall_tau(&a, &b), kendall_tau(&b, &a));
+                assert_eq!(cycle_distance(&a, &b), cycle_distance(&b, &a));
+            }
+        }
+    }
+
+    #[test]
+    fn test_metric_dispatch() {
+        let a = perm(&[0, 1, 2, 3]);
+        let b = perm(&[3, 2, 1, 0]);
+        assert_eq!(PermutationMetric::Mismatch.distance(&a, &b), Some(4));
+        assert_eq!(PermutationMetric::KendallTau.distance(&a, &b), Some(6));
+        assert_eq!(PermutationMetric::Cayley.distance(&a, &b), Some(2));
+    }
+
+    #[test]
+    fn test_invalid_inputs_are_rejected() {
+        let a = perm(&[0, 1, 2, 3]);
+        // lacuna no intervalo: o 3 de `a` não aparece em `b`
+        assert_eq!(kendall_tau(&a, &perm(&[0, 1, 2, 4])), None);
+        assert_eq!(cycle_distance(&a, &perm(&[0, 1, 2, 9])), None);
+        // elementos repetidos em qualquer um dos lados
+        assert_eq!(kendall_tau(&a, &perm(&[0, 1, 1, 3])), None);
+        assert_eq!(cayley_distance(&perm(&[0, 0, 2, 3]), &a), None);
+        // comprimentos diferentes
+        assert_eq!(mismatch(&a, &perm(&[0, 1, 2])), None);
+        assert_eq!(PermutationMetric::KendallTau.distance(&a, &perm(&[0, 1, 2])), None);
+        // mesmo comprimento mas intervalos diferentes
+        assert_eq!(kendall_tau(&a, &perm(&[1, 2, 3, 4])), None);
+    }
+}
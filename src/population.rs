@@ -1,66 +1,789 @@
 use rand::prelude::*;
-use rand::distributions::{Uniform};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use crate::permutation_metrics;
 
 // ======================================================================
 // == Encoding Marker Trait & Implementations
 // ======================================================================
 
-pub trait Encoding: Clone {}
+pub trait Encoding: Clone {
+    /// Distância genotípica, usada em nichos e especiação. Só é definida
+    /// entre indivíduos de mesma dimensão.
+    fn distance(&self, other: &Self) -> f64;
+}
+
+/// Encodings representados como uma sequência de genes.
+pub trait Genome: Encoding {
+    fn len(&self) -> usize;
 
-#[derive(Clone)]
-pub struct BinaryEncoding(pub Vec<bool>);
-impl Encoding for BinaryEncoding {}
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+// Com a feature `serde`, cada encoding é serializado como um array JSON de
+// genes: booleanos para BinaryEncoding e números para os demais.
+
+/// Genes armazenados empacotados em palavras de 64 bits; os bits além de `len`
+/// na última palavra são sempre zero. Serializado como `[true, false, ...]`;
+/// na leitura também aceita uma bitstring como `"0110"`.
+#[derive(Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(try_from = "BinaryRepr", into = "Vec<bool>"))]
+pub struct BinaryEncoding {
+    words: Vec<u64>,
+    len: usize,
+}
+impl Encoding for BinaryEncoding {
+    /// Distância de Hamming.
+    fn distance(&self, other: &Self) -> f64 {
+        self.hamming_distance(other) as f64
+    }
+}
+impl Genome for BinaryEncoding {
+    fn len(&self) -> usize {
+        self.len
+    }
+}
 impl BinaryEncoding {
-    pub fn to_bool_slice(&self) -> &[bool] {
-        &self.0
+    const WORD_BITS: usize = 64;
+
+    pub fn zeros(len: usize) -> Self {
+        BinaryEncoding { words: vec![0; len.div_ceil(Self::WORD_BITS)], len }
+    }
+
+    pub fn from_bools(bits: &[bool]) -> Self {
+        let mut encoding = Self::zeros(bits.len());
+        bits
+            .iter()
+            .enumerate()
+            .filter(|(_, bit)| **bit)
+            .for_each(|(i, _)| encoding.words[i / Self::WORD_BITS] |= 1 << (i % Self::WORD_BITS));
+        encoding
+    }
+
+    pub fn get(&self, index: usize) -> bool {
+        assert!(index < self.len, "index {} out of bounds for length {}", index, self.len);
+        self.words[index / Self::WORD_BITS] >> (index % Self::WORD_BITS) & 1 == 1
+    }
+
+    pub fn set(&mut self, index: usize, value: bool) {
+        assert!(index < self.len, "index {} out of bounds for length {}", index, self.len);
+        let mask = 1 << (index % Self::WORD_BITS);
+        let word = &mut self.words[index / Self::WORD_BITS];
+        if value { *word |= mask } else { *word &= !mask }
+    }
+
+    pub fn flip(&mut self, index: usize) {
+        assert!(index < self.len, "index {} out of bounds for length {}", index, self.len);
+        self.words[index / Self::WORD_BITS] ^= 1 << (index % Self::WORD_BITS);
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = bool> + '_ {
+        (0..self.len).map(|i| self.get(i))
+    }
+
+    pub fn count_ones(&self) -> usize {
+        self.words.iter().map(|word| word.count_ones() as usize).sum()
+    }
+
+    pub fn to_bools(&self) -> Vec<bool> {
+        self.iter().collect()
+    }
+
+    pub fn words(&self) -> &[u64] {
+        &self.words
+    }
+
+    pub fn hamming_distance(&self, other: &BinaryEncoding) -> usize {
+        assert_eq!(self.len, other.len, "hamming distance between encodings of different lengths");
+        self.words
+            .iter()
+            .zip(other.words.iter())
+            .map(|(a, b)| (a ^ b).count_ones() as usize)
+            .sum()
+    }
+
+    /// Crossover uniforme palavra a palavra: cada bit do filho vem de `self`
+    /// ou de `other` com probabilidade 1/2.
+    pub fn uniform_crossover<R: Rng + ?Sized>(&self, other: &BinaryEncoding, rng: &mut R) -> BinaryEncoding {
+        assert_eq!(self.len, other.len, "crossover between encodings of different lengths");
+        let mut child = BinaryEncoding {
+            words: self.words
+                .iter()
+                .zip(other.words.iter())
+                .map(|(a, b)| {
+                    let mask: u64 = rng.r#gen();
+                    (a & mask) | (b & !mask)
+                })
+                .collect(),
+            len: self.len,
+        };
+        child.clear_unused_bits();
+        child
+    }
+
+    fn clear_unused_bits(&mut self) {
+        let used = self.len % Self::WORD_BITS;
+        if used != 0 && let Some(last) = self.words.last_mut() {
+            *last &= (1 << used) - 1;
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum ParseBitstringError {
+    InvalidCharacter { position: usize, character: char },
+}
+
+impl std::fmt::Display for ParseBitstringError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ParseBitstringError::InvalidCharacter { position, character } =>
+                write!(f, "invalid character {:?} at position {} in bitstring", character, position),
+        }
+    }
+}
+
+impl std::error::Error for ParseBitstringError {}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct WidthOverflowError {
+    pub width: usize,
+}
+
+impl std::fmt::Display for WidthOverflowError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} bits do not fit in a u64", self.width)
     }
 }
 
-#[derive(Clone)]
+impl std::error::Error for WidthOverflowError {}
+
+impl BinaryEncoding {
+    /// Lê uma string de '0' e '1' (o primeiro caractere é o gene 0). Os
+    /// separadores '_' e espaços em branco são ignorados.
+    pub fn from_bitstring(bitstring: &str) -> Result<Self, ParseBitstringError> {
+        let bits: Result<Vec<bool>, ParseBitstringError> = bitstring
+            .chars()
+            .enumerate()
+            .filter(|(_, character)| *character != '_' && !character.is_whitespace())
+            .map(|(position, character)| match character {
+                '0' => Ok(false),
+                '1' => Ok(true),
+                _ => Err(ParseBitstringError::InvalidCharacter { position, character }),
+            })
+            .collect();
+        bits.map(|bits| BinaryEncoding::from_bools(&bits))
+    }
+
+    pub fn to_bitstring(&self) -> String {
+        self.iter().map(|bit| if bit { '1' } else { '0' }).collect()
+    }
+
+    /// Representa os `width` bits menos significativos de `value`, com o bit
+    /// mais significativo no gene 0.
+    pub fn from_u64(value: u64, width: usize) -> Self {
+        let mut encoding = BinaryEncoding::zeros(width);
+        (0..width.min(64))
+            .filter(|shift| value >> shift & 1 == 1)
+            .for_each(|shift| encoding.set(width - 1 - shift, true));
+        encoding
+    }
+}
+
+impl TryFrom<&BinaryEncoding> for u64 {
+    type Error = WidthOverflowError;
+
+    fn try_from(encoding: &BinaryEncoding) -> Result<Self, Self::Error> {
+        if encoding.len() > 64
+        { return Err(WidthOverflowError { width: encoding.len() }) }
+
+        Ok(encoding.iter().fold(0u64, |value, bit| (value << 1) | bit as u64))
+    }
+}
+
+/// Formas aceitas na desserialização: array de booleanos ou bitstring.
+#[cfg(feature = "serde")]
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum BinaryRepr {
+    Bools(Vec<bool>),
+    Bitstring(String),
+}
+
+#[cfg(feature = "serde")]
+impl TryFrom<BinaryRepr> for BinaryEncoding {
+    type Error = ParseBitstringError;
+
+    fn try_from(repr: BinaryRepr) -> Result<Self, Self::Error> {
+        match repr {
+            BinaryRepr::Bools(bits) => Ok(BinaryEncoding::from_bools(&bits)),
+            BinaryRepr::Bitstring(bitstring) => BinaryEncoding::from_bitstring(&bitstring),
+        }
+    }
+}
+
+impl From<Vec<bool>> for BinaryEncoding {
+    fn from(bits: Vec<bool>) -> Self {
+        BinaryEncoding::from_bools(&bits)
+    }
+}
+
+impl From<BinaryEncoding> for Vec<bool> {
+    fn from(encoding: BinaryEncoding) -> Self {
+        encoding.to_bools()
+    }
+}
+
+impl std::fmt::Debug for BinaryEncoding {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("BinaryEncoding").field(&self.to_bools()).finish()
+    }
+}
+
+/// Serializado como `[2, 0, 1, ...]`.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct IntPermEncoding(pub Vec<usize>);
-impl Encoding for IntPermEncoding {}
+impl Encoding for IntPermEncoding {
+    /// Segundo `permutation_metrics::default_metric()`; por padrão, número de
+    /// posições com elementos diferentes.
+    fn distance(&self, other: &Self) -> f64 {
+        permutation_metrics::default_metric()
+            .distance(self, other)
+            .expect("distance between permutations of different elements") as f64
+    }
+}
+impl Genome for IntPermEncoding {
+    fn len(&self) -> usize {
+        self.0.len()
+    }
+}
 
-#[derive(Clone)]
+/// Serializado como `[7, 3, 10, ...]`.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct IntegerEncoding(pub Vec<u64>);
-impl Encoding for IntegerEncoding {}
+impl Encoding for IntegerEncoding {
+    /// Distância de Manhattan.
+    fn distance(&self, other: &Self) -> f64 {
+        assert_eq!(self.0.len(), other.0.len(), "distance between encodings of different lengths");
+        self.0.iter().zip(&other.0).map(|(a, b)| a.abs_diff(*b) as f64).sum()
+    }
+}
+impl Genome for IntegerEncoding {
+    fn len(&self) -> usize {
+        self.0.len()
+    }
+}
+
+/// Intervalo fechado `[lower, upper]` válido para os genes de um IntegerEncoding.
+/// Geradores, operadores e reparo consultam o mesmo domínio.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct IntegerDomain {
+    lower: u64,
+    upper: u64,
+}
+
+impl IntegerDomain {
+    pub fn new(lower: u64, upper: u64) -> Result<Self, PopGeneratorError> {
+        if lower > upper
+        { return Err(PopGeneratorError::InvalidBounds) }
+
+        Ok(IntegerDomain { lower, upper })
+    }
+
+    pub fn bounds(&self) -> (u64, u64) {
+        (self.lower, self.upper)
+    }
+
+    pub fn contains(&self, gene: u64) -> bool {
+        self.lower <= gene && gene <= self.upper
+    }
+
+    pub fn clamp(&self, gene: u64) -> u64 {
+        gene.clamp(self.lower, self.upper)
+    }
 
-#[derive(Clone)]
+    pub fn random_gene<R: Rng + ?Sized>(&self, rng: &mut R) -> u64 {
+        rng.gen_range(self.lower..=self.upper)
+    }
+
+    pub fn is_within_bounds(&self, individual: &IntegerEncoding) -> bool {
+        individual.0.iter().all(|&gene| self.contains(gene))
+    }
+
+    pub fn clamp_to_bounds(&self, individual: &mut IntegerEncoding) {
+        individual.0.iter_mut().for_each(|gene| *gene = self.clamp(*gene));
+    }
+}
+
+/// Limites `(lower, upper)` dos genes de encodings Integer e Real: um par para
+/// todas as dimensões ou um par por dimensão. Serializado como `[0, 10]` ou
+/// `[[0, 10], [5, 7], ...]`.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(untagged))]
+pub enum Bounds<T> {
+    Uniform((T, T)),
+    PerDimension(Vec<(T, T)>),
+}
+
+impl<T> From<(T, T)> for Bounds<T> {
+    fn from(value: (T, T)) -> Self {
+        Bounds::Uniform(value)
+    }
+}
+
+impl<T> From<Vec<(T, T)>> for Bounds<T> {
+    fn from(value: Vec<(T, T)>) -> Self {
+        Bounds::PerDimension(value)
+    }
+}
+
+impl<T: Copy + PartialOrd> Bounds<T> {
+    /// Os pares dados, sem expandir o limite uniforme.
+    pub fn pairs(&self) -> &[(T, T)] {
+        match self {
+            Bounds::Uniform(pair) => std::slice::from_ref(pair),
+            Bounds::PerDimension(pairs) => pairs,
+        }
+    }
+
+    /// Um par por dimensão; erro se os limites por dimensão não têm `dim` pares.
+    pub fn expand(&self, dim: usize) -> Result<Vec<(T, T)>, PopGeneratorError> {
+        match self {
+            Bounds::Uniform(pair) => Ok(vec![*pair; dim]),
+            Bounds::PerDimension(pairs) if pairs.len() == dim => Ok(pairs.clone()),
+            Bounds::PerDimension(pairs) => Err(PopGeneratorError::BoundsDimensionMismatch { expected: dim, found: pairs.len() }),
+        }
+    }
+
+    /// `None` se os limites são por dimensão e `index` passa do último par.
+    pub fn get(&self, index: usize) -> Option<(T, T)> {
+        match self {
+            Bounds::Uniform(pair) => Some(*pair),
+            Bounds::PerDimension(pairs) => pairs.get(index).copied(),
+        }
+    }
+
+    /// Reparo: leva cada gene para dentro dos limites da sua dimensão. Erro,
+    /// sem alterar os genes, se os limites por dimensão não têm um par por
+    /// gene.
+    pub fn clamp_genes(&self, genes: &mut [T]) -> Result<(), PopGeneratorError> {
+        if let Bounds::PerDimension(pairs) = self && pairs.len() != genes.len()
+        { return Err(PopGeneratorError::BoundsDimensionMismatch { expected: genes.len(), found: pairs.len() }) }
+
+        for (index, gene) in genes.iter_mut().enumerate() {
+            let (lower, upper) = self.get(index).expect("one pair per gene");
+            if *gene < lower { *gene = lower } else if *gene > upper { *gene = upper }
+        }
+        Ok(())
+    }
+}
+
+/// Limites reais finitos com `lower <= upper`, necessários para amostrar em
+/// `[lower, upper]`.
+fn check_real_bounds(pairs: &[(f64, f64)]) -> Result<(), PopGeneratorError> {
+    if pairs.iter().any(|&(lower, upper)| !lower.is_finite() || !upper.is_finite() || lower > upper)
+    { return Err(PopGeneratorError::InvalidBounds) }
+    Ok(())
+}
+
+/// Serializado como `[0.5, -1.25, ...]`.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct RealEncoding(pub Vec<f64>);
-impl Encoding for RealEncoding {}
+impl Encoding for RealEncoding {
+    /// Distância euclidiana.
+    fn distance(&self, other: &Self) -> f64 {
+        assert_eq!(self.0.len(), other.0.len(), "distance between encodings of different lengths");
+        self.0.iter().zip(&other.0).map(|(a, b)| (a - b).powi(2)).sum::<f64>().sqrt()
+    }
+}
+impl Genome for RealEncoding {
+    fn len(&self) -> usize {
+        self.0.len()
+    }
+}
+
+/// Genes categóricos: o gene `i` é um índice em `0..cardinalities[i]` do seu
+/// [`CategoricalDomain`]. Serializado como `[2, 0, 1, ...]`.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct CategoricalEncoding(pub Vec<u32>);
+impl Encoding for CategoricalEncoding {
+    /// Número de posições diferentes (as categorias não têm ordem).
+    fn distance(&self, other: &Self) -> f64 {
+        assert_eq!(self.0.len(), other.0.len(), "distance between encodings of different lengths");
+        self.0.iter().zip(&other.0).filter(|(a, b)| a != b).count() as f64
+    }
+}
+impl Genome for CategoricalEncoding {
+    fn len(&self) -> usize {
+        self.0.len()
+    }
+}
+
+/// Tamanho do alfabeto de cada gene de um CategoricalEncoding.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CategoricalDomain {
+    cardinalities: Vec<u32>,
+}
+
+impl CategoricalDomain {
+    pub fn new(cardinalities: Vec<u32>) -> Result<Self, PopGeneratorError> {
+        if let Some(gene) = cardinalities.iter().position(|&cardinality| cardinality == 0)
+        { return Err(PopGeneratorError::EmptyAlphabet { gene }) }
+
+        Ok(CategoricalDomain { cardinalities })
+    }
+
+    pub fn cardinalities(&self) -> &[u32] {
+        &self.cardinalities
+    }
+
+    pub fn dim(&self) -> usize {
+        self.cardinalities.len()
+    }
+
+    pub fn contains(&self, individual: &CategoricalEncoding) -> bool {
+        individual.0.len() == self.dim()
+            && individual.0.iter().zip(&self.cardinalities).all(|(gene, cardinality)| gene < cardinality)
+    }
+
+    pub fn random_individual<R: Rng + ?Sized>(&self, rng: &mut R) -> CategoricalEncoding {
+        CategoricalEncoding(self.cardinalities.iter().map(|&cardinality| rng.gen_range(0..cardinality)).collect())
+    }
+
+    /// Mutação por reinício aleatório: cada gene é sorteado de novo no seu
+    /// alfabeto com probabilidade `rate`.
+    pub fn random_reset<R: Rng + ?Sized>(&self, individual: &mut CategoricalEncoding, rate: f64, rng: &mut R) {
+        let rate = rate.clamp(0.0, 1.0);
+        for (gene, &cardinality) in individual.0.iter_mut().zip(&self.cardinalities) {
+            if rng.gen_bool(rate)
+            { *gene = rng.gen_range(0..cardinality) }
+        }
+    }
+}
 
 
 // ======================================================================
 // == Population struct, PopGenerator Trait & Implementations
 // ======================================================================
 
+/// Serializada como a sequência de indivíduos, ex. `[[true, false], [false, false]]`.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Population<E: Encoding>(pub Vec<E>);
 impl<E: Encoding> Population<E> {
     pub fn get_individuals(&self) -> &[E] {
         &self.0
     }
+
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Amostra `n` indivíduos distintos (sem reposição), em ordem aleatória.
+    pub fn sample<R: Rng + ?Sized>(&self, n: usize, rng: &mut R) -> Result<Population<E>, SampleSizeError> {
+        if n > self.len()
+        { return Err(SampleSizeError { requested: n, available: self.len() }) }
+
+        Ok(Population(rand::seq::index::sample(rng, self.len(), n).into_iter().map(|index| self.0[index].clone()).collect()))
+    }
+
+    /// Divide em `[0, index)` e `[index, len)`, preservando a ordem.
+    /// Entra em pânico se `index > len`.
+    pub fn split_at(mut self, index: usize) -> (Population<E>, Population<E>) {
+        let tail = self.0.split_off(index);
+        (self, Population(tail))
+    }
+
+    /// Embaralha e divide em `k` partes cujos tamanhos diferem em no máximo 1
+    /// (as primeiras `len % k` partes têm um indivíduo a mais). Entra em
+    /// pânico se `k == 0`.
+    pub fn partition<R: Rng + ?Sized>(mut self, k: usize, rng: &mut R) -> Vec<Population<E>> {
+        assert!(k > 0, "cannot partition a population into 0 parts");
+        self.0.shuffle(rng);
+
+        let (base, extra) = (self.len() / k, self.len() % k);
+        let mut individuals = self.0.into_iter();
+        (0..k)
+            .map(|part| Population(individuals.by_ref().take(base + usize::from(part < extra)).collect()))
+            .collect()
+    }
+
+    /// Concatena as populações na ordem dada.
+    pub fn merge(parts: Vec<Population<E>>) -> Population<E> {
+        Population(parts.into_iter().flat_map(|part| part.0).collect())
+    }
+}
+
+impl Population<BinaryEncoding> {
+    /// Quantos indivíduos têm cada gene ligado (popcount por coluna), lendo
+    /// as palavras empacotadas. Os indivíduos devem ter o mesmo comprimento.
+    pub fn column_ones(&self) -> Vec<usize> {
+        let Some(first) = self.0.first() else { return Vec::new() };
+        let mut counts = vec![0; first.len()];
+        for individual in &self.0 {
+            assert_eq!(individual.len(), counts.len(), "column counts over encodings of different lengths");
+            for (w, &word) in individual.words().iter().enumerate() {
+                let mut bits = word;
+                while bits != 0 {
+                    counts[w * BinaryEncoding::WORD_BITS + bits.trailing_zeros() as usize] += 1;
+                    bits &= bits - 1;
+                }
+            }
+        }
+        counts
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub struct SampleSizeError {
+    pub requested: usize,
+    pub available: usize,
+}
+
+impl std::fmt::Display for SampleSizeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "cannot sample {} individuals from a population of {}", self.requested, self.available)
+    }
+}
+
+impl std::error::Error for SampleSizeError {}
+
+/// População real armazenada em um único buffer contíguo de `pop_size * dim`
+/// genes, um indivíduo por linha.
+#[derive(Clone, Debug, PartialEq)]
+pub struct MatrixPopulation {
+    data: Vec<f64>,
+    dim: usize,
+    /// Guardado à parte: com `dim == 0` o buffer é vazio qualquer que seja o
+    /// número de indivíduos.
+    pop_size: usize,
+}
+
+impl MatrixPopulation {
+    pub fn zeros(pop_size: usize, dim: usize) -> Self {
+        MatrixPopulation { data: vec![0.0; pop_size * dim], dim, pop_size }
+    }
+
+    /// Retorna `None` se os indivíduos não tiverem todos a mesma dimensão.
+    pub fn from_population(pop: &Population<RealEncoding>) -> Option<Self> {
+        let individuals = pop.get_individuals();
+        let dim = individuals.first().map_or(0, |individual| individual.len());
+        if individuals.iter().any(|individual| individual.len() != dim)
+        { return None }
+
+        let mut data = Vec::with_capacity(individuals.len() * dim);
+        individuals.iter().for_each(|individual| data.extend_from_slice(&individual.0));
+        Some(MatrixPopulation { data, dim, pop_size: individuals.len() })
+    }
+
+    pub fn to_population(&self) -> Population<RealEncoding> {
+        Population(self.rows().map(|row| RealEncoding(row.to_vec())).collect())
+    }
+
+    pub fn dim(&self) -> usize {
+        self.dim
+    }
+
+    pub fn pop_size(&self) -> usize {
+        self.pop_size
+    }
+
+    pub fn row(&self, index: usize) -> &[f64] {
+        &self.data[index * self.dim..(index + 1) * self.dim]
+    }
+
+    pub fn row_mut(&mut self, index: usize) -> &mut [f64] {
+        &mut self.data[index * self.dim..(index + 1) * self.dim]
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[f64]> {
+        (0..self.pop_size).map(move |index| self.row(index))
+    }
+
+    pub fn rows_mut(&mut self) -> impl Iterator<Item = &mut [f64]> {
+        let dim = self.dim;
+        let mut rest = self.data.as_mut_slice();
+        (0..self.pop_size).map(move |_| {
+            let (row, tail) = std::mem::take(&mut rest).split_at_mut(dim);
+            rest = tail;
+            row
+        })
+    }
+
+    pub fn as_slice(&self) -> &[f64] {
+        &self.data
+    }
 }
 
 pub trait PopGenerator {
     type E: Encoding;
-    fn gen_pop(&self) -> Population<Self::E>;
+
+    /// Gera a população consumindo apenas `rng`, de modo que a mesma semente
+    /// (ex. um fluxo `StreamRole::Initialization` de
+    /// [`RngStreams`](crate::alg::substreams::RngStreams)) reproduz a mesma
+    /// população.
+    fn gen_pop_with<R: Rng + ?Sized>(&self, rng: &mut R) -> Population<Self::E>;
+
+    fn gen_pop(&self) -> Population<Self::E> {
+        self.gen_pop_with(&mut rand::thread_rng())
+    }
+
+    fn dimension(&self) -> usize;
+
+    /// `false` para geradores criados sem dimensão (ex.
+    /// `BinaryPopGenerator::sized`), que a recebem do objetivo.
+    fn has_dimension(&self) -> bool {
+        true
+    }
+
+    /// Fixa a dimensão de um gerador criado sem ela; nos demais, não faz
+    /// nada. Falha se alguma opção do gerador não cabe na dimensão recebida.
+    fn infer_dimension(&mut self, _dim: usize) -> Result<(), PopGeneratorError> {
+        Ok(())
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub enum PopGeneratorError {
+    SeedDimensionMismatch { index: usize, expected: usize, found: usize },
+    BoundsDimensionMismatch { expected: usize, found: usize },
+    NonSquareDistanceMatrix,
+    /// Matriz de distâncias sem cidades.
+    EmptyDistanceMatrix,
+    /// Cidade inicial fora de `0..num_cities`.
+    StartOutOfRange { start: usize, num_cities: usize },
+    InvalidBounds,
+    /// Densidade fora de [0, 1].
+    InvalidDensity,
+    DensityDimensionMismatch { expected: usize, found: usize },
+    /// Ruído do vizinho mais próximo fora de [0, 1].
+    InvalidNoise,
+    /// O valor fixado na primeira posição não pertence à permutação.
+    FixedValueOutOfRange { value: usize },
+    /// Gene categórico com alfabeto vazio.
+    EmptyAlphabet { gene: usize },
+}
+
+impl std::fmt::Display for PopGeneratorError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PopGeneratorError::SeedDimensionMismatch { index, expected, found } =>
+                write!(f, "seed {} has dim {} but the generator has dim {}", index, found, expected),
+            PopGeneratorError::BoundsDimensionMismatch { expected, found } =>
+                write!(f, "expected {} bound pairs but found {}", expected, found),
+            PopGeneratorError::NonSquareDistanceMatrix => write!(f, "distance matrix must be square"),
+            PopGeneratorError::EmptyDistanceMatrix => write!(f, "distance matrix has no cities"),
+            PopGeneratorError::StartOutOfRange { start, num_cities } =>
+                write!(f, "start city {} is outside 0..{}", start, num_cities),
+            PopGeneratorError::InvalidBounds => write!(f, "bounds must be finite with lower <= upper"),
+            PopGeneratorError::InvalidDensity => write!(f, "density must be within [0, 1]"),
+            PopGeneratorError::DensityDimensionMismatch { expected, found } =>
+                write!(f, "expected {} gene densities but found {}", expected, found),
+            PopGeneratorError::InvalidNoise => write!(f, "noise must be within [0, 1]"),
+            PopGeneratorError::FixedValueOutOfRange { value } => write!(f, "fixed first value {} is not in the permutation", value),
+            PopGeneratorError::EmptyAlphabet { gene } => write!(f, "gene {} has an empty alphabet", gene),
+        }
+    }
 }
 
+impl std::error::Error for PopGeneratorError {}
+
 // ============ BinaryPopGenerator ============ 
 
+/// Probabilidade de cada gene inicial valer 1.
+#[derive(Clone, Debug, PartialEq)]
+pub enum BinaryDensity {
+    Uniform(f64),
+    /// Uma probabilidade por gene, para inicialização heurística.
+    PerGene(Vec<f64>),
+}
+
 pub struct BinaryPopGenerator {
-    dim: usize,
+    dim: Option<usize>,
     pop_size: usize,
+    density: BinaryDensity,
+}
+
+impl BinaryPopGenerator {
+    /// Densidade padrão: 0.5.
+    pub fn new(dim: usize, pop_size: usize) -> Self {
+        BinaryPopGenerator { dim: Some(dim), pop_size, density: BinaryDensity::Uniform(0.5) }
+    }
+
+    /// Sem dimensão: ela é preenchida pelo `GeneticAlgorithm` a partir do
+    /// objetivo (ex. o número de variáveis de uma fórmula SAT).
+    pub fn sized(pop_size: usize) -> Self {
+        BinaryPopGenerator { dim: None, pop_size, density: BinaryDensity::Uniform(0.5) }
+    }
+
+    pub fn sized_with_density(pop_size: usize, density: f64) -> Result<Self, PopGeneratorError> {
+        if !(0.0..=1.0).contains(&density)
+        { return Err(PopGeneratorError::InvalidDensity) }
+
+        Ok(BinaryPopGenerator { dim: None, pop_size, density: BinaryDensity::Uniform(density) })
+    }
+
+    pub fn new_with_density(dim: usize, pop_size: usize, density: f64) -> Result<Self, PopGeneratorError> {
+        if !(0.0..=1.0).contains(&density)
+        { return Err(PopGeneratorError::InvalidDensity) }
+
+        Ok(BinaryPopGenerator { dim: Some(dim), pop_size, density: BinaryDensity::Uniform(density) })
+    }
+
+    pub fn new_with_gene_densities(dim: usize, pop_size: usize, densities: Vec<f64>) -> Result<Self, PopGeneratorError> {
+        if densities.len() != dim
+        { return Err(PopGeneratorError::DensityDimensionMismatch { expected: dim, found: densities.len() }) }
+        if densities.iter().any(|density| !(0.0..=1.0).contains(density))
+        { return Err(PopGeneratorError::InvalidDensity) }
+
+        Ok(BinaryPopGenerator { dim: Some(dim), pop_size, density: BinaryDensity::PerGene(densities) })
+    }
+
+    pub fn density(&self) -> &BinaryDensity {
+        &self.density
+    }
 }
 
 impl PopGenerator for BinaryPopGenerator {
     type E = BinaryEncoding;
-    fn gen_pop(&self) -> Population<BinaryEncoding> {
-        let mut rng = rand::thread_rng();
+
+    fn dimension(&self) -> usize {
+        self.dim.unwrap_or(0)
+    }
+
+    fn has_dimension(&self) -> bool {
+        self.dim.is_some()
+    }
+
+    fn infer_dimension(&mut self, dim: usize) -> Result<(), PopGeneratorError> {
+        self.dim.get_or_insert(dim);
+        Ok(())
+    }
+
+    /// Entra em pânico se o gerador foi criado com `sized` e ainda não
+    /// recebeu a dimensão.
+    fn gen_pop_with<R: Rng + ?Sized>(&self, rng: &mut R) -> Population<BinaryEncoding> {
+        let dim = self.dim.expect("generator has no dimension; pass it to GeneticAlgorithm or call infer_dimension first");
         Population(
             (0..self.pop_size)
                 .map(|_| {
-                    let binary_samples = (0..self.dim).map(|_| rng.gen_bool(0.5)).collect::<Vec<bool>>();
-                    BinaryEncoding(binary_samples)
+                    let binary_samples = match &self.density {
+                        BinaryDensity::Uniform(density) => (0..dim).map(|_| rng.gen_bool(*density)).collect::<Vec<bool>>(),
+                        BinaryDensity::PerGene(densities) => densities.iter().map(|density| rng.gen_bool(*density)).collect(),
+                    };
+                    BinaryEncoding::from(binary_samples)
                 })
                 .collect()
         )
@@ -69,21 +792,43 @@ impl PopGenerator for BinaryPopGenerator {
 
 // ============ IntegerPopGenerator ============
 pub struct IntegerPopGenerator {
-    dim: usize,
-    bounds: (u64, u64),
+    domains: Vec<IntegerDomain>,
     pop_size: usize,
 }
 
+impl IntegerPopGenerator {
+    pub fn new(dim: usize, domain: IntegerDomain, pop_size: usize) -> Self {
+        IntegerPopGenerator { domains: vec![domain; dim], pop_size }
+    }
+
+    /// Um domínio por dimensão a partir de `bounds`.
+    pub fn with_bounds(dim: usize, bounds: &Bounds<u64>, pop_size: usize) -> Result<Self, PopGeneratorError> {
+        let domains = bounds
+            .expand(dim)?
+            .into_iter()
+            .map(|(lower, upper)| IntegerDomain::new(lower, upper))
+            .collect::<Result<_, _>>()?;
+        Ok(IntegerPopGenerator { domains, pop_size })
+    }
+
+    /// Domínio do gene `gene`.
+    pub fn domain(&self, gene: usize) -> IntegerDomain {
+        self.domains[gene]
+    }
+}
+
 impl PopGenerator for IntegerPopGenerator {
     type E = IntegerEncoding;
-    fn gen_pop(&self) -> Population<IntegerEncoding> {
-        let (lower, upper) = self.bounds;
-        let mut rng = rand::thread_rng();
-        let uniform_dist = Uniform::from(lower..=upper);
+
+    fn dimension(&self) -> usize {
+        self.domains.len()
+    }
+
+    fn gen_pop_with<R: Rng + ?Sized>(&self, rng: &mut R) -> Population<IntegerEncoding> {
         Population(
             (0..self.pop_size)
                 .map(|_| {
-                    let int_samples = (0..self.dim).map(|_| uniform_dist.sample(&mut rng)).collect();
+                    let int_samples = self.domains.iter().map(|domain| domain.random_gene(rng)).collect();
                     IntegerEncoding(int_samples)
                 })
                 .collect()
@@ -93,47 +838,411 @@ impl PopGenerator for IntegerPopGenerator {
 
 // ============ IntPermPopGenerator ============
 pub struct IntPermPopGenerator {
-    dim: usize,
+    dim: Option<usize>,
     pop_size: usize,
+    base: usize,
+    fixed_first: Option<usize>,
+}
+
+impl IntPermPopGenerator {
+    pub fn new(dim: usize, pop_size: usize) -> Self {
+        IntPermPopGenerator { dim: Some(dim), pop_size, base: 0, fixed_first: None }
+    }
+
+    /// Sem dimensão, como `BinaryPopGenerator::sized`: ela vem do objetivo
+    /// (ex. o número de cidades de uma instância).
+    pub fn sized(pop_size: usize) -> Self {
+        IntPermPopGenerator { dim: None, pop_size, base: 0, fixed_first: None }
+    }
+
+    /// Genes em `base..base + dim` (ex. 1 para formatos 1-indexados) e,
+    /// opcionalmente, `fixed_first` na posição 0 de todo indivíduo (ex. a
+    /// cidade de partida de uma rota). Falha se o valor fixado não pertence à
+    /// permutação.
+    pub fn new_with_layout(dim: usize, pop_size: usize, base: usize, fixed_first: Option<usize>) -> Result<Self, PopGeneratorError> {
+        check_fixed_first(Some(dim), base, fixed_first)?;
+        Ok(IntPermPopGenerator { dim: Some(dim), pop_size, base, fixed_first })
+    }
+
+    /// Como `new_with_layout`, sem dimensão. Só o limite inferior do valor
+    /// fixado é conferido aqui; o superior, em `infer_dimension`.
+    pub fn sized_with_layout(pop_size: usize, base: usize, fixed_first: Option<usize>) -> Result<Self, PopGeneratorError> {
+        check_fixed_first(None, base, fixed_first)?;
+        Ok(IntPermPopGenerator { dim: None, pop_size, base, fixed_first })
+    }
+}
+
+/// O valor fixado deve pertencer a `base..base + dim`.
+fn check_fixed_first(dim: Option<usize>, base: usize, fixed_first: Option<usize>) -> Result<(), PopGeneratorError> {
+    let upper = dim.map_or(usize::MAX, |dim| base + dim);
+    match fixed_first {
+        Some(value) if !(base..upper).contains(&value) => Err(PopGeneratorError::FixedValueOutOfRange { value }),
+        _ => Ok(()),
+    }
+}
+
+impl IntPermEncoding {
+    /// Rotaciona a permutação até `value` ficar na posição 0, preservando a
+    /// ordem cíclica (a mesma rota, começando em `value`). Retorna `false`
+    /// se `value` não está na permutação.
+    pub fn rotate_to_front(&mut self, value: usize) -> bool {
+        match self.0.iter().position(|&element| element == value) {
+            Some(position) => { self.0.rotate_left(position); true },
+            None => false,
+        }
+    }
+}
+
+/// Fixa `value` na posição 0 dos indivíduos de qualquer gerador de
+/// permutações, via [`IntPermEncoding::rotate_to_front`]. Indivíduos que não
+/// contêm `value` ficam inalterados.
+pub struct FixedFirstPopGenerator<G: PopGenerator<E = IntPermEncoding>> {
+    inner: G,
+    value: usize,
+}
+
+impl<G: PopGenerator<E = IntPermEncoding>> FixedFirstPopGenerator<G> {
+    pub fn new(inner: G, value: usize) -> Self {
+        FixedFirstPopGenerator { inner, value }
+    }
+}
+
+impl<G: PopGenerator<E = IntPermEncoding>> PopGenerator for FixedFirstPopGenerator<G> {
+    type E = IntPermEncoding;
+
+    fn dimension(&self) -> usize {
+        self.inner.dimension()
+    }
+
+    fn has_dimension(&self) -> bool {
+        self.inner.has_dimension()
+    }
+
+    fn infer_dimension(&mut self, dim: usize) -> Result<(), PopGeneratorError> {
+        self.inner.infer_dimension(dim)
+    }
+
+    fn gen_pop_with<R: Rng + ?Sized>(&self, rng: &mut R) -> Population<IntPermEncoding> {
+        let mut pop = self.inner.gen_pop_with(rng);
+        pop.0.iter_mut().for_each(|individual| { individual.rotate_to_front(self.value); });
+        pop
+    }
+}
+
+impl PopGenerator for IntPermPopGenerator {
+    type E = IntPermEncoding;
+
+    fn dimension(&self) -> usize {
+        self.dim.unwrap_or(0)
+    }
+
+    fn has_dimension(&self) -> bool {
+        self.dim.is_some()
+    }
+
+    /// Falha se o valor fixado não cabe na dimensão recebida.
+    fn infer_dimension(&mut self, dim: usize) -> Result<(), PopGeneratorError> {
+        if self.dim.is_none() {
+            check_fixed_first(Some(dim), self.base, self.fixed_first)?;
+            self.dim = Some(dim);
+        }
+        Ok(())
+    }
+
+    /// Entra em pânico se o gerador foi criado com `sized` e ainda não
+    /// recebeu a dimensão.
+    fn gen_pop_with<R: Rng + ?Sized>(&self, rng: &mut R) -> Population<IntPermEncoding> {
+        let dim = self.dim.expect("generator has no dimension; pass it to GeneticAlgorithm or call infer_dimension first");
+        Population(
+            (0..self.pop_size)
+                .map(|_| {
+                    let mut range = (self.base..self.base + dim).collect::<Vec<usize>>();
+                    range.shuffle(rng);
+                    let mut individual = IntPermEncoding(range);
+                    // o valor fixado foi conferido contra `base..base + dim`
+                    if let Some(value) = self.fixed_first {
+                        individual.rotate_to_front(value);
+                    }
+                    individual
+                })
+                .collect()
+        )
+    }
+}
+
+// ============ RealPopGenerator ============
+pub struct RealPopGenerator {
+    bounds: Vec<(f64, f64)>,
+    pop_size: usize,
+}
+
+impl RealPopGenerator {
+    pub fn new(dim: usize, bounds: (f64, f64), pop_size: usize) -> Self {
+        RealPopGenerator { bounds: vec![bounds; dim], pop_size }
+    }
+
+    /// Falha se algum limite não é finito ou tem `lower > upper`.
+    pub fn with_bounds(dim: usize, bounds: &Bounds<f64>, pop_size: usize) -> Result<Self, PopGeneratorError> {
+        let bounds = bounds.expand(dim)?;
+        check_real_bounds(&bounds)?;
+        Ok(RealPopGenerator { bounds, pop_size })
+    }
+}
+
+impl PopGenerator for RealPopGenerator {
+    type E = RealEncoding;
+
+    fn dimension(&self) -> usize {
+        self.bounds.len()
+    }
+
+    fn gen_pop_with<R: Rng + ?Sized>(&self, rng: &mut R) -> Population<RealEncoding> {
+        Population(
+            (0..self.pop_size)
+                .map(|_| {
+                    let real_samples = self.bounds.iter().map(|&(lower, upper)| rng.gen_range(lower..=upper)).collect();
+                    RealEncoding(real_samples)
+                })
+                .collect()
+        )
+    }
+}
+
+// ============ CategoricalPopGenerator ============
+pub struct CategoricalPopGenerator {
+    domain: CategoricalDomain,
+    pop_size: usize,
+}
+
+impl CategoricalPopGenerator {
+    pub fn new(domain: CategoricalDomain, pop_size: usize) -> Self {
+        CategoricalPopGenerator { domain, pop_size }
+    }
+
+    pub fn domain(&self) -> &CategoricalDomain {
+        &self.domain
+    }
+}
+
+impl PopGenerator for CategoricalPopGenerator {
+    type E = CategoricalEncoding;
+
+    fn dimension(&self) -> usize {
+        self.domain.dim()
+    }
+
+    fn gen_pop_with<R: Rng + ?Sized>(&self, rng: &mut R) -> Population<CategoricalEncoding> {
+        Population((0..self.pop_size).map(|_| self.domain.random_individual(rng)).collect())
+    }
+}
+
+// ============ LhsRealPopGenerator ============
+
+/// Amostragem por hipercubo latino: o intervalo de cada dimensão é dividido em
+/// `pop_size` estratos e cada estrato é amostrado exatamente uma vez.
+pub struct LhsRealPopGenerator {
+    bounds: Vec<(f64, f64)>,
+    pop_size: usize,
+}
+
+impl LhsRealPopGenerator {
+    pub fn new(dim: usize, bounds: (f64, f64), pop_size: usize) -> Self {
+        LhsRealPopGenerator { bounds: vec![bounds; dim], pop_size }
+    }
+
+    pub fn with_dim_bounds(dim: usize, bounds: Vec<(f64, f64)>, pop_size: usize) -> Result<Self, PopGeneratorError> {
+        if bounds.len() != dim
+        { return Err(PopGeneratorError::BoundsDimensionMismatch { expected: dim, found: bounds.len() }) }
+        check_real_bounds(&bounds)?;
+
+        Ok(LhsRealPopGenerator { bounds, pop_size })
+    }
+}
+
+impl PopGenerator for LhsRealPopGenerator {
+    type E = RealEncoding;
+
+    fn dimension(&self) -> usize {
+        self.bounds.len()
+    }
+
+    fn gen_pop_with<R: Rng + ?Sized>(&self, rng: &mut R) -> Population<RealEncoding> {
+        let mut individuals = vec![Vec::with_capacity(self.bounds.len()); self.pop_size];
+        let strata_size = 1.0 / self.pop_size as f64;
+
+        for &(lower, upper) in &self.bounds {
+            let mut column: Vec<f64> = (0..self.pop_size)
+                .map(|stratum| {
+                    let offset = (stratum as f64 + rng.r#gen::<f64>()) * strata_size;
+                    lower + offset * (upper - lower)
+                })
+                .collect();
+            column.shuffle(rng);
+
+            individuals
+                .iter_mut()
+                .zip(column)
+                .for_each(|(individual, gene)| individual.push(gene));
+        }
+
+        Population(individuals.into_iter().map(RealEncoding).collect())
+    }
+}
+
+// ============ NearestNeighborPopGenerator ============
+
+/// Constrói rotas pela heurística do vizinho mais próximo a partir de uma cidade
+/// inicial aleatória. Com probabilidade `noise`, o próximo passo é escolhido ao
+/// acaso entre os `k` vizinhos mais próximos, para que a população não seja
+/// composta de rotas idênticas.
+pub struct NearestNeighborPopGenerator {
+    distances: Vec<Vec<f64>>,
+    pop_size: usize,
+    noise: f64,
+    k: usize,
+}
+
+impl NearestNeighborPopGenerator {
+    pub fn new(distances: Vec<Vec<f64>>, pop_size: usize) -> Result<Self, PopGeneratorError> {
+        let num_cities = distances.len();
+        if num_cities == 0
+        { return Err(PopGeneratorError::EmptyDistanceMatrix) }
+        if distances.iter().any(|row| row.len() != num_cities)
+        { return Err(PopGeneratorError::NonSquareDistanceMatrix) }
+
+        Ok(NearestNeighborPopGenerator { distances, pop_size, noise: 0.0, k: 1 })
+    }
+
+    /// Falha se `noise` está fora de [0, 1]; `k` é ao menos 1.
+    pub fn with_noise(mut self, noise: f64, k: usize) -> Result<Self, PopGeneratorError> {
+        if !(0.0..=1.0).contains(&noise)
+        { return Err(PopGeneratorError::InvalidNoise) }
+
+        self.noise = noise;
+        self.k = k.max(1);
+        Ok(self)
+    }
+
+    pub fn construct_tour<R: Rng + ?Sized>(&self, start: usize, rng: &mut R) -> Result<IntPermEncoding, PopGeneratorError> {
+        let num_cities = self.distances.len();
+        if start >= num_cities
+        { return Err(PopGeneratorError::StartOutOfRange { start, num_cities }) }
+
+        Ok(self.tour_from(start, rng))
+    }
+
+    fn tour_from<R: Rng + ?Sized>(&self, start: usize, rng: &mut R) -> IntPermEncoding {
+        let num_cities = self.distances.len();
+        let mut visited = vec![false; num_cities];
+        let mut tour = Vec::with_capacity(num_cities);
+        let mut current = start;
+        visited[current] = true;
+        tour.push(current);
+
+        while tour.len() < num_cities {
+            let mut candidates: Vec<usize> = (0..num_cities).filter(|&city| !visited[city]).collect();
+            let distances = &self.distances[current];
+            candidates.sort_by(|&a, &b| distances[a].total_cmp(&distances[b]));
+
+            current = if self.noise > 0.0 && rng.gen_bool(self.noise) {
+                candidates[rng.gen_range(0..self.k.min(candidates.len()))]
+            } else {
+                candidates[0]
+            };
+            visited[current] = true;
+            tour.push(current);
+        }
+
+        IntPermEncoding(tour)
+    }
 }
 
-impl PopGenerator for IntPermPopGenerator {
+impl PopGenerator for NearestNeighborPopGenerator {
     type E = IntPermEncoding;
-    fn gen_pop(&self) -> Population<IntPermEncoding> {
-        let mut rng = rand::thread_rng();
+
+    fn dimension(&self) -> usize {
+        self.distances.len()
+    }
+
+    fn gen_pop_with<R: Rng + ?Sized>(&self, rng: &mut R) -> Population<IntPermEncoding> {
+        let num_cities = self.distances.len();
         Population(
             (0..self.pop_size)
                 .map(|_| {
-                    let mut range = (0..self.dim).collect::<Vec<usize>>();
-                    range.shuffle(&mut rng);
-                    IntPermEncoding(range)
+                    let start = rng.gen_range(0..num_cities);
+                    self.tour_from(start, rng)
                 })
                 .collect()
         )
     }
 }
 
-// ============ RealPopGenerator ============
-pub struct RealPopGenerator {
-    dim: usize,
-    bounds: (f64, f64),
-    pop_size: usize,
+// ============ SeededPopGenerator ============
+
+/// Decora outro gerador: os primeiros indivíduos da população são as sementes
+/// fornecidas e o restante vem do gerador interno. Sementes além do tamanho da
+/// população são descartadas.
+pub struct SeededPopGenerator<G: PopGenerator> {
+    inner: G,
+    seeds: Vec<G::E>,
 }
 
-impl PopGenerator for RealPopGenerator {
-    type E = RealEncoding;
-    fn gen_pop(&self) -> Population<RealEncoding> {
-        let (lower, upper) = self.bounds;
-        let uniform_dist = Uniform::from(lower..=upper);
-        let mut rng = rand::thread_rng();
-        Population(
-            (0..self.pop_size)
-                .map(|_| {
-                    let real_samples = (0..self.dim).map(|_| uniform_dist.sample(&mut rng)).collect();
-                    RealEncoding(real_samples)
-                })
-                .collect()
-        )
+impl<G: PopGenerator> SeededPopGenerator<G> {
+    /// Com um gerador ainda sem dimensão (ex. `BinaryPopGenerator::sized`), o
+    /// comprimento das sementes só é conferido em `infer_dimension`.
+    pub fn new(inner: G, seeds: Vec<G::E>) -> Result<Self, PopGeneratorError>
+    where
+        G::E: Genome,
+    {
+        let generator = SeededPopGenerator { inner, seeds };
+        if generator.inner.has_dimension() {
+            generator.check_seeds()?;
+        }
+        Ok(generator)
+    }
+
+    fn check_seeds(&self) -> Result<(), PopGeneratorError>
+    where
+        G::E: Genome,
+    {
+        let expected = self.inner.dimension();
+        match self.seeds.iter().enumerate().find(|(_, seed)| seed.len() != expected) {
+            Some((index, seed)) => Err(PopGeneratorError::SeedDimensionMismatch { index, expected, found: seed.len() }),
+            None => Ok(()),
+        }
+    }
+
+    pub fn get_seeds(&self) -> &[G::E] {
+        &self.seeds
+    }
+}
+
+impl<G: PopGenerator> PopGenerator for SeededPopGenerator<G>
+where
+    G::E: Genome,
+{
+    type E = G::E;
+
+    fn dimension(&self) -> usize {
+        self.inner.dimension()
+    }
+
+    fn has_dimension(&self) -> bool {
+        self.inner.has_dimension()
+    }
+
+    fn infer_dimension(&mut self, dim: usize) -> Result<(), PopGeneratorError> {
+        self.inner.infer_dimension(dim)?;
+        self.check_seeds()
+    }
+
+    fn gen_pop_with<R: Rng + ?Sized>(&self, rng: &mut R) -> Population<G::E> {
+        let mut individuals = self.inner.gen_pop_with(rng).0;
+        individuals
+            .iter_mut()
+            .zip(self.seeds.iter())
+            .for_each(|(individual, seed)| *individual = seed.clone());
+        Population(individuals)
     }
 }
 
@@ -145,22 +1254,52 @@ mod population_tests {
     fn test_generate_binary_population() {
         let dim = 17;
         let pop_size = 120;
-        let pop_generator = BinaryPopGenerator { dim, pop_size };
+        let pop_generator = BinaryPopGenerator::new(dim, pop_size);
         let population = pop_generator.gen_pop();
         let individuals = population.0;
         assert_eq!(individuals.len(), pop_size);
 
         for individual in individuals {
-            assert_eq!(individual.0.len(), dim);
+            assert_eq!(individual.len(), dim);
         }
     }
 
+    #[test]
+    fn test_generate_binary_population_with_density() {
+        let pop = BinaryPopGenerator::new_with_density(100, 200, 0.05).unwrap().gen_pop();
+        let ones: usize = pop.get_individuals().iter().map(BinaryEncoding::count_ones).sum();
+        // 20000 genes com p = 0.05: média 1000, desvio padrão ~31
+        assert!((850..=1150).contains(&ones));
+
+        let dense = BinaryPopGenerator::new_with_density(10, 5, 1.0).unwrap().gen_pop();
+        assert!(dense.get_individuals().iter().all(|ind| ind.count_ones() == 10));
+
+        assert_eq!(BinaryPopGenerator::new_with_density(10, 5, 1.5).err(), Some(PopGeneratorError::InvalidDensity));
+        assert_eq!(BinaryPopGenerator::new_with_density(10, 5, f64::NAN).err(), Some(PopGeneratorError::InvalidDensity));
+    }
+
+    #[test]
+    fn test_generate_binary_population_with_gene_densities() {
+        let pop = BinaryPopGenerator::new_with_gene_densities(3, 50, vec![0.0, 1.0, 0.5]).unwrap().gen_pop();
+        assert_eq!(pop.column_ones()[..2], [0, 50]);
+
+        assert_eq!(
+            BinaryPopGenerator::new_with_gene_densities(3, 50, vec![0.5; 2]).err(),
+            Some(PopGeneratorError::DensityDimensionMismatch { expected: 3, found: 2 })
+        );
+        assert_eq!(
+            BinaryPopGenerator::new_with_gene_densities(2, 50, vec![0.5, -0.1]).err(),
+            Some(PopGeneratorError::InvalidDensity)
+        );
+    }
+
     #[test]
     fn test_generate_integer_population() {
         let dim = 15;
         let bounds = (1, 10);
         let pop_size = 25;
-        let pop_generator = IntegerPopGenerator { dim, bounds, pop_size };
+        let domain = IntegerDomain::new(bounds.0, bounds.1).unwrap();
+        let pop_generator = IntegerPopGenerator::new(dim, domain, pop_size);
         let population = pop_generator.gen_pop();
         let individuals = population.0;
         assert_eq!(individuals.len(), pop_size);
@@ -178,7 +1317,7 @@ mod population_tests {
         let dim = 12;
         let bounds = (53.2, 105.1);
         let pop_size = 110;
-        let pop_generator = RealPopGenerator { dim, bounds, pop_size };
+        let pop_generator = RealPopGenerator::new(dim, bounds, pop_size);
         let population = pop_generator.gen_pop();
         let individuals = population.0;
         assert_eq!(individuals.len(), pop_size);
@@ -191,11 +1330,107 @@ mod population_tests {
         }
     }
 
+    #[test]
+    fn test_generate_populations_with_per_dimension_bounds() {
+        let bounds = Bounds::from(vec![(0, 1), (10, 10), (100, 200)]);
+        let pop = IntegerPopGenerator::with_bounds(3, &bounds, 100).unwrap().gen_pop();
+        for individual in pop.get_individuals() {
+            assert!(individual.0.iter().enumerate().all(|(i, gene)| {
+                let (lower, upper) = bounds.get(i).unwrap();
+                (lower..=upper).contains(gene)
+            }));
+        }
+
+        let bounds = Bounds::from(vec![(-1.0, 0.0), (5.0, 5.5)]);
+        let generator = RealPopGenerator::with_bounds(2, &bounds, 100).unwrap();
+        assert_eq!(generator.dimension(), 2);
+        for individual in generator.gen_pop().get_individuals() {
+            assert!((-1.0..=0.0).contains(&individual.0[0]) && (5.0..=5.5).contains(&individual.0[1]));
+        }
+
+        assert_eq!(
+            RealPopGenerator::with_bounds(3, &bounds, 10).err(),
+            Some(PopGeneratorError::BoundsDimensionMismatch { expected: 3, found: 2 })
+        );
+        assert_eq!(
+            IntegerPopGenerator::with_bounds(2, &Bounds::from(vec![(0, 1), (4, 2)]), 10).err(),
+            Some(PopGeneratorError::InvalidBounds)
+        );
+        assert_eq!(IntegerPopGenerator::with_bounds(4, &Bounds::from((2, 3)), 10).unwrap().domain(3).bounds(), (2, 3));
+    }
+
+    #[test]
+    fn test_real_generators_reject_invalid_bounds() {
+        for pair in [(1.0, 0.0), (f64::NAN, 1.0), (0.0, f64::INFINITY), (f64::NEG_INFINITY, 0.0)] {
+            assert_eq!(RealPopGenerator::with_bounds(2, &Bounds::from(pair), 10).err(), Some(PopGeneratorError::InvalidBounds));
+            assert_eq!(
+                RealPopGenerator::with_bounds(2, &Bounds::from(vec![(0.0, 1.0), pair]), 10).err(),
+                Some(PopGeneratorError::InvalidBounds)
+            );
+            assert_eq!(LhsRealPopGenerator::with_dim_bounds(2, vec![(0.0, 1.0), pair], 10).err(), Some(PopGeneratorError::InvalidBounds));
+        }
+        // limites degenerados são válidos
+        let pop = LhsRealPopGenerator::with_dim_bounds(1, vec![(2.0, 2.0)], 5).unwrap().gen_pop();
+        assert!(pop.get_individuals().iter().all(|individual| individual.0 == vec![2.0]));
+    }
+
+    #[test]
+    fn test_bounds_clamp_per_dimension() {
+        let bounds = Bounds::from(vec![(0.0, 1.0), (-5.0, -4.0), (2.0, 3.0)]);
+        let mut individual = RealEncoding(vec![1.5, -4.5, 0.0]);
+        bounds.clamp_genes(&mut individual.0).unwrap();
+        assert_eq!(individual.0, vec![1.0, -4.5, 2.0]);
+
+        let mut individual = IntegerEncoding(vec![0, 9, 4]);
+        Bounds::from((3, 8)).clamp_genes(&mut individual.0).unwrap();
+        assert_eq!(individual.0, vec![3, 8, 4]);
+
+        let mut individual = RealEncoding(vec![1.5, -4.5, 0.0, 7.0]);
+        assert_eq!(bounds.clamp_genes(&mut individual.0), Err(PopGeneratorError::BoundsDimensionMismatch { expected: 4, found: 3 }));
+        assert_eq!(individual.0, vec![1.5, -4.5, 0.0, 7.0]);
+        assert_eq!(bounds.get(3), None);
+    }
+
+    #[test]
+    fn test_generate_categorical_population() {
+        let domain = CategoricalDomain::new(vec![1, 2, 5, 3]).unwrap();
+        let pop = CategoricalPopGenerator::new(domain.clone(), 200).gen_pop();
+        assert_eq!(pop.len(), 200);
+        assert!(pop.get_individuals().iter().all(|ind| domain.contains(ind)));
+        // todas as categorias do gene de cardinalidade 5 aparecem
+        let mut seen: Vec<u32> = pop.get_individuals().iter().map(|ind| ind.0[2]).collect();
+        seen.sort();
+        seen.dedup();
+        assert_eq!(seen, vec![0, 1, 2, 3, 4]);
+
+        assert_eq!(CategoricalDomain::new(vec![2, 0, 3]), Err(PopGeneratorError::EmptyAlphabet { gene: 1 }));
+    }
+
+    #[test]
+    fn test_categorical_random_reset_respects_gene_domains() {
+        let mut rng = StdRng::seed_from_u64(3);
+        let domain = CategoricalDomain::new(vec![1, 2, 7]).unwrap();
+        let mut individual = CategoricalEncoding(vec![0, 1, 6]);
+        let mut changed = false;
+        for _ in 0..100 {
+            let before = individual.clone();
+            domain.random_reset(&mut individual, 1.0, &mut rng);
+            assert!(domain.contains(&individual));
+            assert_eq!(individual.0[0], 0);
+            changed |= individual != before;
+        }
+        assert!(changed);
+
+        let before = individual.clone();
+        domain.random_reset(&mut individual, 0.0, &mut rng);
+        assert_eq!(individual, before);
+    }
+
     #[test]
     fn test_generate_int_permutation_population() {
         let dim = 15;
         let pop_size = 10;
-        let pop_generator = IntPermPopGenerator { dim, pop_size };
+        let pop_generator = IntPermPopGenerator::new(dim, pop_size);
         let population = pop_generator.gen_pop();
         let individuals = population.0;
         let comparison_vec: Vec<usize> = (0..=dim).collect();
@@ -211,4 +1446,533 @@ mod population_tests {
                 .for_each(|(i, &val)| assert_eq!(val, comparison_vec[i]));
         }
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_sized_permutation_generator_takes_inferred_dimension() {
+        let mut generator = IntPermPopGenerator::sized_with_layout(8, 1, Some(1)).unwrap();
+        assert!(!generator.has_dimension());
+
+        generator.infer_dimension(5).unwrap();
+        assert_eq!(generator.dimension(), 5);
+        for individual in generator.gen_pop().get_individuals() {
+            let mut sorted = individual.0.clone();
+            sorted.sort();
+            assert_eq!(sorted, (1..=5).collect::<Vec<_>>());
+            assert_eq!(individual.0[0], 1);
+        }
+        assert!(IntPermPopGenerator::sized_with_layout(8, 1, Some(0)).is_err());
+    }
+
+    #[test]
+    fn test_inferred_dimension_must_hold_the_fixed_first_value() {
+        // 10 não pertence a 1..=8: o erro só aparece quando a dimensão chega
+        let mut generator = IntPermPopGenerator::sized_with_layout(8, 1, Some(10)).unwrap();
+        assert_eq!(generator.infer_dimension(8), Err(PopGeneratorError::FixedValueOutOfRange { value: 10 }));
+        assert!(!generator.has_dimension());
+
+        generator.infer_dimension(10).unwrap();
+        assert!(generator.gen_pop().get_individuals().iter().all(|individual| individual.0[0] == 10));
+    }
+
+    #[test]
+    fn test_wrappers_pass_the_inferred_dimension_through() {
+        let seeds = vec![BinaryEncoding::from_bools(&[true; 4])];
+        let mut seeded = SeededPopGenerator::new(BinaryPopGenerator::sized(6), seeds).unwrap();
+        assert!(!seeded.has_dimension());
+        seeded.infer_dimension(4).unwrap();
+        assert_eq!(seeded.dimension(), 4);
+        let pop = seeded.gen_pop();
+        assert_eq!(pop.get_individuals()[0], BinaryEncoding::from_bools(&[true; 4]));
+        assert!(pop.get_individuals().iter().all(|individual| individual.len() == 4));
+
+        // sementes de outro tamanho são rejeitadas quando a dimensão chega
+        let seeds = vec![BinaryEncoding::from_bools(&[true; 4])];
+        let mut seeded = SeededPopGenerator::new(BinaryPopGenerator::sized(6), seeds).unwrap();
+        assert_eq!(seeded.infer_dimension(5), Err(PopGeneratorError::SeedDimensionMismatch { index: 0, expected: 5, found: 4 }));
+
+        let mut fixed = FixedFirstPopGenerator::new(IntPermPopGenerator::sized(6), 2);
+        assert!(!fixed.has_dimension());
+        fixed.infer_dimension(5).unwrap();
+        assert_eq!(fixed.dimension(), 5);
+        assert!(fixed.gen_pop().get_individuals().iter().all(|individual| individual.0.len() == 5 && individual.0[0] == 2));
+    }
+
+    #[test]
+    #[should_panic(expected = "generator has no dimension")]
+    fn test_sized_binary_generator_without_dimension_panics() {
+        BinaryPopGenerator::sized(4).gen_pop();
+    }
+
+    #[test]
+    #[should_panic(expected = "generator has no dimension")]
+    fn test_sized_permutation_generator_without_dimension_panics() {
+        IntPermPopGenerator::sized(4).gen_pop();
+    }
+
+    #[test]
+    fn test_generate_one_based_permutations_with_fixed_first() {
+        let pop = IntPermPopGenerator::new_with_layout(6, 40, 1, Some(1)).unwrap().gen_pop();
+        for individual in pop.get_individuals() {
+            assert_eq!(individual.0[0], 1);
+            let mut sorted = individual.0.clone();
+            sorted.sort();
+            assert_eq!(sorted, (1..=6).collect::<Vec<_>>());
+        }
+
+        assert_eq!(
+            IntPermPopGenerator::new_with_layout(6, 40, 1, Some(0)).err(),
+            Some(PopGeneratorError::FixedValueOutOfRange { value: 0 })
+        );
+        assert_eq!(
+            IntPermPopGenerator::new_with_layout(6, 40, 1, Some(7)).err(),
+            Some(PopGeneratorError::FixedValueOutOfRange { value: 7 })
+        );
+    }
+
+    #[test]
+    fn test_fixed_first_wrapper_preserves_cyclic_order() {
+        let mut tour = IntPermEncoding(vec![3, 1, 0, 2]);
+        assert!(tour.rotate_to_front(0));
+        assert_eq!(tour, IntPermEncoding(vec![0, 2, 3, 1]));
+        assert!(!tour.rotate_to_front(7));
+
+        let seeds = vec![IntPermEncoding(vec![2, 0, 1])];
+        let inner = SeededPopGenerator::new(IntPermPopGenerator::new(3, 10), seeds).unwrap();
+        let pop = FixedFirstPopGenerator::new(inner, 1).gen_pop();
+        assert_eq!(pop.get_individuals()[0], IntPermEncoding(vec![1, 2, 0]));
+        assert!(pop.get_individuals().iter().all(|individual| individual.0[0] == 1));
+    }
+
+    #[test]
+    fn test_seeded_population_starts_with_seeds() {
+        let seeds = vec![
+            BinaryEncoding::from(vec![true, true, true, true]),
+            BinaryEncoding::from(vec![false, true, false, true]),
+        ];
+        let pop_generator = SeededPopGenerator::new(BinaryPopGenerator::new(4, 10), seeds.clone()).unwrap();
+        let individuals = pop_generator.gen_pop().0;
+        assert_eq!(individuals.len(), 10);
+        assert_eq!(&individuals[..2], seeds.as_slice());
+
+        for individual in individuals {
+            assert_eq!(individual.len(), 4);
+        }
+    }
+
+    #[test]
+    fn test_seeded_population_truncates_extra_seeds() {
+        let seeds: Vec<IntPermEncoding> = (0..5).map(|_| IntPermEncoding(vec![2, 1, 0])).collect();
+        let pop_generator = SeededPopGenerator::new(IntPermPopGenerator::new(3, 3), seeds).unwrap();
+        let individuals = pop_generator.gen_pop().0;
+        assert_eq!(individuals, vec![IntPermEncoding(vec![2, 1, 0]); 3]);
+    }
+
+    #[test]
+    fn test_seeded_population_dimension_mismatch() {
+        let seeds = vec![RealEncoding(vec![0.0, 1.0]), RealEncoding(vec![0.0])];
+        let result = SeededPopGenerator::new(RealPopGenerator::new(2, (0.0, 1.0), 5), seeds);
+        match result {
+            Err(PopGeneratorError::SeedDimensionMismatch { index: 1, expected: 2, found: 1 }) => {},
+            Err(err) => panic!("Expected PopGeneratorError::SeedDimensionMismatch, got {:?}", err),
+            _ => panic!("Expected an error")
+        }
+    }
+
+    fn assert_one_sample_per_stratum(individuals: &[RealEncoding], bounds: &[(f64, f64)]) {
+        let pop_size = individuals.len();
+        for (d, &(lower, upper)) in bounds.iter().enumerate() {
+            let mut strata: Vec<usize> = individuals
+                .iter()
+                .map(|individual| {
+                    let gene = individual.0[d];
+                    assert!(gene >= lower && gene <= upper);
+                    let stratum = ((gene - lower) / (upper - lower) * pop_size as f64) as usize;
+                    stratum.min(pop_size - 1)
+                })
+                .collect();
+            strata.sort();
+            assert_eq!(strata, (0..pop_size).collect::<Vec<usize>>());
+        }
+    }
+
+    #[test]
+    fn test_generate_lhs_real_population() {
+        let dim = 6;
+        let bounds = (-5.0, 5.0);
+        let pop_size = 40;
+        let pop_generator = LhsRealPopGenerator::new(dim, bounds, pop_size);
+        let individuals = pop_generator.gen_pop().0;
+        assert_eq!(individuals.len(), pop_size);
+        assert_one_sample_per_stratum(&individuals, &vec![bounds; dim]);
+    }
+
+    #[test]
+    fn test_generate_lhs_real_population_per_dimension_bounds() {
+        let bounds = vec![(0.0, 1.0), (-100.0, -50.0), (1e3, 2e3)];
+        let pop_size = 25;
+        let pop_generator = LhsRealPopGenerator::with_dim_bounds(3, bounds.clone(), pop_size).unwrap();
+        let individuals = pop_generator.gen_pop().0;
+        assert_eq!(individuals.len(), pop_size);
+        assert_one_sample_per_stratum(&individuals, &bounds);
+    }
+
+    #[test]
+    fn test_lhs_bounds_length_mismatch() {
+        let result = LhsRealPopGenerator::with_dim_bounds(3, vec![(0.0, 1.0); 2], 10);
+        match result {
+            Err(PopGeneratorError::BoundsDimensionMismatch { expected: 3, found: 2 }) => {},
+            Err(err) => panic!("Expected PopGeneratorError::BoundsDimensionMismatch, got {:?}", err),
+            _ => panic!("Expected an error")
+        }
+    }
+
+    fn circle_distances(num_cities: usize) -> Vec<Vec<f64>> {
+        let points: Vec<(f64, f64)> = (0..num_cities)
+            .map(|i| {
+                // embaralha a ordem dos pontos para que a identidade não seja a rota ótima
+                let angle = ((i * 7) % num_cities) as f64 * std::f64::consts::TAU / num_cities as f64;
+                (angle.cos(), angle.sin())
+            })
+            .collect();
+        points
+            .iter()
+            .map(|a| points.iter().map(|b| ((a.0 - b.0).powi(2) + (a.1 - b.1).powi(2)).sqrt()).collect())
+            .collect()
+    }
+
+    fn tour_length(tour: &[usize], distances: &[Vec<f64>]) -> f64 {
+        (0..tour.len())
+            .map(|i| distances[tour[i]][tour[(i + 1) % tour.len()]])
+            .sum()
+    }
+
+    #[test]
+    fn test_generate_nearest_neighbor_population() {
+        let num_cities = 12;
+        let distances = circle_distances(num_cities);
+        let pop_size = 30;
+        let pop_generator = NearestNeighborPopGenerator::new(distances.clone(), pop_size)
+            .unwrap()
+            .with_noise(0.2, 3)
+            .unwrap();
+        let individuals = pop_generator.gen_pop().0;
+        assert_eq!(individuals.len(), pop_size);
+
+        for individual in &individuals {
+            let mut genes = individual.0.clone();
+            genes.sort();
+            assert_eq!(genes, (0..num_cities).collect::<Vec<usize>>());
+        }
+
+        let random_individuals = IntPermPopGenerator::new(num_cities, pop_size).gen_pop().0;
+        let mean_length = |individuals: &[IntPermEncoding]| {
+            individuals.iter().map(|ind| tour_length(&ind.0, &distances)).sum::<f64>() / individuals.len() as f64
+        };
+        assert!(mean_length(&individuals) < mean_length(&random_individuals));
+    }
+
+    #[test]
+    fn test_nearest_neighbor_without_noise_is_deterministic() {
+        let pop_generator = NearestNeighborPopGenerator::new(circle_distances(9), 1).unwrap();
+        let mut rng = rand::thread_rng();
+        let first = pop_generator.construct_tour(4, &mut rng).unwrap();
+        let second = pop_generator.construct_tour(4, &mut rng).unwrap();
+        assert_eq!(first, second);
+        assert_eq!(first.0[0], 4);
+        assert_eq!(pop_generator.construct_tour(9, &mut rng), Err(PopGeneratorError::StartOutOfRange { start: 9, num_cities: 9 }));
+    }
+
+    #[test]
+    fn test_nearest_neighbor_rejects_invalid_noise() {
+        for noise in [-0.1, 1.5, f64::NAN, f64::INFINITY] {
+            let pop_generator = NearestNeighborPopGenerator::new(circle_distances(5), 4).unwrap();
+            assert!(matches!(pop_generator.with_noise(noise, 2), Err(PopGeneratorError::InvalidNoise)));
+        }
+        let pop_generator = NearestNeighborPopGenerator::new(circle_distances(5), 4).unwrap();
+        assert_eq!(pop_generator.with_noise(1.0, 2).unwrap().gen_pop().get_individuals().len(), 4);
+    }
+
+    #[test]
+    fn test_nearest_neighbor_rejects_non_square_matrix() {
+        let result = NearestNeighborPopGenerator::new(vec![vec![0.0, 1.0], vec![1.0]], 4);
+        match result {
+            Err(PopGeneratorError::NonSquareDistanceMatrix) => {},
+            Err(err) => panic!("Expected PopGeneratorError::NonSquareDistanceMatrix, got {:?}", err),
+            _ => panic!("Expected an error")
+        }
+
+        assert!(matches!(NearestNeighborPopGenerator::new(Vec::new(), 4), Err(PopGeneratorError::EmptyDistanceMatrix)));
+        assert!(matches!(NearestNeighborPopGenerator::new(vec![Vec::new()], 4), Err(PopGeneratorError::NonSquareDistanceMatrix)));
+    }
+
+    #[test]
+    fn test_binary_encoding_matches_bool_vec_model() {
+        let mut rng = rand::thread_rng();
+        let len = 150;
+        let mut model: Vec<bool> = (0..len).map(|_| rng.gen_bool(0.5)).collect();
+        let mut encoding = BinaryEncoding::from(model.clone());
+
+        for _ in 0..1000 {
+            let index = rng.gen_range(0..len);
+            if rng.gen_bool(0.5) {
+                let value = rng.gen_bool(0.5);
+                model[index] = value;
+                encoding.set(index, value);
+            } else {
+                model[index] = !model[index];
+                encoding.flip(index);
+            }
+            assert_eq!(encoding.get(index), model[index]);
+        }
+
+        assert_eq!(encoding.len(), len);
+        assert_eq!(encoding.iter().collect::<Vec<bool>>(), model);
+        assert_eq!(encoding.count_ones(), model.iter().filter(|bit| **bit).count());
+        assert_eq!(encoding, BinaryEncoding::from(model));
+    }
+
+    #[test]
+    fn test_binary_encoding_hamming_distance() {
+        let mut rng = rand::thread_rng();
+        for len in [1, 63, 64, 65, 200] {
+            let a: Vec<bool> = (0..len).map(|_| rng.gen_bool(0.5)).collect();
+            let b: Vec<bool> = (0..len).map(|_| rng.gen_bool(0.5)).collect();
+            let naive = a.iter().zip(b.iter()).filter(|(x, y)| x != y).count();
+            assert_eq!(BinaryEncoding::from(a).hamming_distance(&BinaryEncoding::from(b)), naive);
+        }
+    }
+
+    #[test]
+    fn test_encoding_distances() {
+        let a = BinaryEncoding::from_bools(&[true, false, true]);
+        let b = BinaryEncoding::from_bools(&[false, false, false]);
+        assert_eq!(a.distance(&b), 2.0);
+        assert_eq!(IntPermEncoding(vec![0, 1, 2, 3]).distance(&IntPermEncoding(vec![0, 2, 1, 3])), 2.0);
+        assert_eq!(IntegerEncoding(vec![5, 1]).distance(&IntegerEncoding(vec![2, 4])), 6.0);
+        assert_eq!(RealEncoding(vec![0.0, 0.0]).distance(&RealEncoding(vec![3.0, 4.0])), 5.0);
+    }
+
+    #[test]
+    fn test_binary_encoding_uniform_crossover() {
+        let mut rng = rand::thread_rng();
+        let len = 130;
+        let a = BinaryEncoding::zeros(len);
+        let b = BinaryEncoding::from(vec![true; len]);
+        let child = a.uniform_crossover(&b, &mut rng);
+        assert_eq!(child.len(), len);
+        // os bits não usados da última palavra permanecem zerados
+        assert_eq!(child.count_ones(), child.iter().filter(|bit| *bit).count());
+
+        let parent_1: Vec<bool> = (0..len).map(|_| rng.gen_bool(0.5)).collect();
+        let parent_2: Vec<bool> = (0..len).map(|_| rng.gen_bool(0.5)).collect();
+        let child = BinaryEncoding::from(parent_1.clone()).uniform_crossover(&BinaryEncoding::from(parent_2.clone()), &mut rng);
+        for (i, bit) in child.iter().enumerate() {
+            assert!(bit == parent_1[i] || bit == parent_2[i]);
+        }
+    }
+
+    fn numbered_population(size: usize) -> Population<IntegerEncoding> {
+        Population((0..size as u64).map(|i| IntegerEncoding(vec![i])).collect())
+    }
+
+    #[test]
+    fn test_population_sample_without_replacement() {
+        let mut rng = rand::thread_rng();
+        let pop = numbered_population(20);
+        for n in [0, 1, 7, 20] {
+            let mut sampled: Vec<u64> = pop.sample(n, &mut rng).unwrap().0.into_iter().map(|ind| ind.0[0]).collect();
+            sampled.sort();
+            sampled.dedup();
+            assert_eq!(sampled.len(), n);
+        }
+        assert_eq!(pop.sample(21, &mut rng), Err(SampleSizeError { requested: 21, available: 20 }));
+    }
+
+    #[test]
+    fn test_population_column_ones() {
+        let pop = Population(vec![
+            BinaryEncoding::from_bitstring("1100").unwrap(),
+            BinaryEncoding::from_bitstring("1010").unwrap(),
+            BinaryEncoding::from_bitstring("1000").unwrap(),
+        ]);
+        assert_eq!(pop.column_ones(), vec![3, 1, 1, 0]);
+        assert!(Population::<BinaryEncoding>(vec![]).column_ones().is_empty());
+
+        let mut rng = rand::thread_rng();
+        let bools: Vec<Vec<bool>> = (0..20).map(|_| (0..150).map(|_| rng.gen_bool(0.5)).collect()).collect();
+        let pop = Population(bools.iter().map(|bits| BinaryEncoding::from_bools(bits)).collect());
+        let naive: Vec<usize> = (0..150).map(|i| bools.iter().filter(|bits| bits[i]).count()).collect();
+        assert_eq!(pop.column_ones(), naive);
+    }
+
+    #[test]
+    fn test_population_split_at() {
+        let (head, tail) = numbered_population(5).split_at(2);
+        assert_eq!(head, Population(vec![IntegerEncoding(vec![0]), IntegerEncoding(vec![1])]));
+        assert_eq!(tail.len(), 3);
+        assert_eq!(tail.0[0], IntegerEncoding(vec![2]));
+    }
+
+    #[test]
+    fn test_population_partition_and_merge() {
+        let mut rng = rand::thread_rng();
+        for (size, k) in [(10, 3), (9, 3), (2, 5), (0, 1)] {
+            let parts = numbered_population(size).partition(k, &mut rng);
+            assert_eq!(parts.len(), k);
+            let sizes: Vec<usize> = parts.iter().map(Population::len).collect();
+            assert!(sizes.iter().max().unwrap() - sizes.iter().min().unwrap() <= 1);
+
+            let mut merged: Vec<u64> = Population::merge(parts).0.into_iter().map(|ind| ind.0[0]).collect();
+            merged.sort();
+            assert_eq!(merged, (0..size as u64).collect::<Vec<_>>());
+        }
+    }
+
+    #[test]
+    fn test_matrix_population_round_trip() {
+        let population = RealPopGenerator::new(7, (-1.0, 1.0), 20).gen_pop();
+        let matrix = MatrixPopulation::from_population(&population).unwrap();
+        assert_eq!(matrix.pop_size(), 20);
+        assert_eq!(matrix.dim(), 7);
+        for (i, individual) in population.get_individuals().iter().enumerate() {
+            assert_eq!(matrix.row(i), individual.0.as_slice());
+        }
+        assert_eq!(matrix.to_population(), population);
+    }
+
+    #[test]
+    fn test_matrix_population_zero_dimension() {
+        let matrix = MatrixPopulation::zeros(5, 0);
+        assert_eq!(matrix.pop_size(), 5);
+        assert_eq!(matrix.rows().count(), 5);
+        assert!(matrix.rows().all(<[f64]>::is_empty));
+
+        let population = Population(vec![RealEncoding(Vec::new()); 3]);
+        let mut matrix = MatrixPopulation::from_population(&population).unwrap();
+        assert_eq!(matrix.pop_size(), 3);
+        assert_eq!(matrix.rows_mut().count(), 3);
+        assert_eq!(matrix.to_population(), population);
+    }
+
+    #[test]
+    fn test_matrix_population_rejects_ragged_population() {
+        let population = Population(vec![RealEncoding(vec![0.0, 1.0]), RealEncoding(vec![0.0])]);
+        assert!(MatrixPopulation::from_population(&population).is_none());
+    }
+
+    #[test]
+    fn test_matrix_population_rows_share_one_buffer() {
+        let (pop_size, dim) = (10_000, 1_000);
+        let mut matrix = MatrixPopulation::zeros(pop_size, dim);
+        matrix.row_mut(9_999)[999] = 1.0;
+
+        let base = matrix.as_slice().as_ptr();
+        assert_eq!(matrix.as_slice().len(), pop_size * dim);
+        for (i, row) in matrix.rows().enumerate() {
+            assert_eq!(row.as_ptr(), base.wrapping_add(i * dim));
+        }
+        assert_eq!(matrix.rows().count(), pop_size);
+        assert_eq!(matrix.as_slice()[pop_size * dim - 1], 1.0);
+    }
+
+    #[test]
+    fn test_integer_domain_clamping() {
+        let domain = IntegerDomain::new(3, 8).unwrap();
+        let mut individual = IntegerEncoding(vec![0, 3, 5, 8, 42]);
+        assert!(!domain.is_within_bounds(&individual));
+
+        domain.clamp_to_bounds(&mut individual);
+        assert_eq!(individual.0, vec![3, 3, 5, 8, 8]);
+        assert!(domain.is_within_bounds(&individual));
+
+        let mut rng = rand::thread_rng();
+        assert!((0..100).all(|_| domain.contains(domain.random_gene(&mut rng))));
+    }
+
+    #[test]
+    fn test_integer_domain_invalid_bounds() {
+        match IntegerDomain::new(10, 1) {
+            Err(PopGeneratorError::InvalidBounds) => {},
+            Err(err) => panic!("Expected PopGeneratorError::InvalidBounds, got {:?}", err),
+            _ => panic!("Expected an error")
+        }
+        assert!(IntegerDomain::new(u64::MAX, u64::MAX).unwrap().contains(u64::MAX));
+    }
+
+    #[test]
+    fn test_binary_encoding_bitstring_round_trip() {
+        let encoding = BinaryEncoding::from_bitstring("0010_1101 01").unwrap();
+        assert_eq!(encoding.len(), 10);
+        assert_eq!(encoding.to_bitstring(), "0010110101");
+        assert_eq!(BinaryEncoding::from_bitstring(&encoding.to_bitstring()).unwrap(), encoding);
+    }
+
+    #[test]
+    fn test_binary_encoding_invalid_bitstring() {
+        match BinaryEncoding::from_bitstring("10a1") {
+            Err(ParseBitstringError::InvalidCharacter { position: 2, character: 'a' }) => {},
+            Err(err) => panic!("Expected ParseBitstringError::InvalidCharacter, got {:?}", err),
+            _ => panic!("Expected an error")
+        }
+    }
+
+    #[test]
+    fn test_binary_encoding_u64_round_trip() {
+        let encoding = BinaryEncoding::from_u64(6, 8);
+        assert_eq!(encoding.to_bitstring(), "00000110");
+        assert_eq!(u64::try_from(&encoding), Ok(6));
+
+        let value = 0xDEAD_BEEF_0123_4567;
+        assert_eq!(u64::try_from(&BinaryEncoding::from_u64(value, 64)), Ok(value));
+    }
+
+    #[test]
+    fn test_binary_encoding_u64_width_overflow() {
+        let encoding = BinaryEncoding::from_u64(1, 65);
+        assert_eq!(encoding.to_bitstring().len(), 65);
+        assert_eq!(u64::try_from(&encoding), Err(WidthOverflowError { width: 65 }));
+    }
+}
+#[cfg(all(test, feature = "serde"))]
+mod serde_tests {
+    use super::*;
+
+    fn assert_round_trip<T>(value: &T)
+    where
+        T: Serialize + for<'de> Deserialize<'de> + PartialEq + std::fmt::Debug,
+    {
+        let json = serde_json::to_string(value).unwrap();
+        let restored: T = serde_json::from_str(&json).unwrap();
+        assert_eq!(&restored, value);
+    }
+
+    #[test]
+    fn test_encodings_round_trip() {
+        assert_round_trip(&BinaryEncoding::from(vec![true, false, true]));
+        assert_round_trip(&IntPermEncoding(vec![2, 0, 1]));
+        assert_round_trip(&IntegerEncoding(vec![7, 3, 10]));
+        assert_round_trip(&RealEncoding(vec![0.5, -1.25, 3.0]));
+        assert_round_trip(&CategoricalEncoding(vec![2, 0, 1]));
+    }
+
+    #[test]
+    fn test_populations_round_trip() {
+        assert_round_trip(&BinaryPopGenerator::new(8, 5).gen_pop());
+        assert_round_trip(&IntegerPopGenerator::new(4, IntegerDomain::new(1, 9).unwrap(), 5).gen_pop());
+        assert_round_trip(&IntPermPopGenerator::new(6, 5).gen_pop());
+        assert_round_trip(&RealPopGenerator::new(3, (-2.0, 2.0), 5).gen_pop());
+    }
+
+    #[test]
+    fn test_binary_encoding_deserializes_from_bitstring() {
+        let encoding: BinaryEncoding = serde_json::from_str(r#""0110""#).unwrap();
+        assert_eq!(encoding, BinaryEncoding::from(vec![false, true, true, false]));
+        assert!(serde_json::from_str::<BinaryEncoding>(r#""01x0""#).is_err());
+    }
+
+    #[test]
+    fn test_binary_encoding_serializes_as_bool_array() {
+        let json = serde_json::to_string(&BinaryEncoding::from(vec![true, false])).unwrap();
+        assert_eq!(json, "[true,false]");
+    }
+}
@@ -1,32 +1,909 @@
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
+use regex::Regex;
 use serde_json::{error::Category, Deserializer};
-use std::{io::Read};
+use std::{fmt, fs::File, io::{self, Read}, path::{Path, PathBuf}, sync::LazyLock};
+use crate::alg::objectives::{FormulaParsingError, ScoreMode};
+use crate::alg::scaling::{FitnessPipeline, Scaling};
+use crate::alg::termination::{Termination, TerminationCriterion};
+use crate::population::*;
 
-#[derive(Debug, PartialEq, Deserialize)]
-#[serde(tag="type")]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag="type", deny_unknown_fields)]
 pub enum EncodingDTO {
-    Binary {dim: usize},
+    /// `dim` pode ser omitido quando o objetivo o determina (ex. SAT).
+    /// `density` é a probabilidade de cada gene inicial valer 1 (padrão 0.5).
+    Binary {
+        dim: Option<usize>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        density: Option<f64>,
+    },
     IntegerPermutation {dim: usize},
-    Integer {dim: usize, bounds: (usize, usize)},
-    Real {dim: usize, bounds: (f64, f64)},
+    /// `bounds` é um par `[lower, upper]` para todas as dimensões ou uma lista
+    /// com um par por dimensão.
+    Integer {dim: usize, bounds: Bounds<u64>},
+    Real {dim: usize, bounds: Bounds<f64>},
+    /// Um alfabeto por gene: o gene `i` assume valores em `0..cardinalities[i]`.
+    Categorical {cardinalities: Vec<u32>},
 }
 
-#[derive(Deserialize, Debug, PartialEq)]
+/// Peso das cláusulas falsas no score SAT (ver [`ScoreMode`]).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ScoreModeDTO {
+    FalseCount,
+    LengthWeighted,
+    LogLengthWeighted,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag="type", deny_unknown_fields)]
+pub enum ObjectiveDTO {
+    /// `score_mode` ausente equivale a `FalseCount`.
+    Sat {cnf_path: PathBuf, score_mode: Option<ScoreModeDTO>},
+    /// Objetivo construído pela aplicação e registrado em um
+    /// [`ObjectiveRegistry`](super::objective::ObjectiveRegistry).
+    Registered {name: String},
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag="type", deny_unknown_fields)]
+pub enum SelectionDTO {
+    Tournament {size: usize},
+    Roulette,
+    Rank {pressure: f64},
+    Sus,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag="type", deny_unknown_fields)]
+pub enum CrossoverOperatorDTO {
+    OnePoint,
+    TwoPoint,
+    Uniform {ratio: f64},
+    Pmx,
+    Ox,
+    Cx,
+    Sbx {eta: f64},
+    Blend {alpha: f64},
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct CrossoverDTO {
+    pub operator: CrossoverOperatorDTO,
+    pub rate: f64,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag="type", deny_unknown_fields)]
+pub enum MutationOperatorDTO {
+    BitFlip,
+    Swap,
+    Inversion,
+    Scramble,
+    Creep {step: u64},
+    RandomReset,
+    Gaussian {sigma: f64},
+    Polynomial {eta: f64},
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct MutationDTO {
+    pub operator: MutationOperatorDTO,
+    pub rate: f64,
+}
+
+/// Etapa do pipeline de fitness, aplicada na ordem em que aparece em `fitness`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag="type", deny_unknown_fields)]
+pub enum ScalingDTO {
+    Negate,
+    SigmaTruncation {c: f64},
+    Rank,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TerminationModeDTO {
+    #[default]
+    Any,
+    All,
+}
+
+/// Um critério de parada, escrito como `{"stagnation": 50}`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case", deny_unknown_fields)]
+pub enum CriterionDTO {
+    MaxGenerations(u64),
+    TargetScore(f64),
+    Stagnation(u64),
+    MaxSeconds(f64),
+}
+
+/// Grupo aninhado em `termination.criteria`; só contém critérios simples.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct TerminationGroupDTO {
+    pub mode: TerminationModeDTO,
+    pub criteria: Vec<CriterionDTO>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum TerminationEntryDTO {
+    Criterion(CriterionDTO),
+    Group(TerminationGroupDTO),
+}
+
+/// Critérios de parada. Os campos nomeados e os itens de `criteria` são
+/// combinados segundo `mode` (padrão `any`):
+///
+/// ```json
+/// {"mode": "any", "criteria": [
+///     {"target_score": 0.0},
+///     {"mode": "all", "criteria": [{"stagnation": 50}, {"max_generations": 1000}]}
+/// ]}
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct TerminationDTO {
+    pub max_generations: Option<u64>,
+    pub target_score: Option<f64>,
+    pub stagnation: Option<u64>,
+    pub max_seconds: Option<f64>,
+    pub mode: Option<TerminationModeDTO>,
+    pub criteria: Option<Vec<TerminationEntryDTO>>,
+}
+
+/// Apenas `encoding` é obrigatório. Campos de operadores ausentes valem
+/// `None` até serem resolvidos por [`ConfigDefaults::resolve`].
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(deny_unknown_fields)]
 pub struct ConfigDTO {
     pub encoding: EncodingDTO,
+    pub objective: Option<ObjectiveDTO>,
+    #[serde(default = "ConfigDefaults::pop_size")]
     pub pop_size: usize,
+    #[serde(default = "ConfigDefaults::runs")]
     pub runs: usize,
+    #[serde(default = "ConfigDefaults::generations")]
     pub generations: usize,
+    pub selection: Option<SelectionDTO>,
+    pub crossover: Option<CrossoverDTO>,
+    pub mutation: Option<MutationDTO>,
+    pub elitism: Option<usize>,
+    pub termination: Option<TerminationDTO>,
+    /// Semente mestra; as sementes de cada execução são derivadas dela.
+    pub seed: Option<u64>,
+    /// Sementes explícitas, uma por execução (tem precedência sobre `seed`).
+    pub run_seeds: Option<Vec<u64>>,
+    /// Threads usadas na avaliação paralela (feature `parallel`), passadas a
+    /// [`EvalPool::new`](crate::alg::parallel::EvalPool::new).
+    pub threads: Option<usize>,
+    /// Transformações dos scores antes da seleção (ver [`ScalingDTO`]).
+    pub fitness: Option<Vec<ScalingDTO>>,
+}
+
+/// Valores padrão da configuração. Os operadores dependem do encoding: a taxa
+/// de mutação padrão por gene é `1 / dim`, por isso a mutação só é resolvida
+/// quando `dim` é conhecido (após `build_objective`, se ele foi omitido).
+pub struct ConfigDefaults;
+
+impl ConfigDefaults {
+    pub fn pop_size() -> usize { 100 }
+    pub fn runs() -> usize { 1 }
+    pub fn generations() -> usize { 100 }
+    pub fn elitism() -> usize { 1 }
+    pub fn crossover_rate() -> f64 { 0.9 }
+
+    pub fn selection() -> SelectionDTO {
+        SelectionDTO::Tournament { size: 2 }
+    }
+
+    pub fn crossover(encoding: &EncodingDTO) -> CrossoverDTO {
+        let operator = match encoding {
+            EncodingDTO::Binary { .. } | EncodingDTO::Integer { .. } | EncodingDTO::Categorical { .. } =>
+                CrossoverOperatorDTO::Uniform { ratio: 0.5 },
+            EncodingDTO::IntegerPermutation { .. } => CrossoverOperatorDTO::Ox,
+            EncodingDTO::Real { .. } => CrossoverOperatorDTO::Sbx { eta: 20.0 },
+        };
+        CrossoverDTO { operator, rate: Self::crossover_rate() }
+    }
+
+    pub fn mutation(encoding: &EncodingDTO) -> Option<MutationDTO> {
+        let dim = encoding.dim()?;
+        let operator = match encoding {
+            EncodingDTO::Binary { .. } => MutationOperatorDTO::BitFlip,
+            EncodingDTO::IntegerPermutation { .. } => MutationOperatorDTO::Swap,
+            EncodingDTO::Integer { .. } | EncodingDTO::Categorical { .. } => MutationOperatorDTO::RandomReset,
+            EncodingDTO::Real { .. } => MutationOperatorDTO::Polynomial { eta: 20.0 },
+        };
+        Some(MutationDTO { operator, rate: 1.0 / dim.max(1) as f64 })
+    }
+
+    /// Preenche os campos ausentes, devolvendo a configuração efetiva.
+    pub fn resolve(config: ConfigDTO) -> ConfigDTO {
+        ConfigDTO {
+            selection: config.selection.or_else(|| Some(Self::selection())),
+            crossover: config.crossover.or_else(|| Some(Self::crossover(&config.encoding))),
+            mutation: config.mutation.or_else(|| Self::mutation(&config.encoding)),
+            elitism: config.elitism.or(Some(Self::elitism())),
+            termination: config.termination.or_else(|| Some(TerminationDTO::default())),
+            ..config
+        }
+    }
+}
+
+impl EncodingDTO {
+    pub fn name(&self) -> &'static str {
+        match self {
+            EncodingDTO::Binary { .. } => "Binary",
+            EncodingDTO::IntegerPermutation { .. } => "IntegerPermutation",
+            EncodingDTO::Integer { .. } => "Integer",
+            EncodingDTO::Real { .. } => "Real",
+            EncodingDTO::Categorical { .. } => "Categorical",
+        }
+    }
+}
+
+impl CrossoverOperatorDTO {
+    pub fn name(&self) -> &'static str {
+        match self {
+            CrossoverOperatorDTO::OnePoint => "OnePoint",
+            CrossoverOperatorDTO::TwoPoint => "TwoPoint",
+            CrossoverOperatorDTO::Uniform { .. } => "Uniform",
+            CrossoverOperatorDTO::Pmx => "Pmx",
+            CrossoverOperatorDTO::Ox => "Ox",
+            CrossoverOperatorDTO::Cx => "Cx",
+            CrossoverOperatorDTO::Sbx { .. } => "Sbx",
+            CrossoverOperatorDTO::Blend { .. } => "Blend",
+        }
+    }
+
+    pub fn supports(&self, encoding: &EncodingDTO) -> bool {
+        use CrossoverOperatorDTO::*;
+        match encoding {
+            EncodingDTO::Binary { .. } | EncodingDTO::Integer { .. } | EncodingDTO::Categorical { .. } =>
+                matches!(self, OnePoint | TwoPoint | Uniform { .. }),
+            EncodingDTO::IntegerPermutation { .. } => matches!(self, Pmx | Ox | Cx),
+            EncodingDTO::Real { .. } => matches!(self, OnePoint | TwoPoint | Uniform { .. } | Sbx { .. } | Blend { .. }),
+        }
+    }
+}
+
+impl MutationOperatorDTO {
+    pub fn name(&self) -> &'static str {
+        match self {
+            MutationOperatorDTO::BitFlip => "BitFlip",
+            MutationOperatorDTO::Swap => "Swap",
+            MutationOperatorDTO::Inversion => "Inversion",
+            MutationOperatorDTO::Scramble => "Scramble",
+            MutationOperatorDTO::Creep { .. } => "Creep",
+            MutationOperatorDTO::RandomReset => "RandomReset",
+            MutationOperatorDTO::Gaussian { .. } => "Gaussian",
+            MutationOperatorDTO::Polynomial { .. } => "Polynomial",
+        }
+    }
+
+    pub fn supports(&self, encoding: &EncodingDTO) -> bool {
+        use MutationOperatorDTO::*;
+        match encoding {
+            EncodingDTO::Binary { .. } => matches!(self, BitFlip),
+            EncodingDTO::IntegerPermutation { .. } => matches!(self, Swap | Inversion | Scramble),
+            EncodingDTO::Integer { .. } => matches!(self, Creep { .. } | RandomReset),
+            EncodingDTO::Real { .. } => matches!(self, Gaussian { .. } | Polynomial { .. }),
+            EncodingDTO::Categorical { .. } => matches!(self, RandomReset),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum ConfigError {
+    IO(io::Error),
+    /// JSON malformado.
+    Syntax { message: String, line: usize, column: usize },
+    /// JSON válido que não corresponde à configuração (tipo errado, campo
+    /// ausente, variante desconhecida...). `path` indica o campo, ex. `encoding.dim`.
+    Data { message: String, path: Option<String>, line: usize, column: usize },
+    /// Arquivo terminou antes do fim do documento.
+    Eof { line: usize, column: usize },
+    ObjectiveFile { path: PathBuf, error: FormulaParsingError },
+    ObjectiveEncodingMismatch { objective: &'static str, encoding: &'static str },
+    ObjectiveDimensionMismatch { path: PathBuf, expected: usize, found: usize },
+    /// Nome ausente do registro; `available` traz os nomes registrados.
+    UnknownObjective { name: String, available: Vec<String> },
+    RegisteredDimensionMismatch { name: String, expected: usize, found: usize },
+    /// Objetivo registrado para um encoding diferente do configurado.
+    RegisteredEncodingMismatch { name: String, expected: &'static str, found: &'static str },
+    /// Campo desconhecido; `suggestions` traz os campos válidos mais parecidos.
+    UnknownField { field: String, suggestions: Vec<String>, line: usize, column: usize },
+    /// Extensão de arquivo sem formato correspondente (ou com a feature desativada).
+    UnsupportedFormat { path: PathBuf },
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ConfigError::IO(err) => write!(f, "could not read config: {}", err),
+            ConfigError::Syntax { message, line, column } =>
+                write!(f, "syntax error at line {} column {}: {}", line, column, message),
+            ConfigError::Data { message, path: Some(path), line, column } =>
+                write!(f, "invalid value for `{}` at line {} column {}: {}", path, line, column, message),
+            ConfigError::Data { message, path: None, line, column } =>
+                write!(f, "invalid config at line {} column {}: {}", line, column, message),
+            ConfigError::Eof { line, column } =>
+                write!(f, "unexpected end of config at line {} column {}", line, column),
+            ConfigError::ObjectiveFile { path, error } =>
+                write!(f, "could not load objective from {}: {}", path.display(), error),
+            ConfigError::ObjectiveEncodingMismatch { objective, encoding } =>
+                write!(f, "objective {} cannot be used with the {} encoding", objective, encoding),
+            ConfigError::ObjectiveDimensionMismatch { path, expected, found } =>
+                write!(f, "{} requires dim {} but the encoding has dim {}", path.display(), expected, found),
+            ConfigError::UnknownObjective { name, available } if available.is_empty() =>
+                write!(f, "no objective registered as `{}` (the registry is empty)", name),
+            ConfigError::UnknownObjective { name, available } =>
+                write!(f, "no objective registered as `{}`; available: `{}`", name, available.join("`, `")),
+            ConfigError::RegisteredDimensionMismatch { name, expected, found } =>
+                write!(f, "objective `{}` requires dim {} but the encoding has dim {}", name, expected, found),
+            ConfigError::RegisteredEncodingMismatch { name, expected, found } =>
+                write!(f, "objective `{}` evaluates the {} encoding but the config uses {}", name, expected, found),
+            ConfigError::UnknownField { field, suggestions, line, column } => {
+                write!(f, "unknown field `{}` at line {} column {}", field, line, column)?;
+                match suggestions.as_slice() {
+                    [] => Ok(()),
+                    [suggestion] => write!(f, "; did you mean `{}`?", suggestion),
+                    _ => write!(f, "; did you mean one of `{}`?", suggestions.join("`, `")),
+                }
+            },
+            ConfigError::UnsupportedFormat { path } =>
+                write!(f, "unsupported config format for {} (expected .json, .toml, .yaml or .yml)", path.display()),
+        }
+    }
+}
+
+impl std::error::Error for ConfigError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            ConfigError::IO(err) => Some(err),
+            ConfigError::ObjectiveFile { error, .. } => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for ConfigError {
+    fn from(value: io::Error) -> Self {
+        ConfigError::IO(value)
+    }
+}
+
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    for (i, char_a) in a.chars().enumerate() {
+        let mut current = vec![i + 1; b.len() + 1];
+        for (j, char_b) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(char_a != *char_b);
+            current[j + 1] = substitution.min(previous[j + 1] + 1).min(current[j] + 1);
+        }
+        previous = current;
+    }
+    previous[b.len()]
+}
+
+static UNKNOWN_FIELD: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"unknown field `([^`]*)`(?:, expected (.*))?").unwrap());
+
+/// Reconhece a mensagem de `deny_unknown_fields` do serde e sugere, entre os
+/// campos esperados que ela lista, os mais próximos do campo digitado.
+fn unknown_field(message: &str, line: usize, column: usize) -> Option<ConfigError> {
+    let captures = UNKNOWN_FIELD.captures(message)?;
+    let field = captures[1].to_string();
+    let expected: Vec<&str> = captures
+        .get(2)
+        .map(|expected| expected.as_str().split('`').skip(1).step_by(2).collect())
+        .unwrap_or_default();
+
+    let max_distance = 2.max(field.len() / 3);
+    let mut suggestions: Vec<(usize, &str)> = expected
+        .into_iter()
+        .map(|known| (edit_distance(&field, known), known))
+        .filter(|(distance, _)| *distance <= max_distance)
+        .collect();
+    suggestions.sort();
+
+    Some(ConfigError::UnknownField {
+        field,
+        suggestions: suggestions.into_iter().map(|(_, known)| known.to_string()).collect(),
+        line,
+        column,
+    })
+}
+
+fn error_path(path: &serde_path_to_error::Path) -> Option<String> {
+    let path = path.to_string();
+    (path != ".").then_some(path)
+}
+
+impl ConfigError {
+    pub(crate) fn from_json(err: serde_json::Error, path: Option<String>) -> Self {
+        let (line, column) = (err.line(), err.column());
+        match err.classify() {
+            Category::Io => ConfigError::IO(err.into()),
+            Category::Syntax => ConfigError::Syntax { message: err.to_string(), line, column },
+            Category::Eof => ConfigError::Eof { line, column },
+            Category::Data => unknown_field(&err.to_string(), line, column)
+                .unwrap_or_else(|| ConfigError::Data { message: err.to_string(), path, line, column }),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq)]
+pub enum ConfigValidationError {
+    ZeroPopSize,
+    ZeroRuns,
+    ZeroGenerations,
+    ZeroDim,
+    MissingDim,
+    InvertedIntegerBounds { lower: u64, upper: u64 },
+    NonFiniteRealBounds { lower: f64, upper: f64 },
+    InvertedRealBounds { lower: f64, upper: f64 },
+    /// Limites por dimensão com um número de pares diferente de `dim`.
+    BoundsLengthMismatch { dim: usize, found: usize },
+    IncompatibleOperator { field: &'static str, operator: &'static str, encoding: &'static str },
+    RateOutOfRange { field: &'static str, rate: f64 },
+    DensityOutOfRange { density: f64 },
+    EmptyAlphabet { gene: usize },
+    ZeroTournamentSize,
+    ElitismExceedsPopSize { elitism: usize, pop_size: usize },
+    RunSeedsLengthMismatch { runs: usize, found: usize },
+    ZeroThreads,
+    NonPositiveSigmaFactor { c: f64 },
+    /// Lista de critérios de parada (ou grupo) vazia.
+    EmptyTerminationCriteria,
+    /// Critério repetido no mesmo nível de `termination`.
+    DuplicateTerminationCriterion { name: &'static str },
+}
+
+impl ConfigValidationError {
+    pub fn field(&self) -> &'static str {
+        match self {
+            ConfigValidationError::ZeroPopSize => "pop_size",
+            ConfigValidationError::ZeroRuns => "runs",
+            ConfigValidationError::ZeroGenerations => "generations",
+            ConfigValidationError::ZeroDim | ConfigValidationError::MissingDim => "encoding.dim",
+            ConfigValidationError::InvertedIntegerBounds { .. }
+            | ConfigValidationError::NonFiniteRealBounds { .. }
+            | ConfigValidationError::InvertedRealBounds { .. }
+            | ConfigValidationError::BoundsLengthMismatch { .. } => "encoding.bounds",
+            ConfigValidationError::IncompatibleOperator { field, .. }
+            | ConfigValidationError::RateOutOfRange { field, .. } => field,
+            ConfigValidationError::DensityOutOfRange { .. } => "encoding.density",
+            ConfigValidationError::EmptyAlphabet { .. } => "encoding.cardinalities",
+            ConfigValidationError::ZeroTournamentSize => "selection.size",
+            ConfigValidationError::ElitismExceedsPopSize { .. } => "elitism",
+            ConfigValidationError::RunSeedsLengthMismatch { .. } => "run_seeds",
+            ConfigValidationError::ZeroThreads => "threads",
+            ConfigValidationError::NonPositiveSigmaFactor { .. } => "fitness",
+            ConfigValidationError::EmptyTerminationCriteria
+            | ConfigValidationError::DuplicateTerminationCriterion { .. } => "termination.criteria",
+        }
+    }
+}
+
+impl fmt::Display for ConfigValidationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "`{}`: ", self.field())?;
+        match self {
+            ConfigValidationError::ZeroPopSize
+            | ConfigValidationError::ZeroRuns
+            | ConfigValidationError::ZeroGenerations
+            | ConfigValidationError::ZeroDim
+            | ConfigValidationError::ZeroThreads => write!(f, "must be greater than 0"),
+            ConfigValidationError::MissingDim => write!(f, "required when the objective does not determine it"),
+            ConfigValidationError::InvertedIntegerBounds { lower, upper } =>
+                write!(f, "lower bound {} is greater than upper bound {}", lower, upper),
+            ConfigValidationError::NonFiniteRealBounds { lower, upper } =>
+                write!(f, "bounds ({}, {}) must be finite", lower, upper),
+            ConfigValidationError::InvertedRealBounds { lower, upper } =>
+                write!(f, "lower bound {} is greater than upper bound {}", lower, upper),
+            ConfigValidationError::BoundsLengthMismatch { dim, found } =>
+                write!(f, "{} bound pairs given for dim {}", found, dim),
+            ConfigValidationError::IncompatibleOperator { operator, encoding, .. } =>
+                write!(f, "operator {} cannot be used with the {} encoding", operator, encoding),
+            ConfigValidationError::RateOutOfRange { rate, .. } =>
+                write!(f, "rate {} must be within [0, 1]", rate),
+            ConfigValidationError::DensityOutOfRange { density } =>
+                write!(f, "density {} must be within [0, 1]", density),
+            ConfigValidationError::EmptyAlphabet { gene } =>
+                write!(f, "gene {} has cardinality 0", gene),
+            ConfigValidationError::ZeroTournamentSize => write!(f, "must be greater than 0"),
+            ConfigValidationError::ElitismExceedsPopSize { elitism, pop_size } =>
+                write!(f, "{} elites do not fit in a population of {}", elitism, pop_size),
+            ConfigValidationError::RunSeedsLengthMismatch { runs, found } =>
+                write!(f, "{} seeds given for {} runs", found, runs),
+            ConfigValidationError::NonPositiveSigmaFactor { c } =>
+                write!(f, "sigma truncation factor {} must be greater than 0", c),
+            ConfigValidationError::EmptyTerminationCriteria => write!(f, "must not be empty"),
+            ConfigValidationError::DuplicateTerminationCriterion { name } =>
+                write!(f, "criterion {} appears more than once in the same group", name),
+        }
+    }
+}
+
+impl EncodingDTO {
+    pub fn dim(&self) -> Option<usize> {
+        match self {
+            EncodingDTO::Binary { dim, .. } => *dim,
+            EncodingDTO::IntegerPermutation { dim }
+            | EncodingDTO::Integer { dim, .. }
+            | EncodingDTO::Real { dim, .. } => Some(*dim),
+            EncodingDTO::Categorical { cardinalities } => Some(cardinalities.len()),
+        }
+    }
+}
+
+/// Deriva a semente da execução `run` a partir da semente mestra (SplitMix64).
+pub fn derive_run_seed(seed: u64, run: usize) -> u64 {
+    let mut z = seed.wrapping_add((run as u64).wrapping_add(1).wrapping_mul(0x9E37_79B9_7F4A_7C15));
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^ (z >> 31)
 }
 
-//@TODO: tratar melhor esse category, talvez fazer um erro customizado
 impl ConfigDTO {
-    pub fn from_reader<R: Read>(config_reader: R) -> Result<ConfigDTO, Category> {
+    /// Sementes de cada execução: `run_seeds` se fornecido, senão derivadas de
+    /// `seed`, senão sorteadas. Quem executa deve registrar o valor devolvido
+    /// para que a execução possa ser reproduzida.
+    pub fn effective_run_seeds(&self) -> Vec<u64> {
+        match (&self.run_seeds, self.seed) {
+            (Some(run_seeds), _) => run_seeds.clone(),
+            (None, Some(seed)) => (0..self.runs).map(|run| derive_run_seed(seed, run)).collect(),
+            (None, None) => (0..self.runs).map(|_| rand::random()).collect(),
+        }
+    }
+
+    /// Verifica a coerência dos valores, reportando todas as violações
+    /// encontradas e não apenas a primeira.
+    pub fn validate(&self) -> Result<(), Vec<ConfigValidationError>> {
+        let mut errors = Vec::new();
+
+        if self.pop_size == 0
+        { errors.push(ConfigValidationError::ZeroPopSize) }
+
+        if self.runs == 0
+        { errors.push(ConfigValidationError::ZeroRuns) }
+
+        if self.generations == 0
+        { errors.push(ConfigValidationError::ZeroGenerations) }
+
+        match self.encoding.dim() {
+            Some(0) => errors.push(ConfigValidationError::ZeroDim),
+            None if self.objective.is_none() => errors.push(ConfigValidationError::MissingDim),
+            _ => {}
+        }
+
+        match self.encoding {
+            EncodingDTO::Integer { dim, ref bounds } => {
+                if let Bounds::PerDimension(pairs) = bounds && pairs.len() != dim
+                { errors.push(ConfigValidationError::BoundsLengthMismatch { dim, found: pairs.len() }) }
+                for &(lower, upper) in bounds.pairs() {
+                    if lower > upper
+                    { errors.push(ConfigValidationError::InvertedIntegerBounds { lower, upper }) }
+                }
+            },
+            EncodingDTO::Real { dim, ref bounds } => {
+                if let Bounds::PerDimension(pairs) = bounds && pairs.len() != dim
+                { errors.push(ConfigValidationError::BoundsLengthMismatch { dim, found: pairs.len() }) }
+                for &(lower, upper) in bounds.pairs() {
+                    if !lower.is_finite() || !upper.is_finite() {
+                        errors.push(ConfigValidationError::NonFiniteRealBounds { lower, upper });
+                    } else if lower > upper {
+                        errors.push(ConfigValidationError::InvertedRealBounds { lower, upper });
+                    }
+                }
+            },
+            EncodingDTO::Binary { density: Some(density), .. } if !(0.0..=1.0).contains(&density) =>
+                errors.push(ConfigValidationError::DensityOutOfRange { density }),
+            EncodingDTO::Categorical { ref cardinalities } => {
+                if let Some(gene) = cardinalities.iter().position(|&cardinality| cardinality == 0)
+                { errors.push(ConfigValidationError::EmptyAlphabet { gene }) }
+            },
+            _ => {}
+        }
+
+        if let Some(SelectionDTO::Tournament { size: 0 }) = self.selection
+        { errors.push(ConfigValidationError::ZeroTournamentSize) }
+
+        if let Some(crossover) = &self.crossover {
+            if !crossover.operator.supports(&self.encoding) {
+                errors.push(ConfigValidationError::IncompatibleOperator {
+                    field: "crossover.operator",
+                    operator: crossover.operator.name(),
+                    encoding: self.encoding.name(),
+                });
+            }
+            if !(0.0..=1.0).contains(&crossover.rate)
+            { errors.push(ConfigValidationError::RateOutOfRange { field: "crossover.rate", rate: crossover.rate }) }
+        }
+
+        if let Some(mutation) = &self.mutation {
+            if !mutation.operator.supports(&self.encoding) {
+                errors.push(ConfigValidationError::IncompatibleOperator {
+                    field: "mutation.operator",
+                    operator: mutation.operator.name(),
+                    encoding: self.encoding.name(),
+                });
+            }
+            if !(0.0..=1.0).contains(&mutation.rate)
+            { errors.push(ConfigValidationError::RateOutOfRange { field: "mutation.rate", rate: mutation.rate }) }
+        }
+
+        if let Some(elitism) = self.elitism && elitism > self.pop_size
+        { errors.push(ConfigValidationError::ElitismExceedsPopSize { elitism, pop_size: self.pop_size }) }
+
+        if let Some(run_seeds) = &self.run_seeds && run_seeds.len() != self.runs
+        { errors.push(ConfigValidationError::RunSeedsLengthMismatch { runs: self.runs, found: run_seeds.len() }) }
+
+        if self.threads == Some(0)
+        { errors.push(ConfigValidationError::ZeroThreads) }
+
+        for step in self.fitness.iter().flatten() {
+            if let ScalingDTO::SigmaTruncation { c } = *step && (c <= 0.0 || c.is_nan())
+            { errors.push(ConfigValidationError::NonPositiveSigmaFactor { c }) }
+        }
+
+        if let Some(termination) = &self.termination {
+            termination.validate(&mut errors);
+        }
+
+        if errors.is_empty() { Ok(()) } else { Err(errors) }
+    }
+
+    /// Pipeline de fitness descrito em `fitness` (vazio quando ausente).
+    pub fn fitness_pipeline(&self) -> FitnessPipeline {
+        self.fitness.iter().flatten().map(Scaling::from).collect()
+    }
+
+    pub fn from_reader<R: Read>(config_reader: R) -> Result<ConfigDTO, ConfigError> {
         let mut de: Deserializer<serde_json::de::IoRead<R>> = Deserializer::from_reader(config_reader);
-        let config = ConfigDTO::deserialize(&mut de);
-        match config {
-            Err(e) => Err(e.classify()),
-            Ok(config) => Ok(config)
+        let config = serde_path_to_error::deserialize(&mut de).map_err(|err| {
+            let path = error_path(err.path());
+            ConfigError::from_json(err.into_inner(), path)
+        })?;
+        de.end().map_err(|err| ConfigError::from_json(err, None))?;
+        Ok(config)
+    }
+
+    #[cfg(feature = "toml")]
+    pub fn from_toml_reader<R: Read>(mut config_reader: R) -> Result<ConfigDTO, ConfigError> {
+        let mut text = String::new();
+        config_reader.read_to_string(&mut text)?;
+        serde_path_to_error::deserialize(toml::Deserializer::new(&text)).map_err(|err| {
+            let path = error_path(err.path());
+            let err = err.into_inner();
+            // o toml informa apenas o intervalo de bytes; converte para linha/coluna
+            let (line, column) = err
+                .span()
+                .map(|span| {
+                    let before = &text[..span.start];
+                    let line = before.matches('\n').count() + 1;
+                    let column = before.len() - before.rfind('\n').map_or(0, |i| i + 1) + 1;
+                    (line, column)
+                })
+                .unwrap_or((0, 0));
+            unknown_field(err.message(), line, column)
+                .unwrap_or_else(|| ConfigError::Data { message: err.message().to_string(), path, line, column })
+        })
+    }
+
+    #[cfg(feature = "yaml")]
+    pub fn from_yaml_reader<R: Read>(config_reader: R) -> Result<ConfigDTO, ConfigError> {
+        serde_path_to_error::deserialize(serde_yaml::Deserializer::from_reader(config_reader)).map_err(|err| {
+            let path = error_path(err.path());
+            let err = err.into_inner();
+            let (line, column) = err.location().map_or((0, 0), |location| (location.line(), location.column()));
+            unknown_field(&err.to_string(), line, column)
+                .unwrap_or_else(|| ConfigError::Data { message: err.to_string(), path, line, column })
+        })
+    }
+
+    /// Escolhe o formato pela extensão do arquivo: `.json`, `.toml` (feature
+    /// `toml`) ou `.yaml`/`.yml` (feature `yaml`).
+    pub fn from_path(path: impl AsRef<Path>) -> Result<ConfigDTO, ConfigError> {
+        let path = path.as_ref();
+        let extension = path.extension().and_then(|extension| extension.to_str());
+        match extension {
+            Some("json") => ConfigDTO::from_reader(File::open(path)?),
+            #[cfg(feature = "toml")]
+            Some("toml") => ConfigDTO::from_toml_reader(File::open(path)?),
+            #[cfg(feature = "yaml")]
+            Some("yaml" | "yml") => ConfigDTO::from_yaml_reader(File::open(path)?),
+            _ => Err(ConfigError::UnsupportedFormat { path: path.to_path_buf() }),
+        }
+    }
+}
+
+impl From<&CriterionDTO> for TerminationCriterion {
+    fn from(value: &CriterionDTO) -> Self {
+        match *value {
+            CriterionDTO::MaxGenerations(max) => TerminationCriterion::MaxGenerations(max),
+            CriterionDTO::TargetScore(target) => TerminationCriterion::TargetScore(target),
+            CriterionDTO::Stagnation(generations) => TerminationCriterion::Stagnation(generations),
+            CriterionDTO::MaxSeconds(max) => TerminationCriterion::MaxSeconds(max),
+        }
+    }
+}
+
+fn combine(mode: TerminationModeDTO, members: Vec<Termination>) -> Termination {
+    match mode {
+        TerminationModeDTO::Any => Termination::Any(members),
+        TerminationModeDTO::All => Termination::All(members),
+    }
+}
+
+impl TerminationDTO {
+    /// Critérios do nível superior: os campos nomeados, depois `criteria`.
+    fn entries(&self) -> Vec<TerminationEntryDTO> {
+        let named = [
+            self.max_generations.map(CriterionDTO::MaxGenerations),
+            self.target_score.map(CriterionDTO::TargetScore),
+            self.stagnation.map(CriterionDTO::Stagnation),
+            self.max_seconds.map(CriterionDTO::MaxSeconds),
+        ];
+        named.into_iter()
+            .flatten()
+            .map(TerminationEntryDTO::Criterion)
+            .chain(self.criteria.iter().flatten().cloned())
+            .collect()
+    }
+
+    fn validate(&self, errors: &mut Vec<ConfigValidationError>) {
+        fn check_duplicates<'a>(criteria: impl Iterator<Item = &'a CriterionDTO>, errors: &mut Vec<ConfigValidationError>) {
+            let names: Vec<&'static str> = criteria.map(|criterion| TerminationCriterion::from(criterion).name()).collect();
+            for (i, &name) in names.iter().enumerate() {
+                // reporta cada nome repetido uma vez, na segunda ocorrência
+                if names[..i].iter().filter(|&&other| other == name).count() == 1
+                { errors.push(ConfigValidationError::DuplicateTerminationCriterion { name }) }
+            }
+        }
+
+        if self.criteria.as_ref().is_some_and(Vec::is_empty)
+        { errors.push(ConfigValidationError::EmptyTerminationCriteria) }
+
+        let entries = self.entries();
+        let singles = entries.iter().filter_map(|entry| match entry {
+            TerminationEntryDTO::Criterion(criterion) => Some(criterion),
+            TerminationEntryDTO::Group(_) => None,
+        });
+        check_duplicates(singles, errors);
+
+        for entry in &entries {
+            if let TerminationEntryDTO::Group(group) = entry {
+                if group.criteria.is_empty()
+                { errors.push(ConfigValidationError::EmptyTerminationCriteria) }
+                check_duplicates(group.criteria.iter(), errors);
+            }
+        }
+    }
+
+    /// Critério de parada descrito pela configuração.
+    pub fn termination(&self) -> Termination {
+        let members = self.entries()
+            .iter()
+            .map(|entry| match entry {
+                TerminationEntryDTO::Criterion(criterion) => Termination::Criterion(criterion.into()),
+                TerminationEntryDTO::Group(group) => combine(
+                    group.mode,
+                    group.criteria.iter().map(|criterion| Termination::Criterion(criterion.into())).collect(),
+                ),
+            })
+            .collect();
+        combine(self.mode.unwrap_or_default(), members)
+    }
+}
+
+impl From<ScoreModeDTO> for ScoreMode {
+    fn from(value: ScoreModeDTO) -> Self {
+        match value {
+            ScoreModeDTO::FalseCount => ScoreMode::FalseCount,
+            ScoreModeDTO::LengthWeighted => ScoreMode::LengthWeighted,
+            ScoreModeDTO::LogLengthWeighted => ScoreMode::LogLengthWeighted,
+        }
+    }
+}
+
+impl From<&ScalingDTO> for Scaling {
+    fn from(value: &ScalingDTO) -> Self {
+        match *value {
+            ScalingDTO::Negate => Scaling::Negate,
+            ScalingDTO::SigmaTruncation { c } => Scaling::SigmaTruncation { c },
+            ScalingDTO::Rank => Scaling::Rank,
+        }
+    }
+}
+
+// ============ Conversão para os geradores de população ============
+
+#[derive(Debug, PartialEq)]
+pub enum GeneratorConversionError {
+    /// A configuração descreve outro encoding que não o do gerador pedido.
+    EncodingMismatch,
+    InvalidConfig(Vec<ConfigValidationError>),
+    InvalidGenerator(PopGeneratorError),
+}
+
+impl From<PopGeneratorError> for GeneratorConversionError {
+    fn from(value: PopGeneratorError) -> Self {
+        GeneratorConversionError::InvalidGenerator(value)
+    }
+}
+
+impl TryFrom<&ConfigDTO> for BinaryPopGenerator {
+    type Error = GeneratorConversionError;
+
+    fn try_from(config: &ConfigDTO) -> Result<Self, Self::Error> {
+        config.validate().map_err(GeneratorConversionError::InvalidConfig)?;
+        match config.encoding {
+            EncodingDTO::Binary { dim: Some(dim), density: None } => Ok(BinaryPopGenerator::new(dim, config.pop_size)),
+            EncodingDTO::Binary { dim: Some(dim), density: Some(density) } =>
+                Ok(BinaryPopGenerator::new_with_density(dim, config.pop_size, density)?),
+            // `validate` garante que há um objetivo para determinar `dim`
+            EncodingDTO::Binary { dim: None, density: None } => Ok(BinaryPopGenerator::sized(config.pop_size)),
+            EncodingDTO::Binary { dim: None, density: Some(density) } =>
+                Ok(BinaryPopGenerator::sized_with_density(config.pop_size, density)?),
+            _ => Err(GeneratorConversionError::EncodingMismatch),
+        }
+    }
+}
+
+impl TryFrom<&ConfigDTO> for IntPermPopGenerator {
+    type Error = GeneratorConversionError;
+
+    fn try_from(config: &ConfigDTO) -> Result<Self, Self::Error> {
+        config.validate().map_err(GeneratorConversionError::InvalidConfig)?;
+        match config.encoding {
+            EncodingDTO::IntegerPermutation { dim } => Ok(IntPermPopGenerator::new(dim, config.pop_size)),
+            _ => Err(GeneratorConversionError::EncodingMismatch),
+        }
+    }
+}
+
+impl TryFrom<&ConfigDTO> for IntegerPopGenerator {
+    type Error = GeneratorConversionError;
+
+    fn try_from(config: &ConfigDTO) -> Result<Self, Self::Error> {
+        config.validate().map_err(GeneratorConversionError::InvalidConfig)?;
+        match config.encoding {
+            EncodingDTO::Integer { dim, ref bounds } => Ok(IntegerPopGenerator::with_bounds(dim, bounds, config.pop_size)?),
+            _ => Err(GeneratorConversionError::EncodingMismatch),
+        }
+    }
+}
+
+impl TryFrom<&ConfigDTO> for RealPopGenerator {
+    type Error = GeneratorConversionError;
+
+    fn try_from(config: &ConfigDTO) -> Result<Self, Self::Error> {
+        config.validate().map_err(GeneratorConversionError::InvalidConfig)?;
+        match config.encoding {
+            EncodingDTO::Real { dim, ref bounds } => Ok(RealPopGenerator::with_bounds(dim, bounds, config.pop_size)?),
+            _ => Err(GeneratorConversionError::EncodingMismatch),
+        }
+    }
+}
+
+impl TryFrom<&ConfigDTO> for CategoricalPopGenerator {
+    type Error = GeneratorConversionError;
+
+    fn try_from(config: &ConfigDTO) -> Result<Self, Self::Error> {
+        config.validate().map_err(GeneratorConversionError::InvalidConfig)?;
+        match &config.encoding {
+            EncodingDTO::Categorical { cardinalities } => {
+                let domain = CategoricalDomain::new(cardinalities.clone())?;
+                Ok(CategoricalPopGenerator::new(domain, config.pop_size))
+            },
+            _ => Err(GeneratorConversionError::EncodingMismatch),
         }
     }
 }
@@ -34,6 +911,7 @@ impl ConfigDTO {
 #[cfg(test)]
 mod config_tests {
     use std::io::Cursor;
+    use crate::alg::termination::RunProgress;
     use super::*;
 
     fn assert_json_generates_expected_config(json: &str, expected_config: ConfigDTO) {
@@ -47,7 +925,7 @@ mod config_tests {
     }
     
     #[test]
-    fn test_create_config_from_reader() -> () {
+    fn test_create_config_from_reader() {
         let config_json = r#"{
             "encoding": {
                 "type": "Integer",
@@ -59,14 +937,691 @@ mod config_tests {
             "generations": 200
         }"#;
         let expected_config = ConfigDTO {
-            encoding: EncodingDTO::Integer {
-                dim: 12,
-                bounds: (0, 10)
-            },
+            encoding: EncodingDTO::Integer { dim: 12, bounds: (0, 10).into() },
             pop_size: 30,
             runs: 10,
-            generations: 200, 
+            generations: 200,
+            ..valid_config()
         };
         assert_json_generates_expected_config(config_json, expected_config);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_config_converts_into_matching_generator() {
+        let config = ConfigDTO {
+            encoding: EncodingDTO::Integer { dim: 12, bounds: (0, 10).into() },
+            pop_size: 30,
+            ..valid_config()
+        };
+        let pop_generator = IntegerPopGenerator::try_from(&config).unwrap();
+        assert_eq!(pop_generator.dimension(), 12);
+        assert_eq!(pop_generator.domain(0).bounds(), (0, 10));
+        assert_eq!(pop_generator.gen_pop().get_individuals().len(), 30);
+
+        match BinaryPopGenerator::try_from(&config) {
+            Err(GeneratorConversionError::EncodingMismatch) => {},
+            Err(err) => panic!("Expected GeneratorConversionError::EncodingMismatch, got {:?}", err),
+            _ => panic!("Expected an error")
+        }
+    }
+
+    #[test]
+    fn test_config_conversion_rejects_invalid_bounds() {
+        let config = ConfigDTO { encoding: EncodingDTO::Integer { dim: 3, bounds: (10, 0).into() }, ..valid_config() };
+        assert_eq!(
+            IntegerPopGenerator::try_from(&config).err(),
+            Some(GeneratorConversionError::InvalidConfig(vec![
+                ConfigValidationError::InvertedIntegerBounds { lower: 10, upper: 0 }
+            ]))
+        );
+    }
+
+    fn valid_config() -> ConfigDTO {
+        ConfigDTO {
+            encoding: EncodingDTO::Real { dim: 4, bounds: (-1.0, 1.0).into() },
+            objective: None,
+            pop_size: 20,
+            runs: 3,
+            generations: 50,
+            selection: None,
+            crossover: None,
+            mutation: None,
+            elitism: None,
+            termination: None,
+            seed: None,
+            run_seeds: None,
+            threads: None,
+            fitness: None,
+        }
+    }
+
+    fn assert_single_violation(config: ConfigDTO, expected: ConfigValidationError) {
+        assert_eq!(config.validate(), Err(vec![expected]));
+    }
+
+    #[test]
+    fn test_validate_valid_config() {
+        assert_eq!(valid_config().validate(), Ok(()));
+    }
+
+    #[test]
+    fn test_validate_zero_pop_size() {
+        assert_single_violation(ConfigDTO { pop_size: 0, ..valid_config() }, ConfigValidationError::ZeroPopSize);
+    }
+
+    #[test]
+    fn test_validate_zero_runs() {
+        assert_single_violation(ConfigDTO { runs: 0, ..valid_config() }, ConfigValidationError::ZeroRuns);
+    }
+
+    #[test]
+    fn test_validate_zero_generations() {
+        assert_single_violation(ConfigDTO { generations: 0, ..valid_config() }, ConfigValidationError::ZeroGenerations);
+    }
+
+    #[test]
+    fn test_validate_zero_dim() {
+        let config = ConfigDTO { encoding: EncodingDTO::Binary { dim: Some(0), density: None }, ..valid_config() };
+        assert_single_violation(config, ConfigValidationError::ZeroDim);
+    }
+
+    #[test]
+    fn test_validate_inverted_integer_bounds() {
+        let config = ConfigDTO { encoding: EncodingDTO::Integer { dim: 2, bounds: (5, 1).into() }, ..valid_config() };
+        assert_single_violation(config, ConfigValidationError::InvertedIntegerBounds { lower: 5, upper: 1 });
+    }
+
+    #[test]
+    fn test_validate_non_finite_real_bounds() {
+        let config = ConfigDTO { encoding: EncodingDTO::Real { dim: 2, bounds: (0.0, f64::INFINITY).into() }, ..valid_config() };
+        assert_single_violation(config, ConfigValidationError::NonFiniteRealBounds { lower: 0.0, upper: f64::INFINITY });
+
+        let config = ConfigDTO { encoding: EncodingDTO::Real { dim: 2, bounds: (f64::NAN, 1.0).into() }, ..valid_config() };
+        match config.validate().unwrap_err().as_slice() {
+            [ConfigValidationError::NonFiniteRealBounds { lower, upper: 1.0 }] if lower.is_nan() => {},
+            errors => panic!("Expected ConfigValidationError::NonFiniteRealBounds, got {:?}", errors),
+        }
+    }
+
+    #[test]
+    fn test_validate_inverted_real_bounds() {
+        let config = ConfigDTO { encoding: EncodingDTO::Real { dim: 2, bounds: (1.0, -1.0).into() }, ..valid_config() };
+        assert_single_violation(config, ConfigValidationError::InvertedRealBounds { lower: 1.0, upper: -1.0 });
+    }
+
+    #[test]
+    fn test_validate_reports_every_violation() {
+        let config = ConfigDTO {
+            encoding: EncodingDTO::Integer { dim: 0, bounds: (3, 2).into() },
+            pop_size: 0,
+            generations: 0,
+            ..valid_config()
+        };
+        let errors = config.validate().unwrap_err();
+        assert_eq!(errors, vec![
+            ConfigValidationError::ZeroPopSize,
+            ConfigValidationError::ZeroGenerations,
+            ConfigValidationError::ZeroDim,
+            ConfigValidationError::InvertedIntegerBounds { lower: 3, upper: 2 },
+        ]);
+        let fields: Vec<&str> = errors.iter().map(|err| err.field()).collect();
+        assert_eq!(fields, vec!["pop_size", "generations", "encoding.dim", "encoding.bounds"]);
+    }
+
+    fn parse_error(json: &str) -> ConfigError {
+        ConfigDTO::from_reader(Cursor::new(json)).unwrap_err()
+    }
+
+    #[test]
+    fn test_truncated_config() {
+        let config_json = r#"{
+            "encoding": {"type": "Binary", "dim": 10},
+            "pop_size": 30,"#;
+        match parse_error(config_json) {
+            ConfigError::Eof { line: 3, .. } => {},
+            err => panic!("Expected ConfigError::Eof at line 3, got {:?}", err),
+        }
+    }
+
+    #[test]
+    fn test_wrong_typed_field() {
+        let config_json = r#"{
+            "encoding": {"type": "Binary", "dim": 10},
+            "pop_size": "thirty",
+            "runs": 1,
+            "generations": 10
+        }"#;
+        match parse_error(config_json) {
+            ConfigError::Data { path: Some(path), line: 3, column, .. } => {
+                assert_eq!(path, "pop_size");
+                assert!(column > 0);
+            },
+            err => panic!("Expected ConfigError::Data for pop_size, got {:?}", err),
+        }
+    }
+
+    #[test]
+    fn test_missing_field() {
+        let config_json = r#"{
+            "pop_size": 30,
+            "runs": 1,
+            "generations": 10
+        }"#;
+        match parse_error(config_json) {
+            ConfigError::Data { message, line: 5, .. } => assert!(message.contains("encoding")),
+            err => panic!("Expected ConfigError::Data for a missing field, got {:?}", err),
+        }
+    }
+
+    #[test]
+    fn test_malformed_config() {
+        match parse_error(r#"{"pop_size": 30 "runs": 1}"#) {
+            ConfigError::Syntax { line: 1, column, .. } => assert!(column > 0),
+            err => panic!("Expected ConfigError::Syntax, got {:?}", err),
+        }
+    }
+
+    #[test]
+    fn test_config_from_unknown_extension() {
+        match ConfigDTO::from_path("/nonexistent/config.ini") {
+            Err(ConfigError::UnsupportedFormat { path }) => assert_eq!(path, PathBuf::from("/nonexistent/config.ini")),
+            Err(err) => panic!("Expected ConfigError::UnsupportedFormat, got {:?}", err),
+            _ => panic!("Expected an error")
+        }
+    }
+
+    #[test]
+    #[cfg(all(feature = "toml", feature = "yaml"))]
+    fn test_same_config_in_every_format() {
+        let json = r#"{
+            "encoding": {"type": "Integer", "dim": 12, "bounds": [0, 10]},
+            "pop_size": 30,
+            "runs": 10,
+            "generations": 200,
+            "selection": {"type": "Tournament", "size": 3},
+            "crossover": {"operator": {"type": "Uniform", "ratio": 0.5}, "rate": 0.9},
+            "mutation": {"operator": {"type": "Creep", "step": 2}, "rate": 0.1},
+            "termination": {"stagnation": 40}
+        }"#;
+        let toml = r#"
+            # comentários são bem-vindos
+            pop_size = 30
+            runs = 10
+            generations = 200
+
+            [encoding]
+            type = "Integer"
+            dim = 12
+            bounds = [0, 10]
+
+            [selection]
+            type = "Tournament"
+            size = 3
+
+            [crossover]
+            operator = { type = "Uniform", ratio = 0.5 }
+            rate = 0.9
+
+            [mutation]
+            operator = { type = "Creep", step = 2 }
+            rate = 0.1
+
+            [termination]
+            stagnation = 40
+        "#;
+        let yaml = r#"
+            encoding: {type: Integer, dim: 12, bounds: [0, 10]}
+            pop_size: 30
+            runs: 10
+            generations: 200
+            selection: {type: Tournament, size: 3}
+            crossover:
+              operator: {type: Uniform, ratio: 0.5}
+              rate: 0.9
+            mutation:
+              operator: {type: Creep, step: 2}
+              rate: 0.1
+            termination:
+              stagnation: 40
+        "#;
+        let from_json = ConfigDTO::from_reader(Cursor::new(json)).unwrap();
+        assert_eq!(ConfigDTO::from_toml_reader(Cursor::new(toml)).unwrap(), from_json);
+        assert_eq!(ConfigDTO::from_yaml_reader(Cursor::new(yaml)).unwrap(), from_json);
+    }
+
+    #[test]
+    #[cfg(feature = "toml")]
+    fn test_toml_error_location() {
+        let toml = "pop_size = \"thirty\"\nruns = 1\n";
+        match ConfigDTO::from_toml_reader(Cursor::new(toml)) {
+            Err(ConfigError::Data { line: 1, column: 12, .. }) => {},
+            Err(err) => panic!("Expected ConfigError::Data at line 1, got {:?}", err),
+            _ => panic!("Expected an error")
+        }
+    }
+
+    #[test]
+    fn test_config_from_missing_path() {
+        match ConfigDTO::from_path("/nonexistent/config.json") {
+            Err(ConfigError::IO(_)) => {},
+            Err(err) => panic!("Expected ConfigError::IO, got {:?}", err),
+            _ => panic!("Expected an error")
+        }
+    }
+
+    #[test]
+    fn test_operator_settings_round_trip() {
+        let config_json = r#"{
+            "encoding": {"type": "IntegerPermutation", "dim": 20},
+            "pop_size": 50,
+            "runs": 2,
+            "generations": 300,
+            "selection": {"type": "Tournament", "size": 3},
+            "crossover": {"operator": {"type": "Pmx"}, "rate": 0.9},
+            "mutation": {"operator": {"type": "Swap"}, "rate": 0.1},
+            "elitism": 2,
+            "termination": {"target_score": 0.0, "stagnation": 50}
+        }"#;
+        let config = ConfigDTO::from_reader(Cursor::new(config_json)).unwrap();
+        assert_eq!(config.selection, Some(SelectionDTO::Tournament { size: 3 }));
+        assert_eq!(config.crossover, Some(CrossoverDTO { operator: CrossoverOperatorDTO::Pmx, rate: 0.9 }));
+        assert_eq!(config.mutation, Some(MutationDTO { operator: MutationOperatorDTO::Swap, rate: 0.1 }));
+        assert_eq!(config.elitism, Some(2));
+        assert_eq!(config.termination, Some(TerminationDTO {
+            target_score: Some(0.0),
+            stagnation: Some(50),
+            ..TerminationDTO::default()
+        }));
+        assert_eq!(config.validate(), Ok(()));
+
+        let serialized = serde_json::to_string(&config).unwrap();
+        assert_eq!(ConfigDTO::from_reader(Cursor::new(serialized)).unwrap(), config);
+    }
+
+    #[test]
+    fn test_validate_incompatible_operators() {
+        let config = ConfigDTO {
+            encoding: EncodingDTO::Binary { dim: Some(10), density: None },
+            crossover: Some(CrossoverDTO { operator: CrossoverOperatorDTO::Pmx, rate: 0.9 }),
+            mutation: Some(MutationDTO { operator: MutationOperatorDTO::Gaussian { sigma: 0.1 }, rate: 1.0 }),
+            ..valid_config()
+        };
+        let errors = config.validate().unwrap_err();
+        assert_eq!(errors, vec![
+            ConfigValidationError::IncompatibleOperator { field: "crossover.operator", operator: "Pmx", encoding: "Binary" },
+            ConfigValidationError::IncompatibleOperator { field: "mutation.operator", operator: "Gaussian", encoding: "Binary" },
+        ]);
+        let message = errors[0].to_string();
+        assert!(message.contains("Pmx") && message.contains("Binary"));
+    }
+
+    #[test]
+    fn test_validate_operator_parameters() {
+        let config = ConfigDTO {
+            selection: Some(SelectionDTO::Tournament { size: 0 }),
+            crossover: Some(CrossoverDTO { operator: CrossoverOperatorDTO::Sbx { eta: 2.0 }, rate: 1.5 }),
+            elitism: Some(21),
+            ..valid_config()
+        };
+        assert_eq!(config.validate(), Err(vec![
+            ConfigValidationError::ZeroTournamentSize,
+            ConfigValidationError::RateOutOfRange { field: "crossover.rate", rate: 1.5 },
+            ConfigValidationError::ElitismExceedsPopSize { elitism: 21, pop_size: 20 },
+        ]));
+    }
+
+    #[test]
+    fn test_validate_missing_dim_without_objective() {
+        let config = ConfigDTO { encoding: EncodingDTO::Binary { dim: None, density: None }, ..valid_config() };
+        assert_single_violation(config, ConfigValidationError::MissingDim);
+
+        let config = ConfigDTO {
+            encoding: EncodingDTO::Binary { dim: None, density: None },
+            objective: Some(ObjectiveDTO::Sat { cnf_path: PathBuf::from("formula.cnf"), score_mode: None }),
+            ..valid_config()
+        };
+        assert_eq!(config.validate(), Ok(()));
+    }
+
+    #[test]
+    fn test_binary_density_round_trip() {
+        let config = ConfigDTO::from_reader(Cursor::new(r#"{"encoding": {"type": "Binary", "dim": 8, "density": 0.05}}"#)).unwrap();
+        assert_eq!(config.encoding, EncodingDTO::Binary { dim: Some(8), density: Some(0.05) });
+        let json = serde_json::to_string(&config).unwrap();
+        assert_eq!(ConfigDTO::from_reader(Cursor::new(json)).unwrap(), config);
+
+        let config = ConfigDTO::from_reader(Cursor::new(r#"{"encoding": {"type": "Binary", "dim": 8}}"#)).unwrap();
+        assert_eq!(config.encoding, EncodingDTO::Binary { dim: Some(8), density: None });
+        let json = serde_json::to_string(&config).unwrap();
+        assert!(!json.contains("density"));
+        assert_eq!(ConfigDTO::from_reader(Cursor::new(json)).unwrap(), config);
+    }
+
+    #[test]
+    fn test_binary_density_validation_and_conversion() {
+        let config = ConfigDTO { encoding: EncodingDTO::Binary { dim: Some(8), density: Some(1.5) }, ..valid_config() };
+        assert_single_violation(config, ConfigValidationError::DensityOutOfRange { density: 1.5 });
+
+        let config = ConfigDTO { encoding: EncodingDTO::Binary { dim: Some(8), density: Some(1.0) }, ..valid_config() };
+        let pop_generator = BinaryPopGenerator::try_from(&config).unwrap();
+        assert_eq!(pop_generator.density(), &BinaryDensity::Uniform(1.0));
+        assert!(pop_generator.gen_pop().get_individuals().iter().all(|ind| ind.count_ones() == 8));
+    }
+
+    fn assert_unknown_field(json: &str, expected_field: &str, expected_suggestions: &[&str]) {
+        match parse_error(json) {
+            ConfigError::UnknownField { field, suggestions, .. } => {
+                assert_eq!(field, expected_field);
+                assert_eq!(suggestions, expected_suggestions);
+            },
+            err => panic!("Expected ConfigError::UnknownField, got {:?}", err),
+        }
+    }
+
+    #[test]
+    fn test_unknown_top_level_field() {
+        let config_json = r#"{
+            "encoding": {"type": "Binary", "dim": 10},
+            "popsize": 30,
+            "runs": 1,
+            "generations": 10
+        }"#;
+        assert_unknown_field(config_json, "popsize", &["pop_size"]);
+        assert!(parse_error(config_json).to_string().contains("did you mean `pop_size`?"));
+    }
+
+    #[test]
+    fn test_misspelled_encoding_field() {
+        let config_json = r#"{
+            "encodng": {"type": "Binary", "dim": 10},
+            "pop_size": 30,
+            "runs": 1,
+            "generations": 10
+        }"#;
+        assert_unknown_field(config_json, "encodng", &["encoding"]);
+    }
+
+    #[test]
+    fn test_unknown_nested_field_without_suggestion() {
+        let config_json = r#"{
+            "encoding": {"type": "Binary", "dim": 10, "colour": "blue"},
+            "pop_size": 30,
+            "runs": 1,
+            "generations": 10
+        }"#;
+        assert_unknown_field(config_json, "colour", &[]);
+    }
+
+    #[test]
+    fn test_minimal_config_resolves_to_defaults() {
+        let config_json = r#"{
+            "encoding": {"type": "Binary", "dim": 50},
+            "objective": {"type": "Sat", "cnf_path": "formula.cnf"}
+        }"#;
+        let config = ConfigDefaults::resolve(ConfigDTO::from_reader(Cursor::new(config_json)).unwrap());
+        assert_eq!(config, ConfigDTO {
+            encoding: EncodingDTO::Binary { dim: Some(50), density: None },
+            objective: Some(ObjectiveDTO::Sat { cnf_path: PathBuf::from("formula.cnf"), score_mode: None }),
+            pop_size: 100,
+            runs: 1,
+            generations: 100,
+            selection: Some(SelectionDTO::Tournament { size: 2 }),
+            crossover: Some(CrossoverDTO { operator: CrossoverOperatorDTO::Uniform { ratio: 0.5 }, rate: 0.9 }),
+            mutation: Some(MutationDTO { operator: MutationOperatorDTO::BitFlip, rate: 0.02 }),
+            elitism: Some(1),
+            termination: Some(TerminationDTO::default()),
+            ..valid_config()
+        });
+        assert_eq!(config.validate(), Ok(()));
+    }
+
+    #[test]
+    fn test_explicit_values_override_defaults() {
+        let config_json = r#"{
+            "encoding": {"type": "IntegerPermutation", "dim": 8},
+            "pop_size": 12,
+            "mutation": {"operator": {"type": "Inversion"}, "rate": 0.3},
+            "elitism": 0
+        }"#;
+        let config = ConfigDefaults::resolve(ConfigDTO::from_reader(Cursor::new(config_json)).unwrap());
+        assert_eq!(config.pop_size, 12);
+        assert_eq!(config.mutation, Some(MutationDTO { operator: MutationOperatorDTO::Inversion, rate: 0.3 }));
+        assert_eq!(config.elitism, Some(0));
+        assert_eq!(config.crossover, Some(CrossoverDTO { operator: CrossoverOperatorDTO::Ox, rate: 0.9 }));
+    }
+
+    #[test]
+    fn test_resolved_config_round_trips() {
+        let config_json = r#"{"encoding": {"type": "Real", "dim": 4, "bounds": [-1.0, 1.0]}}"#;
+        let config = ConfigDefaults::resolve(ConfigDTO::from_reader(Cursor::new(config_json)).unwrap());
+        let serialized = serde_json::to_string(&config).unwrap();
+        assert_eq!(ConfigDTO::from_reader(Cursor::new(serialized)).unwrap(), config);
+    }
+
+    #[test]
+    fn test_mutation_default_waits_for_dim() {
+        let config_json = r#"{
+            "encoding": {"type": "Binary"},
+            "objective": {"type": "Sat", "cnf_path": "formula.cnf"}
+        }"#;
+        let config = ConfigDefaults::resolve(ConfigDTO::from_reader(Cursor::new(config_json)).unwrap());
+        assert_eq!(config.mutation, None);
+    }
+
+    #[test]
+    fn test_explicit_run_seeds_are_honored_in_order() {
+        let config = ConfigDTO { runs: 3, seed: Some(7), run_seeds: Some(vec![30, 10, 20]), ..valid_config() };
+        assert_eq!(config.validate(), Ok(()));
+        assert_eq!(config.effective_run_seeds(), vec![30, 10, 20]);
+    }
+
+    #[test]
+    fn test_run_seeds_derived_from_seed() {
+        let config = ConfigDTO { runs: 4, seed: Some(42), ..valid_config() };
+        let seeds = config.effective_run_seeds();
+        assert_eq!(seeds, config.effective_run_seeds());
+        assert_eq!(seeds, (0..4).map(|run| derive_run_seed(42, run)).collect::<Vec<u64>>());
+
+        let mut distinct = seeds.clone();
+        distinct.sort();
+        distinct.dedup();
+        assert_eq!(distinct.len(), 4);
+        assert_ne!(seeds, ConfigDTO { runs: 4, seed: Some(43), ..valid_config() }.effective_run_seeds());
+    }
+
+    #[test]
+    fn test_run_seeds_drawn_when_unseeded() {
+        let config = ConfigDTO { runs: 5, ..valid_config() };
+        assert_eq!(config.effective_run_seeds().len(), 5);
+    }
+
+    #[test]
+    fn test_validate_run_seeds_length() {
+        let config = ConfigDTO { runs: 3, run_seeds: Some(vec![1, 2]), ..valid_config() };
+        assert_single_violation(config, ConfigValidationError::RunSeedsLengthMismatch { runs: 3, found: 2 });
+    }
+
+    #[test]
+    fn test_validate_zero_threads() {
+        assert_single_violation(ConfigDTO { threads: Some(0), ..valid_config() }, ConfigValidationError::ZeroThreads);
+        assert_eq!(ConfigDTO { threads: Some(4), ..valid_config() }.validate(), Ok(()));
+    }
+
+    #[test]
+    fn test_fitness_pipeline_from_config() {
+        let config_json = r#"{
+            "encoding": {"type": "Binary", "dim": 10},
+            "fitness": [{"type": "Negate"}, {"type": "SigmaTruncation", "c": 2.0}, {"type": "Rank"}]
+        }"#;
+        let config = ConfigDTO::from_reader(Cursor::new(config_json)).unwrap();
+        assert_eq!(config.validate(), Ok(()));
+        assert_eq!(
+            config.fitness_pipeline(),
+            FitnessPipeline::new(vec![Scaling::Negate, Scaling::SigmaTruncation { c: 2.0 }, Scaling::Rank])
+        );
+        assert_eq!(valid_config().fitness_pipeline(), FitnessPipeline::default());
+    }
+
+    #[test]
+    fn test_unknown_fitness_step() {
+        let config_json = r#"{
+            "encoding": {"type": "Binary", "dim": 10},
+            "fitness": [{"type": "Negate"}, {"type": "Windowing"}]
+        }"#;
+        match parse_error(config_json) {
+            ConfigError::Data { message, path, .. } => {
+                assert_eq!(path.as_deref(), Some("fitness[1].type"));
+                assert!(message.contains("unknown variant `Windowing`"), "{}", message);
+                assert!(message.contains("`Negate`, `SigmaTruncation`, `Rank`"), "{}", message);
+            },
+            err => panic!("Expected ConfigError::Data, got {:?}", err),
+        }
+    }
+
+    #[test]
+    fn test_validate_sigma_truncation_factor() {
+        let config = ConfigDTO { fitness: Some(vec![ScalingDTO::SigmaTruncation { c: 0.0 }]), ..valid_config() };
+        assert_single_violation(config, ConfigValidationError::NonPositiveSigmaFactor { c: 0.0 });
+    }
+
+    #[test]
+    fn test_parse_nested_termination() {
+        let config = ConfigDTO::from_reader(Cursor::new(r#"{
+            "encoding": {"type": "Binary", "dim": 10},
+            "termination": {"mode": "any", "criteria": [
+                {"target_score": 0.0},
+                {"mode": "all", "criteria": [{"stagnation": 50}, {"max_generations": 1000}]}
+            ]}
+        }"#)).unwrap();
+        let termination = config.termination.as_ref().unwrap();
+        assert_eq!(termination.criteria, Some(vec![
+            TerminationEntryDTO::Criterion(CriterionDTO::TargetScore(0.0)),
+            TerminationEntryDTO::Group(TerminationGroupDTO {
+                mode: TerminationModeDTO::All,
+                criteria: vec![CriterionDTO::Stagnation(50), CriterionDTO::MaxGenerations(1000)],
+            }),
+        ]));
+        assert_eq!(config.validate(), Ok(()));
+        assert_eq!(termination.termination(), Termination::Any(vec![
+            Termination::Criterion(TerminationCriterion::TargetScore(0.0)),
+            Termination::All(vec![
+                Termination::Criterion(TerminationCriterion::Stagnation(50)),
+                Termination::Criterion(TerminationCriterion::MaxGenerations(1000)),
+            ]),
+        ]));
+
+        let serialized = serde_json::to_string(&config).unwrap();
+        assert_eq!(ConfigDTO::from_reader(Cursor::new(serialized)).unwrap(), config);
+    }
+
+    #[test]
+    fn test_named_termination_fields_are_criteria() {
+        let termination = TerminationDTO { target_score: Some(0.0), stagnation: Some(50), ..TerminationDTO::default() };
+        assert_eq!(termination.termination(), Termination::Any(vec![
+            Termination::Criterion(TerminationCriterion::TargetScore(0.0)),
+            Termination::Criterion(TerminationCriterion::Stagnation(50)),
+        ]));
+
+        let termination = TerminationDTO { mode: Some(TerminationModeDTO::All), ..termination };
+        assert!(matches!(termination.termination(), Termination::All(members) if members.len() == 2));
+    }
+
+    #[test]
+    fn test_reported_criterion_matches_config_entry() {
+        let config = ConfigDTO::from_reader(Cursor::new(r#"{
+            "encoding": {"type": "Binary", "dim": 10},
+            "termination": {"criteria": [{"max_seconds": 60.0}, {"max_generations": 3}]}
+        }"#)).unwrap();
+        let termination = config.termination.unwrap().termination();
+
+        let mut progress = RunProgress::default();
+        let mut reason = None;
+        while reason.is_none() {
+            progress.advance(1.0, 10 * (progress.generation + 1), 0.5);
+            reason = termination.check(&progress);
+        }
+        let reason = reason.unwrap();
+        assert_eq!(reason.criterion, "max_generations");
+        assert_eq!((reason.generation, reason.evaluations), (3, 30));
+    }
+
+    #[test]
+    fn test_validate_termination_criteria() {
+        let termination = TerminationDTO { criteria: Some(vec![]), ..TerminationDTO::default() };
+        let config = ConfigDTO { termination: Some(termination), ..valid_config() };
+        assert_single_violation(config, ConfigValidationError::EmptyTerminationCriteria);
+
+        let group = TerminationGroupDTO { mode: TerminationModeDTO::All, criteria: vec![] };
+        let termination = TerminationDTO { criteria: Some(vec![TerminationEntryDTO::Group(group)]), ..TerminationDTO::default() };
+        let config = ConfigDTO { termination: Some(termination), ..valid_config() };
+        assert_single_violation(config, ConfigValidationError::EmptyTerminationCriteria);
+
+        // campo nomeado repetido na lista, no mesmo nível
+        let termination = TerminationDTO {
+            stagnation: Some(10),
+            criteria: Some(vec![
+                TerminationEntryDTO::Criterion(CriterionDTO::Stagnation(20)),
+                TerminationEntryDTO::Criterion(CriterionDTO::Stagnation(30)),
+            ]),
+            ..TerminationDTO::default()
+        };
+        let config = ConfigDTO { termination: Some(termination), ..valid_config() };
+        assert_single_violation(config, ConfigValidationError::DuplicateTerminationCriterion { name: "stagnation" });
+
+        let group = TerminationGroupDTO {
+            mode: TerminationModeDTO::All,
+            criteria: vec![CriterionDTO::MaxGenerations(5), CriterionDTO::MaxGenerations(9)],
+        };
+        let termination = TerminationDTO {
+            max_generations: Some(100),
+            criteria: Some(vec![TerminationEntryDTO::Group(group)]),
+            ..TerminationDTO::default()
+        };
+        let config = ConfigDTO { termination: Some(termination), ..valid_config() };
+        assert_single_violation(config, ConfigValidationError::DuplicateTerminationCriterion { name: "max_generations" });
+    }
+
+    #[test]
+    fn test_categorical_encoding_config() {
+        let config_json = r#"{"encoding": {"type": "Categorical", "cardinalities": [3, 2, 4]}}"#;
+        let config = ConfigDTO::from_reader(Cursor::new(config_json)).unwrap();
+        assert_eq!(config.encoding, EncodingDTO::Categorical { cardinalities: vec![3, 2, 4] });
+        assert_eq!(config.encoding.dim(), Some(3));
+
+        let resolved = ConfigDefaults::resolve(config);
+        assert_eq!(resolved.validate(), Ok(()));
+        let serialized = serde_json::to_string(&resolved).unwrap();
+        assert_eq!(ConfigDTO::from_reader(Cursor::new(serialized)).unwrap(), resolved);
+
+        let generator = CategoricalPopGenerator::try_from(&resolved).unwrap();
+        assert_eq!(generator.domain().cardinalities(), &[3, 2, 4]);
+
+        let config = ConfigDTO { encoding: EncodingDTO::Categorical { cardinalities: vec![3, 0] }, ..valid_config() };
+        assert_single_violation(config, ConfigValidationError::EmptyAlphabet { gene: 1 });
+    }
+
+    #[test]
+    fn test_per_dimension_bounds_config() {
+        let config_json = r#"{"encoding": {"type": "Real", "dim": 2, "bounds": [[-1.0, 1.0], [0.0, 100.0]]}}"#;
+        let config = ConfigDTO::from_reader(Cursor::new(config_json)).unwrap();
+        assert_eq!(config.encoding, EncodingDTO::Real { dim: 2, bounds: vec![(-1.0, 1.0), (0.0, 100.0)].into() });
+        assert_eq!(config.validate(), Ok(()));
+        assert_eq!(RealPopGenerator::try_from(&config).unwrap().dimension(), 2);
+
+        // a forma escalar continua aceita e é serializada como antes
+        let config_json = r#"{"encoding": {"type": "Integer", "dim": 3, "bounds": [0, 10]}}"#;
+        let config = ConfigDTO::from_reader(Cursor::new(config_json)).unwrap();
+        assert_eq!(config.encoding, EncodingDTO::Integer { dim: 3, bounds: Bounds::Uniform((0, 10)) });
+        let serialized = serde_json::to_value(&config.encoding).unwrap();
+        assert_eq!(serialized["bounds"], serde_json::json!([0, 10]));
+
+        let config = ConfigDTO {
+            encoding: EncodingDTO::Integer { dim: 3, bounds: vec![(0, 1), (5, 2)].into() },
+            ..valid_config()
+        };
+        assert_eq!(config.validate(), Err(vec![
+            ConfigValidationError::BoundsLengthMismatch { dim: 3, found: 2 },
+            ConfigValidationError::InvertedIntegerBounds { lower: 5, upper: 2 },
+        ]));
+    }
+}
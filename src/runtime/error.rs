@@ -0,0 +1,185 @@
+use std::{fmt, io, path::{Path, PathBuf}};
+use crate::alg::{BuildError, DimensionMismatch};
+use crate::alg::objectives::FormulaParsingError;
+use super::dto::{ConfigDTO, ConfigError, ConfigValidationError};
+use super::objective::{build_objective_with_registry, ObjectiveInstance, ObjectiveRegistry};
+
+/// Código de saída para configurações ilegíveis ou inválidas.
+pub const EXIT_CONFIG_ERROR: u8 = 2;
+/// Código de saída para falhas ao carregar ou montar a execução.
+pub const EXIT_RUNTIME_ERROR: u8 = 3;
+
+/// Falhas do runtime, cada uma com o arquivo ou campo que a causou.
+#[derive(Debug)]
+pub enum RuntimeError {
+    /// Configuração em `path` que não pôde ser interpretada.
+    Config { path: PathBuf, error: ConfigError },
+    /// Configuração em `path` com valores incoerentes.
+    InvalidConfig { path: PathBuf, errors: Vec<ConfigValidationError> },
+    /// Fórmula em `path` (referenciada pela configuração ou lida diretamente).
+    Formula { path: PathBuf, error: FormulaParsingError },
+    /// Componentes do GA incompatíveis entre si.
+    Build(BuildError),
+    Io { path: PathBuf, error: io::Error },
+}
+
+impl RuntimeError {
+    pub fn exit_code(&self) -> u8 {
+        match self {
+            RuntimeError::Config { .. } | RuntimeError::InvalidConfig { .. } => EXIT_CONFIG_ERROR,
+            RuntimeError::Formula { .. } | RuntimeError::Build(_) | RuntimeError::Io { .. } => EXIT_RUNTIME_ERROR,
+        }
+    }
+
+    /// Separa das falhas de configuração as de leitura de arquivos, que
+    /// `ConfigError` também carrega.
+    fn from_config(path: &Path, error: ConfigError) -> Self {
+        match error {
+            ConfigError::IO(error) => RuntimeError::Io { path: path.to_path_buf(), error },
+            ConfigError::ObjectiveFile { path, error } => RuntimeError::Formula { path, error },
+            error => RuntimeError::Config { path: path.to_path_buf(), error },
+        }
+    }
+}
+
+impl fmt::Display for RuntimeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RuntimeError::Config { path, error } => write!(f, "{}: {}", path.display(), error),
+            RuntimeError::InvalidConfig { path, errors } => {
+                write!(f, "{}: invalid config", path.display())?;
+                errors.iter().try_for_each(|error| write!(f, "\n  {}", error))
+            },
+            RuntimeError::Formula { path, error } => write!(f, "{}: {}", path.display(), error),
+            RuntimeError::Build(error) => write!(f, "could not build the genetic algorithm: {}", error),
+            RuntimeError::Io { path, error } => write!(f, "{}: {}", path.display(), error),
+        }
+    }
+}
+
+impl std::error::Error for RuntimeError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            RuntimeError::Config { error, .. } => Some(error),
+            RuntimeError::InvalidConfig { .. } => None,
+            RuntimeError::Formula { error, .. } => Some(error),
+            RuntimeError::Build(error) => Some(error),
+            RuntimeError::Io { error, .. } => Some(error),
+        }
+    }
+}
+
+impl From<BuildError> for RuntimeError {
+    fn from(value: BuildError) -> Self {
+        RuntimeError::Build(value)
+    }
+}
+
+impl From<DimensionMismatch> for RuntimeError {
+    fn from(value: DimensionMismatch) -> Self {
+        RuntimeError::Build(value.into())
+    }
+}
+
+/// Lê a configuração em `path`, carrega o objetivo que ela descreve e valida
+/// o resultado.
+pub fn load_config(path: impl AsRef<Path>) -> Result<(ConfigDTO, Option<ObjectiveInstance>), RuntimeError> {
+    load_config_with_registry(path, &ObjectiveRegistry::default())
+}
+
+/// Como [`load_config`], resolvendo objetivos `Registered` em `registry`.
+pub fn load_config_with_registry(path: impl AsRef<Path>, registry: &ObjectiveRegistry) -> Result<(ConfigDTO, Option<ObjectiveInstance>), RuntimeError> {
+    let path = path.as_ref();
+    let mut config = ConfigDTO::from_path(path).map_err(|error| RuntimeError::from_config(path, error))?;
+    let objective = build_objective_with_registry(&mut config, registry).map_err(|error| RuntimeError::from_config(path, error))?;
+    config.validate().map_err(|errors| RuntimeError::InvalidConfig { path: path.to_path_buf(), errors })?;
+    Ok((config, objective))
+}
+
+#[cfg(test)]
+mod runtime_error_tests {
+    use std::error::Error;
+    use std::io::Write;
+    use tempfile::NamedTempFile;
+    use super::*;
+
+    fn config_file(contents: &str) -> NamedTempFile {
+        let mut file = tempfile::Builder::new().suffix(".json").tempfile().unwrap();
+        file.write_all(contents.as_bytes()).unwrap();
+        file
+    }
+
+    #[test]
+    fn test_load_valid_config() {
+        let file = config_file(r#"{"encoding": {"type": "Binary", "dim": 4}}"#);
+        let (config, objective) = load_config(file.path()).unwrap();
+        assert_eq!(config.encoding.dim(), Some(4));
+        assert!(objective.is_none());
+    }
+
+    #[test]
+    fn test_missing_config_is_io_error() {
+        let err = load_config("/nonexistent/config.json").unwrap_err();
+        assert!(matches!(err, RuntimeError::Io { .. }), "{:?}", err);
+        assert_eq!(err.exit_code(), EXIT_RUNTIME_ERROR);
+        assert!(err.to_string().contains("/nonexistent/config.json"));
+        assert!(err.source().unwrap().downcast_ref::<io::Error>().is_some());
+    }
+
+    #[test]
+    fn test_malformed_config_is_config_error() {
+        let file = config_file(r#"{"encoding": {"type": "Binary", "dim": 4}, "pop_size": "thirty"}"#);
+        let err = load_config(file.path()).unwrap_err();
+        assert!(matches!(err, RuntimeError::Config { error: ConfigError::Data { .. }, .. }), "{:?}", err);
+        assert_eq!(err.exit_code(), EXIT_CONFIG_ERROR);
+        assert!(err.to_string().contains("pop_size"));
+        assert!(err.source().unwrap().downcast_ref::<ConfigError>().is_some());
+    }
+
+    #[test]
+    fn test_invalid_config_lists_fields() {
+        let file = config_file(r#"{"encoding": {"type": "Binary", "dim": 4}, "pop_size": 0, "runs": 0}"#);
+        let err = load_config(file.path()).unwrap_err();
+        assert!(matches!(&err, RuntimeError::InvalidConfig { errors, .. } if errors.len() == 2), "{:?}", err);
+        assert_eq!(err.exit_code(), EXIT_CONFIG_ERROR);
+        let message = err.to_string();
+        assert!(message.contains("pop_size") && message.contains("runs"), "{}", message);
+    }
+
+    #[test]
+    fn test_missing_formula_is_formula_error() {
+        let file = config_file(r#"{"encoding": {"type": "Binary"}, "objective": {"type": "Sat", "cnf_path": "/nonexistent/formula.cnf"}}"#);
+        let err = load_config(file.path()).unwrap_err();
+        match &err {
+            RuntimeError::Formula { path, error: FormulaParsingError::IO(_) } => assert_eq!(path, Path::new("/nonexistent/formula.cnf")),
+            other => panic!("Expected RuntimeError::Formula, got {:?}", other),
+        }
+        assert_eq!(err.exit_code(), EXIT_RUNTIME_ERROR);
+        assert!(err.to_string().contains("/nonexistent/formula.cnf"));
+        // RuntimeError -> FormulaParsingError -> io::Error
+        let formula_error = err.source().unwrap();
+        assert!(formula_error.source().unwrap().downcast_ref::<io::Error>().is_some());
+    }
+
+    #[test]
+    fn test_dimension_mismatch_is_build_error() {
+        let err = RuntimeError::from(DimensionMismatch { expected: 3, found: 5, source: "SATObjective" });
+        assert_eq!(err.exit_code(), EXIT_RUNTIME_ERROR);
+        assert!(err.to_string().contains("SATObjective"));
+        // RuntimeError -> BuildError -> DimensionMismatch
+        let build_error = err.source().unwrap();
+        assert!(build_error.downcast_ref::<BuildError>().is_some());
+        assert!(build_error.source().unwrap().downcast_ref::<DimensionMismatch>().is_some());
+    }
+
+    #[test]
+    fn test_missing_dimension_reaches_runtime() {
+        fn build() -> Result<(), RuntimeError> {
+            Err(BuildError::MissingDimension)?
+        }
+        let err = build().unwrap_err();
+        assert!(matches!(err, RuntimeError::Build(BuildError::MissingDimension)), "{:?}", err);
+        assert_eq!(err.exit_code(), EXIT_RUNTIME_ERROR);
+        assert!(err.to_string().contains("has no dim"));
+    }
+}
@@ -0,0 +1,461 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::fs::File;
+use std::io::Read;
+use std::path::Path;
+use serde::Serialize;
+use serde_json::Value;
+use super::dto::{ConfigDTO, ConfigError};
+use crate::alg::scaling::average_ranks;
+
+/// Grade de parâmetros para varreduras. Cada parâmetro é um caminho na
+/// configuração (ex. `mutation.rate`, `selection.size`) com uma lista de
+/// valores; a expansão produz o produto cartesiano deles.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ParameterGrid {
+    params: Vec<(String, Vec<Value>)>,
+}
+
+/// Uma combinação da grade e a configuração resultante.
+#[derive(Debug, Clone, PartialEq)]
+pub struct GridPoint {
+    pub assignments: Vec<(String, Value)>,
+    pub config: ConfigDTO,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum GridError {
+    /// O caminho não existe na configuração base (campo desconhecido, seção
+    /// ausente ou variante sem esse campo).
+    InvalidPath { path: String },
+    /// O valor atribuído não corresponde ao tipo do campo.
+    InvalidValue { path: String, message: String },
+}
+
+impl fmt::Display for GridError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GridError::InvalidPath { path } => write!(f, "`{}` is not a field of the base config", path),
+            GridError::InvalidValue { path, message } => write!(f, "invalid value for `{}`: {}", path, message),
+        }
+    }
+}
+
+impl std::error::Error for GridError {}
+
+impl ParameterGrid {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn param<V: Into<Value>>(mut self, path: &str, values: impl IntoIterator<Item = V>) -> Self {
+        self.params.push((path.to_string(), values.into_iter().map(Into::into).collect()));
+        self
+    }
+
+    /// Número de combinações da grade.
+    pub fn len(&self) -> usize {
+        self.params.iter().map(|(_, values)| values.len()).product()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Expande a grade sobre `base`. O último parâmetro varia mais rápido.
+    /// Todas as combinações são construídas de antemão, então um caminho ou
+    /// valor inválido falha antes de qualquer execução.
+    pub fn expand(&self, base: &ConfigDTO) -> Result<Vec<GridPoint>, GridError> {
+        let base = serde_json::to_value(base).expect("ConfigDTO is always serializable");
+        let mut points = Vec::with_capacity(self.len());
+        let mut indices = vec![0; self.params.len()];
+
+        for _ in 0..self.len() {
+            let assignments: Vec<(String, Value)> = self.params
+                .iter()
+                .zip(&indices)
+                .map(|((path, values), &index)| (path.clone(), values[index].clone()))
+                .collect();
+
+            let mut value = base.clone();
+            for (path, assigned) in &assignments {
+                *field_mut(&mut value, path)? = assigned.clone();
+            }
+            let config = serde_path_to_error::deserialize(value)
+                .map_err(|err| invalid_value(err, &assignments))?;
+            points.push(GridPoint { assignments, config });
+
+            for (index, (_, values)) in indices.iter_mut().zip(&self.params).rev() {
+                *index += 1;
+                if *index < values.len()
+                { break }
+                *index = 0;
+            }
+        }
+
+        Ok(points)
+    }
+}
+
+/// Atribui o erro ao parâmetro atribuído dentro do campo que falhou. Enums
+/// com tag interna bufferizam o conteúdo, então o caminho do serde pode parar
+/// no enum (`selection`) em vez de no campo (`selection.size`).
+fn invalid_value(err: serde_path_to_error::Error<serde_json::Error>, assignments: &[(String, Value)]) -> GridError {
+    let error_path = err.path().to_string();
+    let path = assignments
+        .iter()
+        .map(|(path, _)| path)
+        .find(|path| *path == &error_path || path.starts_with(&format!("{}.", error_path)))
+        .cloned()
+        .unwrap_or(error_path);
+    GridError::InvalidValue { path, message: err.into_inner().to_string() }
+}
+
+fn field_mut<'a>(value: &'a mut Value, path: &str) -> Result<&'a mut Value, GridError> {
+    path.split('.')
+        .try_fold(value, |value, key| value.as_object_mut().and_then(|object| object.get_mut(key)))
+        .ok_or_else(|| GridError::InvalidPath { path: path.to_string() })
+}
+
+// ============ Normalização de scores entre instâncias ============
+
+/// Melhores valores conhecidos por instância, lidos de um arquivo como
+/// `best_known.json`: `{"uf50-01": 0, "flat30-7": 2}`.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct BestKnown(pub HashMap<String, f64>);
+
+impl BestKnown {
+    pub fn from_reader<R: Read>(reader: R) -> Result<Self, ConfigError> {
+        serde_json::from_reader(reader).map(BestKnown).map_err(|err| ConfigError::from_json(err, None))
+    }
+
+    pub fn from_path(path: impl AsRef<Path>) -> Result<Self, ConfigError> {
+        Self::from_reader(File::open(path)?)
+    }
+
+    pub fn get(&self, instance: &str) -> Option<f64> {
+        self.0.get(instance).copied()
+    }
+}
+
+/// O que fazer com instâncias ausentes de [`BestKnown`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MissingBestKnown {
+    /// A instância fica fora da agregação e é listada em `skipped`.
+    Skip,
+    Error,
+}
+
+/// Normalização aplicada aos scores (cláusulas falsas, menor é melhor) de
+/// cada instância antes de agregá-los entre instâncias.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ScoreNormalization {
+    Raw,
+    /// Fração das cláusulas da instância.
+    ClauseCount,
+    /// `(score - melhor) / max(|melhor|, 1)`: 0 é o melhor conhecido.
+    BestKnown { best_known: BestKnown, missing: MissingBestKnown },
+    /// Posto entre as configurações na mesma instância (1 é a melhor; empates
+    /// recebem o posto médio).
+    Rank,
+}
+
+impl ScoreNormalization {
+    pub fn name(&self) -> &'static str {
+        match self {
+            ScoreNormalization::Raw => "raw",
+            ScoreNormalization::ClauseCount => "clause_count",
+            ScoreNormalization::BestKnown { .. } => "best_known",
+            ScoreNormalization::Rank => "rank",
+        }
+    }
+}
+
+/// Scores de uma instância, um por configuração da varredura, na mesma ordem
+/// em todas as instâncias.
+#[derive(Debug, Clone, PartialEq)]
+pub struct InstanceScores {
+    pub instance: String,
+    pub num_clauses: usize,
+    pub scores: Vec<f64>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum NormalizationError {
+    MissingBestKnown { instance: String },
+    /// Instância com número de scores diferente da primeira.
+    ScoreCountMismatch { instance: String, expected: usize, found: usize },
+    ZeroClauses { instance: String },
+    /// Nenhuma instância entrou na média (ex. todas puladas por falta de
+    /// melhor valor conhecido).
+    NoInstances { skipped: usize },
+}
+
+impl fmt::Display for NormalizationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            NormalizationError::MissingBestKnown { instance } =>
+                write!(f, "no best known value for instance `{}`", instance),
+            NormalizationError::ScoreCountMismatch { instance, expected, found } =>
+                write!(f, "instance `{}` has {} scores, expected {}", instance, found, expected),
+            NormalizationError::ZeroClauses { instance } =>
+                write!(f, "instance `{}` has no clauses to normalize by", instance),
+            NormalizationError::NoInstances { skipped } =>
+                write!(f, "no instance left to summarize ({} skipped)", skipped),
+        }
+    }
+}
+
+impl std::error::Error for NormalizationError {}
+
+/// Resumo da varredura: média dos scores normalizados de cada configuração.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct NormalizedSummary {
+    pub normalization: &'static str,
+    /// Uma média por configuração (menor é melhor).
+    pub means: Vec<f64>,
+    pub instances: Vec<String>,
+    pub skipped: Vec<String>,
+}
+
+impl NormalizedSummary {
+    /// Índices das configurações, da melhor para a pior.
+    pub fn ranking(&self) -> Vec<usize> {
+        let mut order: Vec<usize> = (0..self.means.len()).collect();
+        order.sort_by(|&a, &b| self.means[a].total_cmp(&self.means[b]));
+        order
+    }
+}
+
+/// Normaliza os scores de uma instância; `None` se ela deve ser pulada.
+pub fn normalize_instance(result: &InstanceScores, normalization: &ScoreNormalization) -> Result<Option<Vec<f64>>, NormalizationError> {
+    let normalized = match normalization {
+        ScoreNormalization::Raw => result.scores.clone(),
+        ScoreNormalization::ClauseCount => {
+            if result.num_clauses == 0
+            { return Err(NormalizationError::ZeroClauses { instance: result.instance.clone() }) }
+            result.scores.iter().map(|score| score / result.num_clauses as f64).collect()
+        },
+        ScoreNormalization::BestKnown { best_known, missing } => match best_known.get(&result.instance) {
+            Some(best) => result.scores.iter().map(|score| (score - best) / best.abs().max(1.0)).collect(),
+            None if *missing == MissingBestKnown::Skip => return Ok(None),
+            None => return Err(NormalizationError::MissingBestKnown { instance: result.instance.clone() }),
+        },
+        ScoreNormalization::Rank => average_ranks(&result.scores),
+    };
+    Ok(Some(normalized))
+}
+
+/// Normaliza cada instância e agrega por configuração. Erro se nenhuma
+/// instância sobra para a média, em vez de médias zeradas.
+pub fn summarize_normalized(results: &[InstanceScores], normalization: &ScoreNormalization) -> Result<NormalizedSummary, NormalizationError> {
+    let configs = results.first().map_or(0, |result| result.scores.len());
+    let mut summary = NormalizedSummary {
+        normalization: normalization.name(),
+        means: vec![0.0; configs],
+        instances: Vec::new(),
+        skipped: Vec::new(),
+    };
+
+    for result in results {
+        if result.scores.len() != configs
+        { return Err(NormalizationError::ScoreCountMismatch { instance: result.instance.clone(), expected: configs, found: result.scores.len() }) }
+
+        match normalize_instance(result, normalization)? {
+            Some(normalized) => {
+                summary.means.iter_mut().zip(normalized).for_each(|(sum, score)| *sum += score);
+                summary.instances.push(result.instance.clone());
+            },
+            None => summary.skipped.push(result.instance.clone()),
+        }
+    }
+
+    let used = summary.instances.len();
+    if used == 0
+    { return Err(NormalizationError::NoInstances { skipped: summary.skipped.len() }) }
+
+    summary.means.iter_mut().for_each(|sum| *sum /= used as f64);
+    Ok(summary)
+}
+
+#[cfg(test)]
+mod grid_tests {
+    use std::io::Cursor;
+    use crate::runtime::dto::{MutationDTO, MutationOperatorDTO, SelectionDTO};
+    use super::*;
+
+    fn base_config() -> ConfigDTO {
+        ConfigDTO::from_reader(Cursor::new(r#"{
+            "encoding": {"type": "Binary", "dim": 10},
+            "selection": {"type": "Tournament", "size": 2},
+            "mutation": {"operator": {"type": "BitFlip"}, "rate": 0.1}
+        }"#)).unwrap()
+    }
+
+    #[test]
+    fn test_grid_expansion() {
+        let grid = ParameterGrid::new()
+            .param("mutation.rate", [0.01, 0.05, 0.1])
+            .param("selection.size", [2, 4]);
+        assert_eq!(grid.len(), 6);
+
+        let points = grid.expand(&base_config()).unwrap();
+        assert_eq!(points.len(), 6);
+
+        let combinations: Vec<(f64, usize)> = points
+            .iter()
+            .map(|point| {
+                let Some(MutationDTO { rate, .. }) = point.config.mutation else { panic!("mutation was removed") };
+                let Some(SelectionDTO::Tournament { size }) = point.config.selection else { panic!("selection changed variant") };
+                (rate, size)
+            })
+            .collect();
+        assert_eq!(combinations, vec![(0.01, 2), (0.01, 4), (0.05, 2), (0.05, 4), (0.1, 2), (0.1, 4)]);
+        assert_eq!(points[3].assignments, vec![("mutation.rate".to_string(), 0.05.into()), ("selection.size".to_string(), 4.into())]);
+    }
+
+    #[test]
+    fn test_grid_overrides_nested_enum() {
+        let grid = ParameterGrid::new().param("mutation.operator", [serde_json::json!({"type": "Swap"})]);
+        let points = grid.expand(&base_config()).unwrap();
+        assert_eq!(points[0].config.mutation, Some(MutationDTO { operator: MutationOperatorDTO::Swap, rate: 0.1 }));
+        assert_eq!(points[0].config.encoding, base_config().encoding);
+    }
+
+    #[test]
+    fn test_grid_empty() {
+        let points = ParameterGrid::new().expand(&base_config()).unwrap();
+        assert_eq!(points, vec![GridPoint { assignments: vec![], config: base_config() }]);
+
+        let grid = ParameterGrid::new().param::<f64>("mutation.rate", []);
+        assert!(grid.is_empty());
+        assert_eq!(grid.expand(&base_config()), Ok(vec![]));
+    }
+
+    #[test]
+    fn test_grid_invalid_path() {
+        let grid = ParameterGrid::new().param("mutation.ratee", [0.1]);
+        assert_eq!(grid.expand(&base_config()), Err(GridError::InvalidPath { path: "mutation.ratee".to_string() }));
+
+        // Roulette não tem `size`
+        let base = ConfigDTO { selection: Some(SelectionDTO::Roulette), ..base_config() };
+        let grid = ParameterGrid::new().param("selection.size", [2]);
+        assert_eq!(grid.expand(&base), Err(GridError::InvalidPath { path: "selection.size".to_string() }));
+
+        // seção ausente na configuração base
+        let base = ConfigDTO { crossover: None, ..base_config() };
+        let grid = ParameterGrid::new().param("crossover.rate", [0.5]);
+        assert!(matches!(grid.expand(&base), Err(GridError::InvalidPath { .. })));
+    }
+
+    #[test]
+    fn test_grid_invalid_value() {
+        let grid = ParameterGrid::new().param("selection.size", ["four"]);
+        match grid.expand(&base_config()) {
+            Err(GridError::InvalidValue { path, .. }) => assert_eq!(path, "selection.size"),
+            other => panic!("Expected GridError::InvalidValue, got {:?}", other),
+        }
+    }
+}
+
+#[cfg(test)]
+mod normalization_tests {
+    use std::io::Cursor;
+    use super::*;
+
+    fn instance(name: &str, num_clauses: usize, scores: &[f64]) -> InstanceScores {
+        InstanceScores { instance: name.to_string(), num_clauses, scores: scores.to_vec() }
+    }
+
+    // a configuração 0 vai melhor na instância grande, a 1 nas pequenas
+    fn results() -> Vec<InstanceScores> {
+        vec![
+            instance("big", 1000, &[40.0, 60.0]),
+            instance("small-1", 10, &[3.0, 1.0]),
+            instance("small-2", 10, &[2.0, 1.0]),
+        ]
+    }
+
+    fn best_known() -> BestKnown {
+        BestKnown::from_reader(Cursor::new(r#"{"big": 20, "small-1": 0, "small-2": 1}"#)).unwrap()
+    }
+
+    #[test]
+    fn test_clause_count_normalization() {
+        let normalized = normalize_instance(&results()[0], &ScoreNormalization::ClauseCount).unwrap();
+        assert_eq!(normalized, Some(vec![0.04, 0.06]));
+
+        let empty = instance("empty", 0, &[1.0]);
+        assert_eq!(
+            normalize_instance(&empty, &ScoreNormalization::ClauseCount),
+            Err(NormalizationError::ZeroClauses { instance: "empty".to_string() })
+        );
+    }
+
+    #[test]
+    fn test_best_known_normalization() {
+        let normalization = ScoreNormalization::BestKnown { best_known: best_known(), missing: MissingBestKnown::Error };
+        let normalized: Vec<Vec<f64>> = results().iter().map(|result| normalize_instance(result, &normalization).unwrap().unwrap()).collect();
+        assert_eq!(normalized, vec![vec![1.0, 2.0], vec![3.0, 1.0], vec![1.0, 0.0]]);
+    }
+
+    #[test]
+    fn test_rank_normalization_averages_ties() {
+        let result = instance("ties", 5, &[3.0, 1.0, 3.0, 0.0]);
+        assert_eq!(normalize_instance(&result, &ScoreNormalization::Rank).unwrap(), Some(vec![3.5, 2.0, 3.5, 1.0]));
+    }
+
+    #[test]
+    fn test_rank_normalization_with_nan_terminates() {
+        let result = instance("nan", 5, &[1.0, f64::NAN, 2.0]);
+        assert_eq!(normalize_instance(&result, &ScoreNormalization::Rank).unwrap(), Some(vec![1.0, 3.0, 2.0]));
+    }
+
+    #[test]
+    fn test_missing_best_known() {
+        let mut results = results();
+        results.push(instance("unknown", 10, &[5.0, 5.0]));
+
+        let skip = ScoreNormalization::BestKnown { best_known: best_known(), missing: MissingBestKnown::Skip };
+        let summary = summarize_normalized(&results, &skip).unwrap();
+        assert_eq!(summary.skipped, vec!["unknown".to_string()]);
+        assert_eq!(summary.instances.len(), 3);
+        assert_eq!(summary.means, vec![5.0 / 3.0, 1.0]);
+
+        let error = ScoreNormalization::BestKnown { best_known: best_known(), missing: MissingBestKnown::Error };
+        assert_eq!(
+            summarize_normalized(&results, &error),
+            Err(NormalizationError::MissingBestKnown { instance: "unknown".to_string() })
+        );
+
+        let unknown = vec![instance("unknown", 10, &[5.0, 5.0]), instance("other", 10, &[1.0, 2.0])];
+        assert_eq!(summarize_normalized(&unknown, &skip), Err(NormalizationError::NoInstances { skipped: 2 }));
+        assert_eq!(summarize_normalized(&[], &ScoreNormalization::Raw), Err(NormalizationError::NoInstances { skipped: 0 }));
+    }
+
+    #[test]
+    fn test_normalization_changes_ranking() {
+        let raw = summarize_normalized(&results(), &ScoreNormalization::Raw).unwrap();
+        assert_eq!(raw.ranking(), vec![0, 1]);
+
+        for normalization in [ScoreNormalization::ClauseCount, ScoreNormalization::Rank] {
+            let summary = summarize_normalized(&results(), &normalization).unwrap();
+            assert_eq!(summary.ranking(), vec![1, 0], "{}", normalization.name());
+        }
+
+        let summary = summarize_normalized(&results(), &ScoreNormalization::Rank).unwrap();
+        assert_eq!(summary.normalization, "rank");
+        let report = serde_json::to_value(&summary).unwrap();
+        assert_eq!(report["normalization"], "rank");
+    }
+
+    #[test]
+    fn test_score_count_mismatch() {
+        let mut results = results();
+        results[2].scores.pop();
+        assert_eq!(
+            summarize_normalized(&results, &ScoreNormalization::Raw),
+            Err(NormalizationError::ScoreCountMismatch { instance: "small-2".to_string(), expected: 2, found: 1 })
+        );
+    }
+}
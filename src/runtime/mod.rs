@@ -1,2 +1,4 @@
 pub mod dto;
-use dto::ConfigDTO;
\ No newline at end of file
+pub mod error;
+pub mod experiments;
+pub mod objective;
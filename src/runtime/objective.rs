@@ -0,0 +1,537 @@
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fs::File;
+use std::sync::Arc;
+use crate::alg::ScalarObjective;
+use crate::alg::objectives::{Formula, FormulaParsingError, SATObjective, ScoreMode};
+use crate::population::{BinaryEncoding, CategoricalEncoding, Encoding, IntPermEncoding, IntegerEncoding, RealEncoding};
+use super::dto::{ConfigDTO, ConfigError, EncodingDTO, ObjectiveDTO};
+
+pub type RegisteredObjective<E> = Box<dyn ScalarObjective<E> + Send + Sync>;
+
+/// Objetivo registrado, etiquetado pelo encoding que ele avalia.
+pub enum RegisteredScalar {
+    Binary(RegisteredObjective<BinaryEncoding>),
+    IntegerPermutation(RegisteredObjective<IntPermEncoding>),
+    Integer(RegisteredObjective<IntegerEncoding>),
+    Real(RegisteredObjective<RealEncoding>),
+    Categorical(RegisteredObjective<CategoricalEncoding>),
+}
+
+impl RegisteredScalar {
+    /// Nome do encoding como em [`EncodingDTO::name`].
+    pub fn encoding_name(&self) -> &'static str {
+        match self {
+            RegisteredScalar::Binary(_) => "Binary",
+            RegisteredScalar::IntegerPermutation(_) => "IntegerPermutation",
+            RegisteredScalar::Integer(_) => "Integer",
+            RegisteredScalar::Real(_) => "Real",
+            RegisteredScalar::Categorical(_) => "Categorical",
+        }
+    }
+
+    pub fn required_dimension(&self) -> Option<usize> {
+        match self {
+            RegisteredScalar::Binary(objective) => objective.required_dimension(),
+            RegisteredScalar::IntegerPermutation(objective) => objective.required_dimension(),
+            RegisteredScalar::Integer(objective) => objective.required_dimension(),
+            RegisteredScalar::Real(objective) => objective.required_dimension(),
+            RegisteredScalar::Categorical(objective) => objective.required_dimension(),
+        }
+    }
+}
+
+/// Encodings que a configuração sabe descrever e que, portanto, podem ter
+/// objetivos registrados.
+pub trait RegistryEncoding: Encoding + Sized + 'static {
+    fn tag(objective: RegisteredObjective<Self>) -> RegisteredScalar;
+    fn untag(objective: RegisteredScalar) -> Option<RegisteredObjective<Self>>;
+}
+
+macro_rules! registry_encoding {
+    ($encoding:ty, $variant:ident) => {
+        impl RegistryEncoding for $encoding {
+            fn tag(objective: RegisteredObjective<Self>) -> RegisteredScalar {
+                RegisteredScalar::$variant(objective)
+            }
+
+            fn untag(objective: RegisteredScalar) -> Option<RegisteredObjective<Self>> {
+                match objective {
+                    RegisteredScalar::$variant(objective) => Some(objective),
+                    _ => None,
+                }
+            }
+        }
+    };
+}
+
+registry_encoding!(BinaryEncoding, Binary);
+registry_encoding!(IntPermEncoding, IntegerPermutation);
+registry_encoding!(IntegerEncoding, Integer);
+registry_encoding!(RealEncoding, Real);
+registry_encoding!(CategoricalEncoding, Categorical);
+
+pub enum ObjectiveInstance {
+    Sat(SATObjective),
+    Registered { name: String, objective: RegisteredScalar },
+}
+
+impl fmt::Debug for ObjectiveInstance {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ObjectiveInstance::Sat(objective) => f.debug_tuple("Sat").field(objective).finish(),
+            ObjectiveInstance::Registered { name, objective } => f
+                .debug_struct("Registered")
+                .field("name", name)
+                .field("encoding", &objective.encoding_name())
+                .finish_non_exhaustive(),
+        }
+    }
+}
+
+impl ObjectiveInstance {
+    /// O objetivo como `ScalarObjective<E>`; `None` se ele avalia outro
+    /// encoding.
+    pub fn into_scalar<E: RegistryEncoding>(self) -> Option<Box<dyn ScalarObjective<E>>> {
+        let objective = match self {
+            ObjectiveInstance::Sat(objective) => RegisteredScalar::Binary(Box::new(objective)),
+            ObjectiveInstance::Registered { objective, .. } => objective,
+        };
+        E::untag(objective).map(|objective| objective as Box<dyn ScalarObjective<E>>)
+    }
+}
+
+impl ObjectiveDTO {
+    pub fn name(&self) -> &'static str {
+        match self {
+            ObjectiveDTO::Sat { .. } => "Sat",
+            ObjectiveDTO::Registered { .. } => "Registered",
+        }
+    }
+}
+
+type ObjectiveFactory = dyn Fn() -> RegisteredScalar + Send + Sync;
+
+/// Objetivos construídos pela aplicação (fórmulas, matrizes de distância...)
+/// referenciados na configuração por nome, via `{"type": "Registered",
+/// "name": ...}`. Cada objetivo avalia um dos encodings da configuração, que
+/// precisa coincidir com o configurado. Cada uso chama a fábrica, então
+/// execuções paralelas recebem instâncias próprias. Clonar só incrementa um
+/// contador de referências.
+#[derive(Clone, Default)]
+pub struct ObjectiveRegistry {
+    factories: Arc<BTreeMap<String, Arc<ObjectiveFactory>>>,
+}
+
+impl fmt::Debug for ObjectiveRegistry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_set().entries(self.names()).finish()
+    }
+}
+
+impl ObjectiveRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registra `factory` sob `name`, substituindo um registro anterior. O
+    /// encoding é deduzido do objetivo (ou dado com `register::<E, _, _>`).
+    pub fn register<E, O, F>(&mut self, name: impl Into<String>, factory: F) -> &mut Self
+    where
+        E: RegistryEncoding,
+        O: ScalarObjective<E> + Send + Sync + 'static,
+        F: Fn() -> O + Send + Sync + 'static,
+    {
+        let factory = move || E::tag(Box::new(factory()));
+        Arc::make_mut(&mut self.factories).insert(name.into(), Arc::new(factory));
+        self
+    }
+
+    /// Nomes registrados, em ordem alfabética.
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.factories.keys().map(String::as_str)
+    }
+
+    pub fn contains(&self, name: &str) -> bool {
+        self.factories.contains_key(name)
+    }
+
+    pub fn create(&self, name: &str) -> Result<RegisteredScalar, ConfigError> {
+        match self.factories.get(name) {
+            Some(factory) => Ok(factory()),
+            None => Err(ConfigError::UnknownObjective {
+                name: name.to_string(),
+                available: self.names().map(str::to_string).collect(),
+            }),
+        }
+    }
+}
+
+/// Confere `dim` contra a dimensão exigida pelo objetivo, preenchendo-a se
+/// foi omitida.
+fn resolve_dim(dim: &mut Option<usize>, expected: usize) -> Result<(), (usize, usize)> {
+    match *dim {
+        Some(found) if found != expected => Err((expected, found)),
+        Some(_) => Ok(()),
+        None => {
+            *dim = Some(expected);
+            Ok(())
+        },
+    }
+}
+
+/// Carrega o objetivo descrito em `config.objective`, conferindo-o contra o
+/// encoding configurado. Quando o encoding omite `dim`, ele é preenchido com a
+/// dimensão exigida pelo objetivo. Retorna `None` se não há objetivo.
+pub fn build_objective(config: &mut ConfigDTO) -> Result<Option<ObjectiveInstance>, ConfigError> {
+    build_objective_with_registry(config, &ObjectiveRegistry::default())
+}
+
+/// Como [`build_objective`], resolvendo objetivos `Registered` em `registry`.
+/// O tipo do objetivo na configuração decide a origem: `Sat` sempre lê o
+/// arquivo e `Registered` sempre consulta o registro, mesmo que o nome
+/// coincida com um caminho existente.
+pub fn build_objective_with_registry(config: &mut ConfigDTO, registry: &ObjectiveRegistry) -> Result<Option<ObjectiveInstance>, ConfigError> {
+    let Some(objective) = &config.objective else { return Ok(None) };
+
+    match objective {
+        ObjectiveDTO::Sat { cnf_path, score_mode } => {
+            let EncodingDTO::Binary { dim, .. } = &mut config.encoding else {
+                return Err(ConfigError::ObjectiveEncodingMismatch {
+                    objective: objective.name(),
+                    encoding: config.encoding.name(),
+                })
+            };
+
+            let formula = File::open(cnf_path)
+                .map_err(FormulaParsingError::from)
+                .and_then(Formula::parse_from_dimacs_cnf)
+                .map_err(|error| ConfigError::ObjectiveFile { path: cnf_path.clone(), error })?;
+
+            resolve_dim(dim, formula.get_num_vars() as usize).map_err(|(expected, found)| {
+                ConfigError::ObjectiveDimensionMismatch { path: cnf_path.clone(), expected, found }
+            })?;
+
+            let mode = score_mode.map(ScoreMode::from).unwrap_or_default();
+            Ok(Some(ObjectiveInstance::Sat(SATObjective::new(formula).with_mode(mode))))
+        },
+        ObjectiveDTO::Registered { name } => {
+            let instance = registry.create(name)?;
+            if instance.encoding_name() != config.encoding.name() {
+                return Err(ConfigError::RegisteredEncodingMismatch {
+                    name: name.clone(),
+                    expected: instance.encoding_name(),
+                    found: config.encoding.name(),
+                })
+            }
+
+            if let Some(required) = instance.required_dimension() {
+                let mismatch = |(expected, found)| ConfigError::RegisteredDimensionMismatch { name: name.clone(), expected, found };
+                match &mut config.encoding {
+                    EncodingDTO::Binary { dim, .. } => resolve_dim(dim, required).map_err(mismatch)?,
+                    encoding => match encoding.dim() {
+                        Some(found) if found != required => return Err(mismatch((required, found))),
+                        _ => {},
+                    },
+                }
+            }
+            Ok(Some(ObjectiveInstance::Registered { name: name.clone(), objective: instance }))
+        },
+    }
+}
+
+#[cfg(test)]
+mod objective_factory_tests {
+    use std::io::{Cursor, Write};
+    use tempfile::NamedTempFile;
+    use std::thread;
+    use crate::alg::Objective;
+    use crate::population::{BinaryPopGenerator, IntPermPopGenerator, PopGenerator, Population};
+    use super::*;
+
+    struct OneMax {
+        dim: usize,
+    }
+
+    impl ScalarObjective<BinaryEncoding> for OneMax {
+        fn eval_scalar(&self, pop: &Population<BinaryEncoding>) -> Option<Vec<f64>> {
+            Some(pop.0.iter().map(|individual| individual.to_bools().iter().filter(|&&bit| bit).count() as f64).collect())
+        }
+
+        fn required_dimension(&self) -> Option<usize> {
+            Some(self.dim)
+        }
+    }
+
+    fn one_max_registry(name: &str, dim: usize) -> ObjectiveRegistry {
+        let mut registry = ObjectiveRegistry::new();
+        registry.register(name, move || OneMax { dim });
+        registry
+    }
+
+    fn registered_config(name: &str) -> ConfigDTO {
+        let config_json = format!(r#"{{
+            "encoding": {{"type": "Binary"}},
+            "objective": {{"type": "Registered", "name": {:?}}},
+            "pop_size": 8
+        }}"#, name);
+        ConfigDTO::from_reader(Cursor::new(config_json)).unwrap()
+    }
+
+    fn cnf_file() -> NamedTempFile {
+        let mut file = NamedTempFile::new().unwrap();
+        write!(file, "p cnf 3 2\n1 -3 0\n2 3 0\n%\n").unwrap();
+        file
+    }
+
+    fn sat_config(file: &NamedTempFile, dim: Option<usize>) -> ConfigDTO {
+        let config_json = format!(r#"{{
+            "encoding": {{"type": "Binary"{}}},
+            "objective": {{"type": "Sat", "cnf_path": {:?}}},
+            "pop_size": 8,
+            "runs": 1,
+            "generations": 10
+        }}"#,
+            dim.map(|dim| format!(r#", "dim": {}"#, dim)).unwrap_or_default(),
+            file.path().to_str().unwrap());
+        ConfigDTO::from_reader(Cursor::new(config_json)).unwrap()
+    }
+
+    #[test]
+    fn test_sat_objective_derives_dim() {
+        let file = cnf_file();
+        let mut config = sat_config(&file, None);
+        assert_eq!(config.validate(), Ok(()));
+
+        let Some(ObjectiveInstance::Sat(objective)) = build_objective(&mut config).unwrap() else {
+            panic!("Expected a SAT objective")
+        };
+        assert_eq!(config.encoding, EncodingDTO::Binary { dim: Some(3), density: None });
+
+        let population = BinaryPopGenerator::try_from(&config).unwrap().gen_pop();
+        let scores = objective.eval(&population).unwrap();
+        assert_eq!(scores.len(), 8);
+        assert!(scores.iter().all(|&score| score <= 2));
+    }
+
+    #[test]
+    fn test_omitted_dim_builds_sized_generator() {
+        use crate::alg::{GeneticAlgorithm, IdentityFitness};
+
+        let file = cnf_file();
+        let config = sat_config(&file, None);
+        assert_eq!(config.encoding.dim(), None);
+
+        // sem passar por `build_objective`, o gerador fica sem dimensão
+        let generator = BinaryPopGenerator::try_from(&config).unwrap();
+        assert!(!generator.has_dimension());
+
+        let objective = File::open(file.path()).map(|file| Formula::parse_from_dimacs_cnf(file).unwrap()).unwrap();
+        let ga = GeneticAlgorithm::new(SATObjective::new(objective), IdentityFitness, generator, 1, 10).unwrap();
+        assert_eq!(ga.pop_generator().dimension(), 3);
+    }
+
+    #[test]
+    fn test_sat_score_mode_from_config() {
+        let file = cnf_file();
+        let config_json = format!(
+            r#"{{"encoding": {{"type": "Binary"}}, "objective": {{"type": "Sat", "cnf_path": {:?}, "score_mode": "LogLengthWeighted"}}}}"#,
+            file.path().to_str().unwrap());
+        let mut config = ConfigDTO::from_reader(Cursor::new(config_json)).unwrap();
+        let Some(ObjectiveInstance::Sat(objective)) = build_objective(&mut config).unwrap() else {
+            panic!("Expected a SAT objective")
+        };
+        assert_eq!(objective.mode(), ScoreMode::LogLengthWeighted);
+
+        let Some(ObjectiveInstance::Sat(objective)) = build_objective(&mut sat_config(&file, None)).unwrap() else {
+            panic!("Expected a SAT objective")
+        };
+        assert_eq!(objective.mode(), ScoreMode::FalseCount);
+
+        let config_json = r#"{"encoding": {"type": "Binary"}, "objective": {"type": "Sat", "cnf_path": "f.cnf", "score_mode": "Squared"}}"#;
+        assert!(ConfigDTO::from_reader(Cursor::new(config_json)).is_err());
+    }
+
+    #[test]
+    fn test_sat_objective_dimension_mismatch() {
+        let file = cnf_file();
+        let mut config = sat_config(&file, Some(5));
+        match build_objective(&mut config) {
+            Err(ConfigError::ObjectiveDimensionMismatch { path, expected: 3, found: 5 }) => assert_eq!(path, file.path()),
+            Err(err) => panic!("Expected ConfigError::ObjectiveDimensionMismatch, got {:?}", err),
+            _ => panic!("Expected an error")
+        }
+    }
+
+    #[test]
+    fn test_sat_objective_encoding_mismatch() {
+        let file = cnf_file();
+        let mut config = sat_config(&file, None);
+        config.encoding = EncodingDTO::Real { dim: 3, bounds: (0.0, 1.0).into() };
+        match build_objective(&mut config) {
+            Err(ConfigError::ObjectiveEncodingMismatch { objective: "Sat", encoding: "Real" }) => {},
+            Err(err) => panic!("Expected ConfigError::ObjectiveEncodingMismatch, got {:?}", err),
+            _ => panic!("Expected an error")
+        }
+    }
+
+    #[test]
+    fn test_sat_objective_missing_file() {
+        let mut config = sat_config(&cnf_file(), None);
+        config.objective = Some(ObjectiveDTO::Sat { cnf_path: "/nonexistent/formula.cnf".into(), score_mode: None });
+        match build_objective(&mut config) {
+            Err(err @ ConfigError::ObjectiveFile { error: FormulaParsingError::IO(_), .. }) =>
+                assert!(err.to_string().contains("/nonexistent/formula.cnf")),
+            Err(err) => panic!("Expected ConfigError::ObjectiveFile, got {:?}", err),
+            _ => panic!("Expected an error")
+        }
+    }
+
+    #[test]
+    fn test_registered_objective() {
+        let registry = one_max_registry("one_max", 12);
+        let mut config = registered_config("one_max");
+        assert_eq!(config.validate(), Ok(()));
+
+        let objective = build_objective_with_registry(&mut config, &registry).unwrap().unwrap();
+        assert!(matches!(&objective, ObjectiveInstance::Registered { name, .. } if name == "one_max"));
+        assert_eq!(config.encoding, EncodingDTO::Binary { dim: Some(12), density: None });
+
+        // avalia uma população gerada a partir da configuração; não há laço
+        // do GA para uma execução completa
+        let population = BinaryPopGenerator::try_from(&config).unwrap().gen_pop();
+        let objective = objective.into_scalar::<BinaryEncoding>().unwrap();
+        let scores = objective.eval(&population).unwrap();
+        let expected: Vec<f64> = population.0.iter().map(|individual| individual.to_bools().iter().filter(|&&bit| bit).count() as f64).collect();
+        assert_eq!(scores, expected);
+    }
+
+    /// Comprimento da rota sobre uma matriz construída pela aplicação.
+    struct TourLength {
+        distances: Vec<Vec<f64>>,
+    }
+
+    impl ScalarObjective<IntPermEncoding> for TourLength {
+        fn eval_scalar(&self, pop: &Population<IntPermEncoding>) -> Option<Vec<f64>> {
+            Some(pop.0.iter().map(|tour| {
+                (0..tour.0.len()).map(|i| self.distances[tour.0[i]][tour.0[(i + 1) % tour.0.len()]]).sum()
+            }).collect())
+        }
+
+        fn required_dimension(&self) -> Option<usize> {
+            Some(self.distances.len())
+        }
+    }
+
+    fn tour_registry() -> ObjectiveRegistry {
+        // cinco cidades numa reta, nas posições 0, 1, ..., 4
+        let distances: Vec<Vec<f64>> = (0..5).map(|i: i32| (0..5).map(|j: i32| (i - j).abs() as f64).collect()).collect();
+        let mut registry = ObjectiveRegistry::new();
+        registry.register("tour", move || TourLength { distances: distances.clone() });
+        registry
+    }
+
+    #[test]
+    fn test_registered_permutation_objective() {
+        let config_json = r#"{"encoding": {"type": "IntegerPermutation", "dim": 5}, "objective": {"type": "Registered", "name": "tour"}}"#;
+        let mut config = ConfigDTO::from_reader(Cursor::new(config_json)).unwrap();
+        let objective = build_objective_with_registry(&mut config, &tour_registry()).unwrap().unwrap();
+        assert_eq!(format!("{:?}", objective), r#"Registered { name: "tour", encoding: "IntegerPermutation", .. }"#);
+
+        let population = IntPermPopGenerator::try_from(&config).unwrap().gen_pop();
+        let scores = objective.into_scalar::<IntPermEncoding>().unwrap().eval(&population).unwrap();
+        assert_eq!(scores.len(), population.len());
+        // toda rota sobre a reta mede pelo menos ida e volta
+        assert!(scores.iter().all(|&length| length >= 8.0));
+
+        let objective = build_objective_with_registry(&mut config, &tour_registry()).unwrap().unwrap();
+        assert!(objective.into_scalar::<BinaryEncoding>().is_none());
+    }
+
+    #[test]
+    fn test_registered_encoding_and_dimension_checks() {
+        let mut config = registered_config("tour");
+        match build_objective_with_registry(&mut config, &tour_registry()) {
+            Err(err @ ConfigError::RegisteredEncodingMismatch { .. }) => assert_eq!(
+                err.to_string(),
+                "objective `tour` evaluates the IntegerPermutation encoding but the config uses Binary"
+            ),
+            Err(err) => panic!("Expected ConfigError::RegisteredEncodingMismatch, got {:?}", err),
+            _ => panic!("Expected an error")
+        }
+
+        config.encoding = EncodingDTO::IntegerPermutation { dim: 6 };
+        match build_objective_with_registry(&mut config, &tour_registry()) {
+            Err(ConfigError::RegisteredDimensionMismatch { expected: 5, found: 6, .. }) => {},
+            Err(err) => panic!("Expected ConfigError::RegisteredDimensionMismatch, got {:?}", err),
+            _ => panic!("Expected an error")
+        }
+    }
+
+    #[test]
+    fn test_unknown_registered_objective() {
+        let mut registry = one_max_registry("one_max", 12);
+        registry.register("leading_ones", || OneMax { dim: 4 });
+        match build_objective_with_registry(&mut registered_config("onemax"), &registry) {
+            Err(err @ ConfigError::UnknownObjective { .. }) => {
+                let ConfigError::UnknownObjective { name, available } = &err else { unreachable!() };
+                assert_eq!(name, "onemax");
+                assert_eq!(available, &["leading_ones", "one_max"]);
+                assert!(err.to_string().contains("available: `leading_ones`, `one_max`"), "{}", err);
+            },
+            Err(err) => panic!("Expected ConfigError::UnknownObjective, got {:?}", err),
+            _ => panic!("Expected an error")
+        }
+
+        // sem registro, `build_objective` não conhece nenhum nome
+        let err = build_objective(&mut registered_config("one_max")).unwrap_err();
+        assert!(err.to_string().contains("registry is empty"), "{}", err);
+    }
+
+    #[test]
+    fn test_registered_dimension_mismatch() {
+        let registry = one_max_registry("one_max", 12);
+        let mut config = registered_config("one_max");
+        config.encoding = EncodingDTO::Binary { dim: Some(10), density: None };
+        match build_objective_with_registry(&mut config, &registry) {
+            Err(ConfigError::RegisteredDimensionMismatch { name, expected: 12, found: 10 }) => assert_eq!(name, "one_max"),
+            Err(err) => panic!("Expected ConfigError::RegisteredDimensionMismatch, got {:?}", err),
+            _ => panic!("Expected an error")
+        }
+    }
+
+    #[test]
+    fn test_objective_type_decides_between_file_and_registry() {
+        // o nome registrado coincide com o caminho de uma fórmula existente
+        let file = cnf_file();
+        let path = file.path().to_str().unwrap();
+        let registry = one_max_registry(path, 7);
+
+        let mut config = registered_config(path);
+        assert!(matches!(build_objective_with_registry(&mut config, &registry), Ok(Some(ObjectiveInstance::Registered { .. }))));
+        assert_eq!(config.encoding.dim(), Some(7));
+
+        let mut config = sat_config(&file, None);
+        assert!(matches!(build_objective_with_registry(&mut config, &registry), Ok(Some(ObjectiveInstance::Sat(_)))));
+        assert_eq!(config.encoding.dim(), Some(3));
+    }
+
+    #[test]
+    fn test_registry_is_shared_between_threads() {
+        let registry = one_max_registry("one_max", 5);
+        let dims: Vec<Option<usize>> = (0..4)
+            .map(|_| {
+                let registry = registry.clone();
+                thread::spawn(move || {
+                    let mut config = registered_config("one_max");
+                    build_objective_with_registry(&mut config, &registry).unwrap();
+                    config.encoding.dim()
+                })
+            })
+            .collect::<Vec<_>>()
+            .into_iter()
+            .map(|handle| handle.join().unwrap())
+            .collect();
+        assert_eq!(dims, vec![Some(5); 4]);
+    }
+}
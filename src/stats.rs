@@ -0,0 +1,363 @@
+use std::cmp::Ordering;
+use std::io::{self, Write};
+
+/// Resultado do teste U de Mann–Whitney entre as amostras `a` e `b`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MannWhitney {
+    /// Estatística U da amostra `a`: pares em que `a` supera `b` (empates valem 1/2).
+    pub u: f64,
+    /// Aproximação normal com correção de empates e de continuidade.
+    pub z: f64,
+    /// p-valor bilateral.
+    pub p_value: f64,
+    /// Correlação rank-biserial em [-1, 1]; positiva quando `a` tende a ser maior.
+    pub effect_size: f64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ComparisonResult {
+    Compared(MannWhitney),
+    /// Menos de 3 observações em alguma amostra, ou todas as observações
+    /// empatadas (variância nula).
+    InsufficientData,
+}
+
+/// Tamanho mínimo de cada amostra para a aproximação normal.
+pub const MIN_SAMPLE_SIZE: usize = 3;
+
+/// Compara as amostras de duas configurações (ex. melhor score de cada
+/// execução) com o teste U de Mann–Whitney.
+pub fn compare_runs(a: &[f64], b: &[f64]) -> ComparisonResult {
+    let (n1, n2) = (a.len(), b.len());
+    if n1 < MIN_SAMPLE_SIZE || n2 < MIN_SAMPLE_SIZE
+    { return ComparisonResult::InsufficientData }
+
+    let mut values: Vec<(f64, bool)> = a.iter().map(|&v| (v, true)).chain(b.iter().map(|&v| (v, false))).collect();
+    values.sort_by(|(x, _), (y, _)| x.total_cmp(y));
+
+    // postos médios por grupo de empates
+    let mut rank_sum_a = 0.0;
+    let mut tie_term = 0.0;
+    let mut start = 0;
+    while start < values.len() {
+        let end = start + values[start..].iter().take_while(|(v, _)| v.total_cmp(&values[start].0) == Ordering::Equal).count();
+        let ties = (end - start) as f64;
+        let rank = (start + 1 + end) as f64 / 2.0;
+        rank_sum_a += rank * values[start..end].iter().filter(|(_, in_a)| *in_a).count() as f64;
+        tie_term += ties.powi(3) - ties;
+        start = end;
+    }
+
+    let (n1, n2, n) = (n1 as f64, n2 as f64, (n1 + n2) as f64);
+    let u = rank_sum_a - n1 * (n1 + 1.0) / 2.0;
+    let variance = n1 * n2 / 12.0 * ((n + 1.0) - tie_term / (n * (n - 1.0)));
+    if variance <= 0.0
+    { return ComparisonResult::InsufficientData }
+
+    let deviation = u - n1 * n2 / 2.0;
+    let z = deviation.signum() * (deviation.abs() - 0.5).max(0.0) / variance.sqrt();
+
+    ComparisonResult::Compared(MannWhitney {
+        u,
+        z,
+        p_value: erfc(z.abs() / std::f64::consts::SQRT_2).min(1.0),
+        effect_size: 2.0 * u / (n1 * n2) - 1.0,
+    })
+}
+
+/// Função erro complementar (Numerical Recipes, erro relativo < 1.2e-7).
+fn erfc(x: f64) -> f64 {
+    let z = x.abs();
+    let t = 1.0 / (1.0 + 0.5 * z);
+    let poly = -z * z - 1.26551223 + t * (1.00002368 + t * (0.37409196 + t * (0.09678418
+        + t * (-0.18628806 + t * (0.27886807 + t * (-1.13520398 + t * (1.48851587
+        + t * (-0.82215223 + t * 0.17087277))))))));
+    let result = t * poly.exp();
+    if x >= 0.0 { result } else { 2.0 - result }
+}
+
+/// Distribuição, a cada geração, do melhor score até então entre execuções.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConvergenceCurves {
+    /// Quantis reportados, em [0, 1].
+    pub quantiles: Vec<f64>,
+    pub points: Vec<ConvergencePoint>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConvergencePoint {
+    pub generation: usize,
+    pub mean: f64,
+    pub median: f64,
+    /// Um valor por entrada de [`ConvergenceCurves::quantiles`].
+    pub quantiles: Vec<f64>,
+    /// Execuções que ainda não tinham terminado nesta geração.
+    pub active_runs: usize,
+}
+
+/// Agrega o histórico de melhor-até-então de cada execução (um valor por
+/// geração). Execuções que terminaram cedo têm o último valor repetido nas
+/// gerações seguintes. Históricos vazios são ignorados.
+pub fn convergence_curves(histories: &[Vec<f64>], quantiles: &[f64]) -> ConvergenceCurves {
+    let histories: Vec<&Vec<f64>> = histories.iter().filter(|history| !history.is_empty()).collect();
+    let generations = histories.iter().map(|history| history.len()).max().unwrap_or(0);
+
+    let points = (0..generations)
+        .map(|generation| {
+            let mut values: Vec<f64> = histories
+                .iter()
+                .map(|history| history[generation.min(history.len() - 1)])
+                .collect();
+            values.sort_by(f64::total_cmp);
+
+            ConvergencePoint {
+                generation,
+                mean: values.iter().sum::<f64>() / values.len() as f64,
+                median: quantile(&values, 0.5),
+                quantiles: quantiles.iter().map(|&q| quantile(&values, q)).collect(),
+                active_runs: histories.iter().filter(|history| history.len() > generation).count(),
+            }
+        })
+        .collect();
+
+    ConvergenceCurves { quantiles: quantiles.to_vec(), points }
+}
+
+/// Quantil com interpolação linear entre postos (`sorted` não vazio).
+fn quantile(sorted: &[f64], q: f64) -> f64 {
+    let position = q.clamp(0.0, 1.0) * (sorted.len() - 1) as f64;
+    let (lower, upper) = (position.floor() as usize, position.ceil() as usize);
+    sorted[lower] + (sorted[upper] - sorted[lower]) * (position - lower as f64)
+}
+
+impl ConvergenceCurves {
+    /// Escreve as curvas em CSV, uma linha por geração.
+    pub fn write_csv<W: Write>(&self, mut writer: W) -> io::Result<()> {
+        write!(writer, "generation,mean,median")?;
+        for q in &self.quantiles {
+            write!(writer, ",q{}", q * 100.0)?;
+        }
+        writeln!(writer, ",active_runs")?;
+
+        for point in &self.points {
+            write!(writer, "{},{},{}", point.generation, point.mean, point.median)?;
+            for value in &point.quantiles {
+                write!(writer, ",{}", value)?;
+            }
+            writeln!(writer, ",{}", point.active_runs)?;
+        }
+        Ok(())
+    }
+}
+
+/// Quantis reportados em [`ScoreDistribution::quantiles`].
+pub const SUMMARY_QUANTILES: [f64; 4] = [0.25, 0.5, 0.75, 0.95];
+
+/// Bordas fixas de um histograma, crescentes. Valores fora do intervalo
+/// contam no primeiro ou no último bin.
+#[derive(Debug, Clone, PartialEq)]
+pub struct HistogramBins {
+    pub edges: Vec<f64>,
+}
+
+impl HistogramBins {
+    /// `bins` bins de mesma largura em `[lower, upper]` (pelo menos um).
+    pub fn uniform(lower: f64, upper: f64, bins: usize) -> Self {
+        let bins = bins.max(1);
+        let width = (upper - lower) / bins as f64;
+        HistogramBins { edges: (0..=bins).map(|i| lower + width * i as f64).collect() }
+    }
+
+    /// Bins uniformes sobre o intervalo dos scores dados (ex. os da primeira
+    /// geração); `None` se não há scores.
+    pub fn from_range(scores: &[f64], bins: usize) -> Option<Self> {
+        let lower = scores.iter().copied().min_by(f64::total_cmp)?;
+        let upper = scores.iter().copied().max_by(f64::total_cmp)?;
+        Some(Self::uniform(lower, upper, bins))
+    }
+
+    pub fn count(&self, scores: &[f64]) -> Vec<usize> {
+        let bins = self.edges.len().saturating_sub(1).max(1);
+        let mut counts = vec![0; bins];
+        for score in scores {
+            let bin = self.edges.partition_point(|edge| edge <= score).saturating_sub(1).min(bins - 1);
+            counts[bin] += 1;
+        }
+        counts
+    }
+}
+
+/// Resumo da distribuição dos scores de uma geração.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ScoreDistribution {
+    pub mean: f64,
+    pub std_dev: f64,
+    /// Assimetria amostral (momento padronizado de ordem 3); zero sem variância.
+    pub skewness: f64,
+    /// Um valor por entrada de [`SUMMARY_QUANTILES`].
+    pub quantiles: Vec<f64>,
+    /// Contagens por bin, quando há bins configurados.
+    pub histogram: Option<Vec<usize>>,
+}
+
+impl ScoreDistribution {
+    /// `None` se não há scores. Os scores são ordenados uma única vez.
+    pub fn new(scores: &[f64], bins: Option<&HistogramBins>) -> Option<Self> {
+        if scores.is_empty()
+        { return None }
+
+        let n = scores.len() as f64;
+        let mean = scores.iter().sum::<f64>() / n;
+        let m2 = scores.iter().map(|score| (score - mean).powi(2)).sum::<f64>() / n;
+        let m3 = scores.iter().map(|score| (score - mean).powi(3)).sum::<f64>() / n;
+
+        let mut sorted = scores.to_vec();
+        sorted.sort_by(f64::total_cmp);
+
+        Some(ScoreDistribution {
+            mean,
+            std_dev: m2.sqrt(),
+            skewness: if m2 > 0.0 { m3 / m2.powf(1.5) } else { 0.0 },
+            quantiles: SUMMARY_QUANTILES.iter().map(|&q| quantile(&sorted, q)).collect(),
+            histogram: bins.map(|bins| bins.count(scores)),
+        })
+    }
+}
+
+#[cfg(test)]
+mod stats_tests {
+    use super::*;
+
+    fn assert_close(actual: f64, expected: f64) {
+        assert!((actual - expected).abs() < 1e-6, "expected {}, got {}", expected, actual);
+    }
+
+    fn compared(a: &[f64], b: &[f64]) -> MannWhitney {
+        match compare_runs(a, b) {
+            ComparisonResult::Compared(result) => result,
+            ComparisonResult::InsufficientData => panic!("Expected a comparison"),
+        }
+    }
+
+    #[test]
+    fn test_mann_whitney_reference_values() {
+        let result = compared(&[1.1, 2.3, 3.5, 4.2, 5.9, 6.0], &[7.4, 8.8, 9.1, 3.3, 10.5, 11.2, 12.0]);
+        assert_close(result.u, 4.0);
+        assert_close(result.z, -2.357142857);
+        assert_close(result.p_value, 0.018416161);
+        assert_close(result.effect_size, -0.809523810);
+    }
+
+    #[test]
+    fn test_mann_whitney_ties() {
+        let result = compared(&[1.0, 2.0, 2.0, 3.0, 3.0, 3.0, 4.0], &[3.0, 4.0, 4.0, 5.0, 5.0, 6.0]);
+        assert_close(result.u, 3.5);
+        assert_close(result.z, -2.483773283);
+        assert_close(result.p_value, 0.012999854);
+    }
+
+    #[test]
+    fn test_mann_whitney_effect_size_sign() {
+        let a = [10.0, 11.0, 12.0, 13.0];
+        let b = [1.0, 2.0, 3.0, 4.0];
+        let forward = compared(&a, &b);
+        let backward = compared(&b, &a);
+        assert_close(forward.effect_size, 1.0);
+        assert_close(backward.effect_size, -1.0);
+        assert_close(forward.p_value, 0.030382822);
+        assert_close(forward.p_value, backward.p_value);
+        assert!(forward.z > 0.0 && backward.z < 0.0);
+    }
+
+    #[test]
+    fn test_mann_whitney_insufficient_data() {
+        assert_eq!(compare_runs(&[1.0, 2.0], &[3.0, 4.0, 5.0]), ComparisonResult::InsufficientData);
+        assert_eq!(compare_runs(&[1.0; 4], &[1.0; 5]), ComparisonResult::InsufficientData);
+    }
+
+    #[test]
+    fn test_mann_whitney_identical_samples() {
+        let result = compared(&[1.0, 2.0, 3.0], &[1.0, 2.0, 3.0]);
+        assert_close(result.z, 0.0);
+        assert_close(result.p_value, 1.0);
+        assert_close(result.effect_size, 0.0);
+    }
+
+    #[test]
+    fn test_convergence_curves_unequal_lengths() {
+        let histories = vec![
+            vec![5.0, 3.0, 1.0, 0.0],
+            vec![6.0, 4.0],
+            vec![4.0, 4.0, 2.0],
+        ];
+        let curves = convergence_curves(&histories, &[0.1, 0.9]);
+        assert_eq!(curves.points.len(), 4);
+
+        let active: Vec<usize> = curves.points.iter().map(|point| point.active_runs).collect();
+        assert_eq!(active, vec![3, 3, 2, 1]);
+
+        // geração 3: [0.0, 4.0 (repetido), 2.0 (repetido)]
+        let last = &curves.points[3];
+        assert_close(last.mean, 2.0);
+        assert_close(last.median, 2.0);
+        assert_close(last.quantiles[0], 0.4);
+        assert_close(last.quantiles[1], 3.6);
+
+        let first = &curves.points[0];
+        assert_close(first.mean, 5.0);
+        assert_close(first.median, 5.0);
+    }
+
+    #[test]
+    fn test_convergence_curves_empty() {
+        let curves = convergence_curves(&[vec![], vec![]], &[0.5]);
+        assert!(curves.points.is_empty());
+    }
+
+    #[test]
+    fn test_convergence_curves_csv() {
+        let curves = convergence_curves(&[vec![2.0, 1.0], vec![4.0]], &[0.1, 0.9]);
+        let mut output = Vec::new();
+        curves.write_csv(&mut output).unwrap();
+        assert_eq!(
+            String::from_utf8(output).unwrap(),
+            "generation,mean,median,q10,q90,active_runs\n0,3,3,2.2,3.8,2\n1,2.5,2.5,1.3,3.7,1\n"
+        );
+    }
+
+    #[test]
+    fn test_histogram_counts_clamp_outliers() {
+        let bins = HistogramBins::uniform(0.0, 10.0, 5);
+        assert_eq!(bins.edges, vec![0.0, 2.0, 4.0, 6.0, 8.0, 10.0]);
+        // bordas internas pertencem ao bin da direita; 10 e os valores fora
+        // do intervalo vão para os bins das pontas
+        let scores = [-3.0, 0.0, 1.9, 2.0, 2.0, 5.0, 9.9, 10.0, 42.0];
+        assert_eq!(bins.count(&scores), vec![3, 2, 1, 0, 3]);
+
+        let degenerate = HistogramBins::from_range(&[4.0, 4.0], 3).unwrap();
+        assert_eq!(degenerate.count(&[4.0, 4.0, 5.0]), vec![0, 0, 3]);
+        assert_eq!(HistogramBins::from_range(&[], 3), None);
+    }
+
+    #[test]
+    fn test_score_distribution() {
+        let scores = [1.0, 2.0, 2.0, 3.0, 3.0, 3.0, 4.0, 10.0];
+        let bins = HistogramBins::from_range(&scores, 3).unwrap();
+        let summary = ScoreDistribution::new(&scores, Some(&bins)).unwrap();
+
+        assert_close(summary.mean, 3.5);
+        // m2 = 6.75, m3 = 31.5
+        assert_close(summary.std_dev, 2.598076211);
+        assert_close(summary.skewness, 1.796200837);
+        assert_eq!(summary.quantiles.len(), SUMMARY_QUANTILES.len());
+        assert_close(summary.quantiles[0], 2.0);
+        assert_close(summary.quantiles[1], 3.0);
+        assert_close(summary.quantiles[2], 3.25);
+        assert_close(summary.quantiles[3], 7.9);
+        assert_eq!(summary.histogram, Some(vec![6, 1, 1]));
+
+        let constant = ScoreDistribution::new(&[2.0; 4], None).unwrap();
+        assert_eq!((constant.std_dev, constant.skewness, constant.histogram), (0.0, 0.0, None));
+        assert_eq!(ScoreDistribution::new(&[], None), None);
+    }
+}
@@ -0,0 +1,59 @@
+#![cfg(feature = "serde")]
+
+use std::io::Write;
+use std::process::Command;
+use tempfile::NamedTempFile;
+
+fn alg_ev(args: &[&str]) -> std::process::Output {
+    Command::new(env!("CARGO_BIN_EXE_alg_ev")).args(args).output().unwrap()
+}
+
+fn temp_file(suffix: &str, contents: &str) -> NamedTempFile {
+    let mut file = tempfile::Builder::new().suffix(suffix).tempfile().unwrap();
+    file.write_all(contents.as_bytes()).unwrap();
+    file
+}
+
+#[test]
+fn test_validate_valid_config() {
+    let cnf = temp_file(".cnf", "p cnf 3 2\n1 -3 0\n2 3 0\n%\n");
+    let config = temp_file(".json", &format!(
+        r#"{{"encoding": {{"type": "Binary"}}, "objective": {{"type": "Sat", "cnf_path": {:?}}}}}"#,
+        cnf.path().to_str().unwrap()
+    ));
+    let output = alg_ev(&["validate", "--config", config.path().to_str().unwrap()]);
+    assert_eq!(output.status.code(), Some(0));
+    assert!(String::from_utf8_lossy(&output.stdout).contains("ok"));
+}
+
+#[test]
+fn test_validate_invalid_config() {
+    let config = temp_file(".json", r#"{"encoding": {"type": "Binary", "dim": 10}, "popsize": 0}"#);
+    let output = alg_ev(&["validate", "--config", config.path().to_str().unwrap()]);
+    assert_eq!(output.status.code(), Some(2));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("pop_size"));
+
+    let config = temp_file(".json", r#"{"encoding": {"type": "Binary", "dim": 10}, "pop_size": 0}"#);
+    let output = alg_ev(&["validate", "--config", config.path().to_str().unwrap()]);
+    assert_eq!(output.status.code(), Some(2));
+}
+
+#[test]
+fn test_stats() {
+    let cnf = temp_file(".cnf", "p cnf 3 2\n1 -3 0\n2 3 -1 0\n%\n");
+    let output = alg_ev(&["stats", "--cnf", cnf.path().to_str().unwrap()]);
+    assert_eq!(output.status.code(), Some(0));
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("variables: 3"));
+    assert!(stdout.contains("clauses: 2"));
+
+    let output = alg_ev(&["stats", "--cnf", "/nonexistent/formula.cnf"]);
+    assert_eq!(output.status.code(), Some(3));
+}
+
+#[test]
+fn test_usage_error() {
+    let output = alg_ev(&["frobnicate"]);
+    assert_eq!(output.status.code(), Some(2));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("usage"));
+}
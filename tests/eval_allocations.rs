@@ -0,0 +1,68 @@
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+use std::io::Cursor;
+use std::sync::Arc;
+use gen_alg::alg::objectives::*;
+use gen_alg::population::*;
+
+/// Conta as alocações feitas pela thread atual enquanto `COUNTING` está ligado.
+struct CountingAllocator;
+
+thread_local! {
+    static COUNTING: Cell<bool> = const { Cell::new(false) };
+    static ALLOCATIONS: Cell<usize> = const { Cell::new(0) };
+}
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        if COUNTING.with(Cell::get) {
+            ALLOCATIONS.with(|count| count.set(count.get() + 1));
+        }
+        unsafe { System.alloc(layout) }
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        unsafe { System.dealloc(ptr, layout) }
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+fn allocations(f: impl FnOnce()) -> usize {
+    ALLOCATIONS.with(|count| count.set(0));
+    COUNTING.with(|counting| counting.set(true));
+    f();
+    COUNTING.with(|counting| counting.set(false));
+    ALLOCATIONS.with(Cell::get)
+}
+
+#[test]
+fn test_evaluation_does_not_allocate() {
+    let dimacs_cnf =
+        r#"p cnf 5 6
+        1 -3 5 0
+        2 3 0
+        -1 -2 -4 0
+        4 5 0
+        -5 1 0
+        3 -4 2 0
+        %"#;
+    let formula = Arc::new(Formula::parse_from_dimacs_cnf(Cursor::new(dimacs_cnf)).unwrap());
+    let pop = BinaryPopGenerator::new(5, 64).gen_pop();
+    let ind = &pop.get_individuals()[0];
+    assert_eq!(allocations(|| { formula.evaluate(ind).unwrap(); }), 0);
+
+    // `FalseCount` não guarda pesos e segue outro caminho em `weighted_score`
+    for mode in [ScoreMode::FalseCount, ScoreMode::LengthWeighted] {
+        let objective = SATObjective::shared(formula.clone()).with_mode(mode);
+        assert_eq!(allocations(|| { objective.weighted_score(ind).unwrap(); }), 0, "{:?}", mode);
+
+        // o buffer reaproveitado entre gerações não realoca
+        let mut scores = Vec::new();
+        objective.eval_into(&pop, &mut scores).unwrap();
+        let first = scores.clone();
+        assert_eq!(allocations(|| objective.eval_into(&pop, &mut scores).unwrap()), 0, "{:?}", mode);
+        assert_eq!(scores, first);
+    }
+}
@@ -0,0 +1,22 @@
+use std::thread;
+use gen_alg::permutation_metrics::{default_metric, set_default_metric, PermutationMetric};
+use gen_alg::population::{Encoding, IntPermEncoding};
+
+// Único teste do binário: a métrica padrão é global ao processo.
+#[test]
+fn test_default_metric_is_shared_between_threads() {
+    let a = IntPermEncoding(vec![0, 1, 2, 3]);
+    let b = IntPermEncoding(vec![3, 2, 1, 0]);
+    assert_eq!(default_metric(), PermutationMetric::Mismatch);
+    assert_eq!(a.distance(&b), 4.0);
+
+    set_default_metric(PermutationMetric::KendallTau);
+    let (a2, b2) = (a.clone(), b.clone());
+    assert_eq!(thread::spawn(move || a2.distance(&b2)).join().unwrap(), 6.0);
+
+    set_default_metric(PermutationMetric::Cayley);
+    assert_eq!(a.distance(&b), 2.0);
+
+    set_default_metric(PermutationMetric::Mismatch);
+    assert_eq!(a.distance(&b), 4.0);
+}
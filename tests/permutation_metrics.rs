@@ -0,0 +1,27 @@
+use gen_alg::alg::niching::{speciate, speciate_with, Clearing};
+use gen_alg::permutation_metrics::PermutationMetric;
+use gen_alg::population::{IntPermEncoding, Population};
+
+fn kendall(a: &IntPermEncoding, b: &IntPermEncoding) -> f64 {
+    PermutationMetric::KendallTau.distance(a, b).expect("permutations of the same elements") as f64
+}
+
+// Uma rotação troca todas as posições, mas mantém quase toda a ordem
+// relativa: as métricas discordam e a escolha muda os nichos.
+#[test]
+fn test_metric_is_chosen_per_call() {
+    let pop = Population(vec![
+        IntPermEncoding(vec![0, 1, 2, 3, 4, 5]),
+        IntPermEncoding(vec![1, 2, 3, 4, 5, 0]),
+    ]);
+
+    assert_eq!(speciate(&pop, &[], 3.0).len(), 2);
+    assert_eq!(speciate_with(&pop, &[], 6.0, kendall).len(), 1);
+
+    let clearing = Clearing { sigma: 6.0, kappa: 1 };
+    let fitness = [2.0, 1.0];
+    assert_eq!(clearing.apply(&pop, &fitness).niches, vec![0, 1]);
+    let result = clearing.apply_with(&pop, &fitness, kendall);
+    assert_eq!(result.niches, vec![0, 0]);
+    assert_eq!(result.fitness, vec![2.0, Clearing::CLEARED]);
+}
@@ -1,6 +1,10 @@
 use std::io::Cursor;
-use gen_alg::alg::{objectives::*, Objective};
+use std::sync::Arc;
+use std::thread;
+use gen_alg::alg::{objectives::*, BuildError, DimensionMismatch, Fitness, GeneticAlgorithm, IdentityFitness, NegateFitness, Objective};
+use gen_alg::alg::parallel::EvalPool;
 use gen_alg::population::*;
+use rand::{SeedableRng, rngs::StdRng};
 
 #[test]
 fn test_sat_objective_small_pop() {
@@ -13,7 +17,7 @@ fn test_sat_objective_small_pop() {
 
     let dimacs_buffer = Cursor::new(dimacs_cnf);
     let formula = Formula::parse_from_dimacs_cnf(dimacs_buffer).unwrap();
-    let objective = SATObjective { formula };
+    let objective = SATObjective::new(formula);
 
     let individuals: Vec<BinaryEncoding> = [
         vec![true, true, false],
@@ -21,10 +25,144 @@ fn test_sat_objective_small_pop() {
         vec![false, false, false],
         vec![true, true, true]
     ].into_iter()
-        .map(|val| BinaryEncoding(val))
+        .map(BinaryEncoding::from)
         .collect();
 
     let population = Population(individuals);
     let scores = objective.eval(&population).unwrap();
     assert_eq!(scores, vec![0, 1, 2, 0]);
-}
\ No newline at end of file
+}
+
+#[test]
+fn test_sat_objective_parallel_matches_serial() {
+    let dimacs_cnf =
+        r#"p cnf 5 6
+        1 -3 0
+        2 3 -5 0
+        1 2 0
+        -1 4 0
+        -4 5 -2 0
+        3 -5 0
+        %"#;
+
+    let formula = Formula::parse_from_dimacs_cnf(Cursor::new(dimacs_cnf)).unwrap();
+    let objective = SATObjective::new(formula);
+    let population = BinaryPopGenerator::new(5, 500).gen_pop_with(&mut StdRng::seed_from_u64(21));
+
+    let serial = objective.eval(&population).unwrap();
+    for threads in [None, Some(1), Some(3)] {
+        let pool = EvalPool::new(threads).unwrap();
+        assert_eq!(objective.eval_parallel(&population, &pool).unwrap(), serial);
+        // o mesmo pool serve para várias gerações
+        assert_eq!(objective.eval_parallel(&population, &pool).unwrap(), serial);
+    }
+}
+
+#[test]
+fn test_sat_fitness_identity_and_negate() {
+    let dimacs_cnf =
+        r#"p cnf 3 3
+        1 -3 0
+        2 3 0
+        1 2 0
+        %"#;
+
+    let formula = Formula::parse_from_dimacs_cnf(Cursor::new(dimacs_cnf)).unwrap();
+    let objective = SATObjective::new(formula);
+    let population = Population(vec![
+        BinaryEncoding::from_bools(&[true, true, false]),
+        BinaryEncoding::from_bools(&[false, false, true]),
+    ]);
+    let raw = objective.eval(&population);
+
+    assert_eq!(<IdentityFitness as Fitness<_, SATObjective>>::eval(&IdentityFitness, &raw), Some(vec![0.0, 2.0]));
+    assert_eq!(<NegateFitness as Fitness<_, SATObjective>>::eval(&NegateFitness, &raw), Some(vec![-0.0, -2.0]));
+    // uma avaliação impossível não vira uma população sem scores
+    assert_eq!(<NegateFitness as Fitness<_, SATObjective>>::eval(&NegateFitness, &None), None);
+    assert_eq!(<IdentityFitness as Fitness<_, SATObjective>>::eval(&IdentityFitness, &Some(Vec::new())), Some(Vec::new()));
+}
+
+#[test]
+fn test_genetic_algorithm_checks_objective_dimension() {
+    let dimacs_cnf =
+        r#"p cnf 3 2
+        1 -3 0
+        2 3 0
+        %"#;
+
+    let objective = SATObjective::new(Formula::parse_from_dimacs_cnf(Cursor::new(dimacs_cnf)).unwrap());
+    let result = GeneticAlgorithm::new(objective, IdentityFitness, BinaryPopGenerator::new(5, 10), 1, 10);
+    assert_eq!(result.err(), Some(BuildError::DimensionMismatch(DimensionMismatch { expected: 3, found: 5, source: "objective" })));
+
+    let objective = SATObjective::new(Formula::parse_from_dimacs_cnf(Cursor::new(dimacs_cnf)).unwrap());
+    assert!(GeneticAlgorithm::new(objective, IdentityFitness, BinaryPopGenerator::new(3, 10), 1, 10).is_ok());
+
+    // OneMax não fixa a dimensão
+    assert_eq!(OneMaxObjective.required_dimension(), None);
+    assert!(GeneticAlgorithm::new(OneMaxObjective, IdentityFitness, BinaryPopGenerator::new(7, 10), 1, 10).is_ok());
+}
+
+#[test]
+fn test_genetic_algorithm_infers_dimension_from_objective() {
+    let dimacs_cnf =
+        r#"p cnf 4 2
+        1 -3 0
+        2 4 0
+        %"#;
+
+    let objective = SATObjective::new(Formula::parse_from_dimacs_cnf(Cursor::new(dimacs_cnf)).unwrap());
+    let generator = BinaryPopGenerator::sized(10);
+    assert!(!generator.has_dimension());
+
+    let ga = GeneticAlgorithm::new(objective, IdentityFitness, generator, 1, 10).unwrap();
+    assert_eq!(ga.pop_generator().dimension(), 4);
+    let population = ga.pop_generator().gen_pop();
+    assert_eq!(population.get_individuals().len(), 10);
+    assert!(population.get_individuals().iter().all(|ind| ind.len() == 4));
+
+    // os decoradores repassam a dimensão ao gerador interno
+    let objective = SATObjective::new(Formula::parse_from_dimacs_cnf(Cursor::new(dimacs_cnf)).unwrap());
+    let seeds = vec![BinaryEncoding::from_bools(&[true, false, true, false])];
+    let seeded = SeededPopGenerator::new(BinaryPopGenerator::sized(10), seeds.clone()).unwrap();
+    let ga = GeneticAlgorithm::new(objective, IdentityFitness, seeded, 1, 10).unwrap();
+    assert_eq!(ga.pop_generator().dimension(), 4);
+    assert_eq!(ga.pop_generator().gen_pop().get_individuals()[0], seeds[0]);
+
+    let objective = SATObjective::new(Formula::parse_from_dimacs_cnf(Cursor::new(dimacs_cnf)).unwrap());
+    let seeded = SeededPopGenerator::new(BinaryPopGenerator::sized(10), vec![BinaryEncoding::from_bools(&[true; 3])]).unwrap();
+    let result = GeneticAlgorithm::new(objective, IdentityFitness, seeded, 1, 10);
+    assert_eq!(result.err(), Some(BuildError::Generator(PopGeneratorError::SeedDimensionMismatch { index: 0, expected: 4, found: 3 })));
+
+    // nada de onde tirar a dimensão
+    let result = GeneticAlgorithm::new(OneMaxObjective, IdentityFitness, BinaryPopGenerator::sized(10), 1, 10);
+    assert_eq!(result.err(), Some(BuildError::MissingDimension));
+}
+
+#[test]
+fn test_sat_objective_shares_formula() {
+    let dimacs_cnf =
+        r#"p cnf 4 3
+        1 -3 0
+        2 3 -4 0
+        -1 4 0
+        %"#;
+
+    let formula = Arc::new(Formula::parse_from_dimacs_cnf(Cursor::new(dimacs_cnf)).unwrap());
+    let first = SATObjective::shared(Arc::clone(&formula));
+    let second = first.clone();
+    assert!(Arc::ptr_eq(&first.formula, &second.formula));
+    assert!(Arc::ptr_eq(&first.formula, &formula));
+
+    let population = BinaryPopGenerator::new(4, 200).gen_pop();
+    let expected = first.eval(&population).unwrap();
+    let handles: Vec<_> = (0..4)
+        .map(|_| {
+            let objective = SATObjective::shared(Arc::clone(&formula));
+            let population = population.clone();
+            thread::spawn(move || objective.eval(&population).unwrap())
+        })
+        .collect();
+    for handle in handles {
+        assert_eq!(handle.join().unwrap(), expected);
+    }
+}
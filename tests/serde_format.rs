@@ -0,0 +1,28 @@
+#![cfg(feature = "serde")]
+
+use gen_alg::population::*;
+
+// Formato fixo: se algum destes testes quebrar, o formato JSON mudou e
+// arquivos salvos por versões anteriores deixarão de ser lidos.
+
+#[test]
+fn test_binary_population_fixture() {
+    let fixture = r#"[[true, false, true], [false, false, true]]"#;
+    let population: Population<BinaryEncoding> = serde_json::from_str(fixture).unwrap();
+    assert_eq!(population, Population(vec![
+        BinaryEncoding::from(vec![true, false, true]),
+        BinaryEncoding::from(vec![false, false, true]),
+    ]));
+}
+
+#[test]
+fn test_numeric_population_fixtures() {
+    let permutations: Population<IntPermEncoding> = serde_json::from_str("[[2, 0, 1], [0, 1, 2]]").unwrap();
+    assert_eq!(permutations, Population(vec![IntPermEncoding(vec![2, 0, 1]), IntPermEncoding(vec![0, 1, 2])]));
+
+    let integers: Population<IntegerEncoding> = serde_json::from_str("[[7, 3, 10]]").unwrap();
+    assert_eq!(integers, Population(vec![IntegerEncoding(vec![7, 3, 10])]));
+
+    let reals: Population<RealEncoding> = serde_json::from_str("[[0.5, -1.25]]").unwrap();
+    assert_eq!(reals, Population(vec![RealEncoding(vec![0.5, -1.25])]));
+}